@@ -57,6 +57,11 @@ pub fn theme() -> conrod_core::Theme {
         widget_styling: conrod_core::theme::StyleMap::default(),
         mouse_drag_threshold: 0.0,
         double_click_threshold: std::time::Duration::from_millis(500),
+        tooltip_delay: std::time::Duration::from_millis(600),
+        long_press_threshold: std::time::Duration::from_millis(500),
+        transition_duration: std::time::Duration::from_millis(150),
+        caret_blink_interval: std::time::Duration::from_millis(530),
+        key_bindings: conrod_core::input::KeyBindings::default(),
     }
 }
 
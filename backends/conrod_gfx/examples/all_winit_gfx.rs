@@ -64,6 +64,7 @@ fn main() {
     // Create Ui and Ids of widgets to instantiate
     let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64])
         .theme(conrod_example_shared::theme())
+        .scale_factor(window.get_hidpi_factor())
         .build();
     let ids = conrod_example_shared::Ids::new(ui.widget_id_generator());
 
@@ -41,6 +41,9 @@ pub struct Commands<'a> {
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
+    ///
+    /// The texture is the variant returned by `image::Map::get_for_scale` for the
+    /// `Renderer::scale_factor`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
@@ -155,6 +158,7 @@ pub struct Renderer<'a, R: Resources> {
     data: pipe::Data<R>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
+    scale_factor: f64,
 }
 
 impl<'a, R: Resources> Renderer<'a, R> {
@@ -222,9 +226,18 @@ impl<'a, R: Resources> Renderer<'a, R> {
             data,
             commands: vec![],
             vertices: vec![],
+            scale_factor: dpi_factor,
         })
     }
 
+    /// The `dpi_factor` given to the last call to `fill`, matching the `Ui`'s `scale_factor`.
+    ///
+    /// Images are drawn using the variant within the `image::Map` that is best suited to this
+    /// scale factor (see `image::Map::get_for_scale`).
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     pub fn commands(&self) -> Commands {
         let Renderer { ref commands, ref vertices, .. } = *self;
@@ -235,6 +248,9 @@ impl<'a, R: Resources> Renderer<'a, R> {
     }
 
     /// Fill the inner vertex and command buffers by translating the given `primitives`.
+    ///
+    /// The `dpi_factor` should match the `Ui`'s `scale_factor`, and is used to select the variant
+    /// of each image that is drawn.
     pub fn fill<P, C>(&mut self,
                       encoder: &mut gfx::Encoder<R, C>,
                       dims: (f32, f32),
@@ -245,6 +261,7 @@ impl<'a, R: Resources> Renderer<'a, R> {
         where P: render::PrimitiveWalker,
               C: gfx::CommandBuffer<R>,
     {
+        self.scale_factor = dpi_factor;
         let Renderer { ref mut commands, ref mut vertices, ref mut glyph_cache, ref mut cache_tex, .. } = *self;
 
         commands.clear();
@@ -486,7 +503,8 @@ impl<'a, R: Resources> Renderer<'a, R> {
 
                     let color = color.unwrap_or(color::WHITE).to_fsa();
 
-                    let (image_w, image_h) = image_map.get(&image_id).unwrap().1;
+                    let (image, image_scale) = image_map.get_for_scale(image_id, dpi_factor).unwrap();
+                    let (image_w, image_h) = image.1;
                     let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
                     // Get the sides of the source rectangle as uv coordinates.
@@ -495,9 +513,14 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     // - left to right: 0.0 to 1.0
                     // - bottom to top: 1.0 to 0.0
                     // Note bottom and top are flipped in comparison to glium so that we don't need to flip images when loading
+                    //
+                    // The source rectangle is given in pixels of the original image, so it is
+                    // scaled to the pixels of the chosen variant.
                     let (uv_l, uv_r, uv_t, uv_b) = match source_rect {
                         Some(src_rect) => {
                             let (l, r, b, t) = src_rect.l_r_b_t();
+                            let (l, r) = (l * image_scale, r * image_scale);
+                            let (b, t) = (b * image_scale, t * image_scale);
                             ((l / image_w) as f32,
                              (r / image_w) as f32,
                              (b / image_h) as f32,
@@ -581,8 +604,8 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     // Draw an image whose texture data lies within the `image_map` at the
                     // given `id`.
                     Draw::Image(image_id, verts) => {
-                        let image = &image_map.get(&image_id).unwrap().0;
-                        data.color.0 = image.clone();
+                        let (image, _) = image_map.get_for_scale(image_id, self.scale_factor).unwrap();
+                        data.color.0 = image.0.clone();
                         let (vbuf, slice) = factory.create_vertex_buffer_with_slice(&verts, ());
                         data.vbuf = vbuf;
                         encoder.draw(&slice, &pipeline, &data);
@@ -27,7 +27,10 @@ fn main() {
     let display = support::GliumDisplayWinitWrapper(display);
 
     // Construct our `Ui`.
-    let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64]).theme(conrod_example_shared::theme()).build();
+    let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64])
+        .theme(conrod_example_shared::theme())
+        .scale_factor(display.0.gl_window().get_hidpi_factor())
+        .build();
 
     // The `widget::Id` of each widget instantiated in `conrod_example_shared::gui`.
     let ids = conrod_example_shared::Ids::new(ui.widget_id_generator());
@@ -103,6 +106,9 @@ fn main() {
         // Instantiate a GUI demonstrating every widget type provided by conrod.
         conrod_example_shared::gui(&mut ui.set_widgets(), &ids, &mut app);
 
+        // Make sure we're updated again if any widget is waiting on the passing of time.
+        event_loop.update_by(ui.next_redraw_deadline());
+
         // Draw the `Ui`.
         if let Some(primitives) = ui.draw_if_changed() {
             renderer.fill(&display.0, primitives, &image_map);
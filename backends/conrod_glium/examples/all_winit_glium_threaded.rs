@@ -62,6 +62,7 @@ fn main() {
 
     // A function that runs the conrod loop.
     fn run_conrod(rust_logo: conrod_core::image::Id,
+                  scale_factor: f64,
                   event_rx: std::sync::mpsc::Receiver<conrod_core::event::Input>,
                   render_tx: std::sync::mpsc::Sender<conrod_core::render::OwnedPrimitives>,
                   events_loop_proxy: glium::glutin::EventsLoopProxy)
@@ -69,6 +70,7 @@ fn main() {
         // Construct our `Ui`.
         let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64])
             .theme(conrod_example_shared::theme())
+            .scale_factor(scale_factor)
             .build();
 
         // Add a `Font` to the `Ui`'s `font::Map` from file.
@@ -137,7 +139,10 @@ fn main() {
     }
 
     // Spawn the conrod loop on its own thread.
-    std::thread::spawn(move || run_conrod(rust_logo, event_rx, render_tx, events_loop_proxy));
+    let scale_factor = display.0.gl_window().get_hidpi_factor();
+    std::thread::spawn(move || {
+        run_conrod(rust_logo, scale_factor, event_rx, render_tx, events_loop_proxy)
+    });
 
     // Run the `winit` loop.
     let mut last_update = std::time::Instant::now();
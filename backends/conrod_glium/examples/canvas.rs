@@ -27,7 +27,9 @@ fn main() {
     let display = support::GliumDisplayWinitWrapper(display);
 
     // construct our `Ui`.
-    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64]).build();
+    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64])
+        .scale_factor(display.0.gl_window().get_hidpi_factor())
+        .build();
 
     // Add a `Font` to the `Ui`'s `font::Map` from file.
     let assets = find_folder::Search::KidsThenParents(3, 5).for_folder("assets").unwrap();
@@ -25,7 +25,9 @@ fn main() {
     let display = support::GliumDisplayWinitWrapper(display);
 
     // construct our `Ui`.
-    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64]).build();
+    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64])
+        .scale_factor(display.0.gl_window().get_hidpi_factor())
+        .build();
 
     // Generate the widget identifiers.
     widget_ids!(struct Ids { canvas, counter });
@@ -24,7 +24,9 @@ fn main() {
     let display = support::GliumDisplayWinitWrapper(display);
 
     // Construct our `Ui`.
-    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64]).build();
+    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64])
+        .scale_factor(display.0.gl_window().get_hidpi_factor())
+        .build();
 
     // A unique identifier for each widget.
     widget_ids!(struct Ids { canvas, file_navigator });
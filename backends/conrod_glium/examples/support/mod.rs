@@ -23,6 +23,7 @@ impl conrod_winit::WinitWindow for GliumDisplayWinitWrapper {
 pub struct EventLoop {
     ui_needs_update: bool,
     last_update: std::time::Instant,
+    maybe_deadline: Option<std::time::Instant>,
 }
 
 impl EventLoop {
@@ -30,6 +31,7 @@ impl EventLoop {
         EventLoop {
             last_update: std::time::Instant::now(),
             ui_needs_update: true,
+            maybe_deadline: None,
         }
     }
 
@@ -48,15 +50,30 @@ impl EventLoop {
         let mut events = Vec::new();
         events_loop.poll_events(|event| events.push(event));
 
-        // If there are no events and the `Ui` does not need updating, wait for the next event.
+        // If there are no events and the `Ui` does not need updating, wait for the next event or
+        // until the `Ui`'s requested deadline, whichever comes first.
         if events.is_empty() && !self.ui_needs_update {
-            events_loop.run_forever(|event| {
-                events.push(event);
-                glium::glutin::ControlFlow::Break
-            });
+            match self.maybe_deadline {
+                Some(deadline) => loop {
+                    let now = std::time::Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    std::thread::sleep(std::cmp::min(deadline - now, sixteen_ms));
+                    events_loop.poll_events(|event| events.push(event));
+                    if !events.is_empty() {
+                        break;
+                    }
+                },
+                None => events_loop.run_forever(|event| {
+                    events.push(event);
+                    glium::glutin::ControlFlow::Break
+                }),
+            }
         }
 
         self.ui_needs_update = false;
+        self.maybe_deadline = None;
         self.last_update = std::time::Instant::now();
 
         events
@@ -70,6 +87,16 @@ impl EventLoop {
     pub fn needs_update(&mut self) {
         self.ui_needs_update = true;
     }
+
+    /// Notifies the event loop that the `Ui` requires another update by the given deadline, even
+    /// if no events arrive in the meantime.
+    ///
+    /// This should be called with `Ui::next_redraw_deadline` after each call to `set_widgets`, so
+    /// that time-dependent widgets (e.g. a `TextEdit`'s blinking cursor or a `Tooltip` waiting
+    /// for its delay) are updated without requiring further input.
+    pub fn update_by(&mut self, maybe_deadline: Option<std::time::Instant>) {
+        self.maybe_deadline = maybe_deadline;
+    }
 }
 
 // Conversion functions for converting between types from glium's version of `winit` and
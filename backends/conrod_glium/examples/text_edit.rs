@@ -30,7 +30,9 @@ fn main() {
     let display = support::GliumDisplayWinitWrapper(display);
 
     // Construct our `Ui`.
-    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64]).build();
+    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64])
+        .scale_factor(display.0.gl_window().get_hidpi_factor())
+        .build();
 
     // A unique identifier for each widget.
     let ids = Ids::new(ui.widget_id_generator());
@@ -89,6 +91,9 @@ fn main() {
         // Instantiate all widgets in the GUI.
         set_ui(ui.set_widgets(), &ids, &mut demo_text);
 
+        // Make sure we're updated again if any widget is waiting on the passing of time.
+        event_loop.update_by(ui.next_redraw_deadline());
+
         // Get the underlying winit window and update the mouse cursor as set by conrod.
         display.0.gl_window().window()
             .set_cursor(support::convert_mouse_cursor(ui.mouse_cursor()));
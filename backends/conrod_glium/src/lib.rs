@@ -29,6 +29,9 @@ pub enum Command<'a> {
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
+    ///
+    /// The texture is the variant returned by `image::Map::get_for_scale` for the
+    /// `Renderer::scale_factor`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
@@ -53,6 +56,7 @@ pub struct Renderer {
     glyph_cache: GlyphCache,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
+    scale_factor: Scalar,
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
@@ -453,9 +457,19 @@ impl Renderer {
             glyph_cache: gc,
             commands: Vec::new(),
             vertices: Vec::new(),
+            scale_factor: 1.0,
         })
     }
 
+    /// The scale factor of the display as of the last call to `fill`, matching the `Ui`'s
+    /// `scale_factor`.
+    ///
+    /// Images are drawn using the variant within the `image::Map` that is best suited to this
+    /// scale factor (see `image::Map::get_for_scale`).
+    pub fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     pub fn commands(&self) -> Commands {
         let Renderer { ref commands, ref vertices, .. } = *self;
@@ -473,7 +487,9 @@ impl Renderer {
         where P: render::PrimitiveWalker,
               T: TextureDimensions,
     {
-        let Renderer { ref mut commands, ref mut vertices, ref mut glyph_cache, .. } = *self;
+        let Renderer {
+            ref mut commands, ref mut vertices, ref mut glyph_cache, ref mut scale_factor, ..
+        } = *self;
 
         commands.clear();
         vertices.clear();
@@ -517,6 +533,7 @@ impl Renderer {
         let half_win_w = win_w / 2.0;
         let half_win_h = win_h / 2.0;
         let dpi_factor = display.gl_window().get_hidpi_factor() as Scalar;
+        *scale_factor = dpi_factor;
 
         // Functions for converting for conrod scalar coords to GL vertex coords (-1.0 to 1.0).
         let vx = |x: Scalar| (x * dpi_factor / half_win_w) as f32;
@@ -758,7 +775,7 @@ impl Renderer {
 
                     let color = color.unwrap_or(color::WHITE).to_fsa();
 
-                    if let Some(image) = image_map.get(&image_id) {
+                    if let Some((image, image_scale)) = image_map.get_for_scale(image_id, dpi_factor) {
                         let (image_w, image_h) = image.dimensions();
                         let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
@@ -767,9 +784,14 @@ impl Renderer {
                         // Texture coordinates range:
                         // - left to right: 0.0 to 1.0
                         // - bottom to top: 0.0 to 1.0
+                        //
+                        // The source rectangle is given in pixels of the original image, so it is
+                        // scaled to the pixels of the chosen variant.
                         let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
                             Some(src_rect) => {
                                 let (l, r, b, t) = src_rect.l_r_b_t();
+                                let (l, r) = (l * image_scale, r * image_scale);
+                                let (b, t) = (b * image_scale, t * image_scale);
                                 ((l / image_w) as f32,
                                  (r / image_w) as f32,
                                  (b / image_h) as f32,
@@ -867,7 +889,7 @@ impl Renderer {
                     // Only submit the vertices if there is enough for at least one triangle.
                     Draw::Image(image_id, slice) => if slice.len() >= NUM_VERTICES_IN_TRIANGLE {
                         let vertex_buffer = glium::VertexBuffer::new(facade, slice).unwrap();
-                        if let Some(image) = image_map.get(&image_id) {
+                        if let Some((image, _)) = image_map.get_for_scale(image_id, self.scale_factor) {
                             let image_uniforms = uniform! {
                                 tex: glium::uniforms::Sampler::new(image)
                                     .wrap_function(glium::uniforms::SamplerWrapFunction::Clamp)
@@ -25,9 +25,11 @@ pub fn main() {
             .build()
             .unwrap();
 
-    // construct our `Ui`.
+    // construct our `Ui`, whose scale factor is the number of pixels drawn per logical point.
+    let scale_factor = window.draw_size().width as f64 / window.size().width as f64;
     let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64])
         .theme(conrod_example_shared::theme())
+        .scale_factor(scale_factor)
         .build();
 
     // Add a `Font` to the `Ui`'s `font::Map` from file.
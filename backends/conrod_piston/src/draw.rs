@@ -69,7 +69,9 @@ pub fn primitives<'a, P, G, T, Img, C, F>(
 /// - `graphics` - The piston `Graphics` backend.
 /// - `text_texture_cache` - Some texture type `T` upon which we can cache text glyphs.
 /// - `glyph_cache` - The RustType `Cache` used to cache glyphs in our `text_texture_cache`.
-/// - `image_map` - Mappings from image widget indices to their associated image data.
+/// - `image_map` - Mappings from image widget indices to their associated image data. Each image
+///   is drawn using the variant best suited to the scale factor of the `context`'s viewport (see
+///   `image::Map::get_for_scale`).
 /// - `glyph_rectangles` - A re-usable buffer for collecting positioning rectangles for glyphs.
 /// - `cache_queue_glyphs` - A function for caching glyphs within the given texture cache.
 /// - `texture_from_image` - A function that borrows a drawable texture `T` from an `Img`. In many
@@ -186,12 +188,19 @@ pub fn primitive<'a, Img, G, T, C, F>(
         },
 
         render::PrimitiveKind::Image { image_id, color, source_rect } => {
-            if let Some(img) = image_map.get(&image_id) {
+            // The number of pixels drawn per logical point, matching the `Ui`'s `scale_factor`.
+            let scale_factor = context.viewport
+                .map(|viewport| viewport.draw_size[0] as f64 / viewport.window_size[0])
+                .unwrap_or(1.0);
+            if let Some((img, image_scale)) = image_map.get_for_scale(image_id, scale_factor) {
                 let mut image = piston_graphics::image::Image::new();
                 image.color = color.map(|c| c.to_fsa());
+                // The source rectangle is given in pixels of the original image, so it is scaled
+                // to the pixels of the chosen variant.
                 if let Some(source_rect) = source_rect {
                     let (x, y, w, h) = source_rect.x_y_w_h();
-                    image.source_rectangle = Some([x, y, w, h]);
+                    image.source_rectangle = Some([x * image_scale, y * image_scale,
+                                                   w * image_scale, h * image_scale]);
                 }
                 let (left, top, w, h) = rect.l_t_w_h();
                 image.rectangle = Some([0.0, 0.0, w, h]);
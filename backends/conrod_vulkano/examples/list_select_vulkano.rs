@@ -59,7 +59,9 @@ fn main() {
     ).unwrap();
 
     // Create Ui and Ids of widgets to instantiate
-    let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64]).build();
+    let mut ui = conrod_core::UiBuilder::new([WIN_W as f64, WIN_H as f64])
+        .scale_factor(window.surface.window().get_hidpi_factor())
+        .build();
     let ids = Ids::new(ui.widget_id_generator());
 
     // Load font from file
@@ -50,6 +50,9 @@ pub struct Commands<'a> {
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
+    ///
+    /// The texture is the variant returned by `image::Map::get_for_scale` for the
+    /// `Renderer::scale_factor`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
@@ -176,6 +179,7 @@ pub struct Renderer {
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
     tex_descs: FixedSizeDescriptorSetsPool<Arc<GraphicsPipelineAbstract + Send + Sync>>,
+    scale_factor: f64,
 }
 
 /// An command for uploading an individual glyph.
@@ -326,9 +330,18 @@ impl Renderer {
             commands: Vec::new(),
             vertices: Vec::new(),
             tex_descs,
+            scale_factor: dpi_factor,
         })
     }
 
+    /// The `dpi_factor` given to the last call to `fill`, matching the `Ui`'s `scale_factor`.
+    ///
+    /// Images are drawn using the variant within the `image::Map` that is best suited to this
+    /// scale factor (see `image::Map::get_for_scale`).
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     pub fn commands(&self) -> Commands {
         let Renderer {
@@ -347,6 +360,9 @@ impl Renderer {
     /// This method may return an `Option<GlyphCacheCommand>`, in which case the user should use
     /// the contained `glyph_cpu_buffer_pool` to write the pixel data to the GPU, and then use a
     /// `copy_buffer_to_image` command to write the data to the given `glyph_cache_texture` image.
+    ///
+    /// The `dpi_factor` should match the `Ui`'s `scale_factor`, and is used to select the variant
+    /// of each image that is drawn.
     pub fn fill<'a, P: render::PrimitiveWalker>(
         &'a mut self,
         image_map: &image::Map<Image>,
@@ -354,6 +370,7 @@ impl Renderer {
         dpi_factor: f64,
         mut primitives: P,
     ) -> Result<Option<GlyphCacheCommand<'a>>, rt::gpu_cache::CacheWriteErr> {
+        self.scale_factor = dpi_factor;
         let Renderer {
             ref mut commands,
             ref mut vertices,
@@ -622,9 +639,9 @@ impl Renderer {
                     color,
                     source_rect,
                 } => {
-                    let image_ref = match image_map.get(&image_id) {
+                    let (image_ref, image_scale) = match image_map.get_for_scale(image_id, dpi_factor) {
                         None => continue,
-                        Some(img) => img,
+                        Some(variant) => variant,
                     };
 
                     // Switch to the `Image` state for this image if we're not in it already.
@@ -663,9 +680,14 @@ impl Renderer {
                     // - bottom to top: 0.0 to 0.1
                     // Note bottom and top are flipped in comparison to glium so that we don't need
                     //  to flip images when loading
+                    //
+                    // The source rectangle is given in pixels of the original image, so it is
+                    // scaled to the pixels of the chosen variant.
                     let (uv_l, uv_r, uv_t, uv_b) = match source_rect {
                         Some(src_rect) => {
                             let (l, r, b, t) = src_rect.l_r_b_t();
+                            let (l, r) = (l * image_scale, r * image_scale);
+                            let (b, t) = (b * image_scale, t * image_scale);
                             (
                                 (l / image_w) as f32,
                                 (r / image_w) as f32,
@@ -810,7 +832,7 @@ impl Renderer {
                         if verts.len() == 0 {
                             continue;
                         }
-                        if let Some(image) = image_map.get(&image_id) {
+                        if let Some((image, _)) = image_map.get_for_scale(image_id, self.scale_factor) {
                             let desc_image = Arc::new(
                                 tex_descs
                                     .next()
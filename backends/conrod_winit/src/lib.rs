@@ -12,5 +12,8 @@ pub trait WinitWindow {
     /// Return the inner size of the window in logical pixels.
     fn get_inner_size(&self) -> Option<(u32, u32)>;
     /// Return the window's DPI factor so that we can convert from pixel values to scalar values.
+    ///
+    /// Only changes to the factor are forwarded to the `Ui` as `Input::ScaleFactor` events, so the
+    /// initial factor should be given to the `Ui` via `UiBuilder::scale_factor`.
     fn hidpi_factor(&self) -> f32;
 }
@@ -171,6 +171,11 @@ macro_rules! convert_window_event {
                 Some(conrod_core::event::Input::Resize(width as _, height as _).into())
             },
 
+            // The initial factor is given via `UiBuilder::scale_factor`.
+            winit::WindowEvent::HiDpiFactorChanged(factor) => {
+                Some(conrod_core::event::Input::ScaleFactor(factor as _).into())
+            },
+
             winit::WindowEvent::ReceivedCharacter(ch) => {
                 let string = match ch {
                     // Ignore control characters and return ascii for Text event (like sdl2).
@@ -247,6 +252,18 @@ macro_rules! convert_window_event {
                 Some(conrod_core::event::Input::Redraw)
             },
 
+            winit::WindowEvent::DroppedFile(path) => {
+                Some(conrod_core::event::Input::DroppedFile(path).into())
+            },
+
+            winit::WindowEvent::HoveredFile(path) => {
+                Some(conrod_core::event::Input::HoveredFile(path).into())
+            },
+
+            winit::WindowEvent::HoveredFileCancelled => {
+                Some(conrod_core::event::Input::HoveredFileCancelled.into())
+            },
+
             _ => None,
         }
     }};
@@ -283,6 +300,7 @@ macro_rules! convert_mouse_cursor {
             conrod_core::cursor::MouseCursor::Hand => winit::MouseCursor::Hand,
             conrod_core::cursor::MouseCursor::Grab => winit::MouseCursor::Grab,
             conrod_core::cursor::MouseCursor::Grabbing => winit::MouseCursor::Grabbing,
+            conrod_core::cursor::MouseCursor::NotAllowed => winit::MouseCursor::NotAllowed,
             conrod_core::cursor::MouseCursor::ResizeVertical => winit::MouseCursor::NsResize,
             conrod_core::cursor::MouseCursor::ResizeHorizontal => winit::MouseCursor::EwResize,
             conrod_core::cursor::MouseCursor::ResizeTopLeftBottomRight => winit::MouseCursor::NwseResize,
@@ -0,0 +1,300 @@
+//! Smooth transitions of widget properties over time.
+//!
+//! Conrod widgets are immediate-mode, so rather than describing an animation up-front, a widget
+//! simply states the *target* value of some property each time it is updated via
+//! `UiCell::animate`. Whenever the target changes, a new transition begins from the property's
+//! current value towards the new target and `UiCell::animate` yields the interpolated value for
+//! the current frame (as described by `Ui::time`).
+//!
+//! Each animated property is identified by the `widget::Id` of the widget that owns it along with
+//! the name of the property. Animations belonging to widgets that are no longer instantiated are
+//! dropped at the end of each `Ui::set_widgets` stage.
+//!
+//! While any animation is in progress, the `Ui` automatically requests that it be redrawn.
+
+use color::{self, Color};
+use fnv;
+use position::{Point, Scalar};
+use std;
+use std::any::Any;
+use widget;
+
+
+/// Values that may be smoothly interpolated between.
+pub trait Animatable: Clone + PartialEq + Send + 'static {
+    /// Linearly interpolate between `self` and `target` where `t` is within the range `0.0..=1.0`.
+    fn lerp(&self, target: &Self, t: Scalar) -> Self;
+}
+
+/// Curves that describe the rate of change of an animation over its duration.
+///
+/// See [easings.net](http://easings.net) for visual examples of each curve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// A constant rate of change.
+    Linear,
+    /// Begins slowly and accelerates.
+    QuadIn,
+    /// Begins quickly and decelerates.
+    QuadOut,
+    /// Accelerates until halfway, then decelerates.
+    QuadInOut,
+    /// Begins slowly and accelerates, more sharply than `QuadIn`.
+    CubicIn,
+    /// Begins quickly and decelerates, more sharply than `QuadOut`.
+    CubicOut,
+    /// Accelerates until halfway, then decelerates, more sharply than `QuadInOut`.
+    CubicInOut,
+    /// Accelerates then decelerates following a sine curve.
+    SineInOut,
+}
+
+/// A transition of some value from one state to another over time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Animation<T> {
+    /// The value at the beginning of the animation.
+    pub from: T,
+    /// The value at the end of the animation.
+    pub to: T,
+    /// The moment at which the animation began.
+    pub start: instant::Instant,
+    /// The duration over which the animation occurs.
+    pub duration: std::time::Duration,
+    /// The curve describing the rate of change over the duration.
+    pub easing: Easing,
+}
+
+/// Uniquely identifies an animated property.
+pub type Key = (widget::Id, &'static str);
+
+/// Stores the animations for all animated widget properties.
+pub struct Animations {
+    map: fnv::FnvHashMap<Key, Entry>,
+}
+
+/// An animation along with the moment at which it ends, so that we may check whether or not it is
+/// still running without knowing its type.
+struct Entry {
+    end: instant::Instant,
+    animation: Box<Any + Send>,
+}
+
+
+impl Easing {
+
+    /// Map the linear progress `t` (within the range `0.0..=1.0`) to the eased progress.
+    pub fn apply(self, t: Scalar) -> Scalar {
+        use std::f64::consts::PI;
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut => if t < 0.5 {
+                2.0 * t * t
+            } else {
+                -1.0 + (4.0 - 2.0 * t) * t
+            },
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let t = t - 1.0;
+                t * t * t + 1.0
+            },
+            Easing::CubicInOut => if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                let t = 2.0 * t - 2.0;
+                0.5 * t * t * t + 1.0
+            },
+            Easing::SineInOut => -0.5 * ((PI * t).cos() - 1.0),
+        }
+    }
+
+}
+
+impl<T> Animation<T>
+    where T: Animatable,
+{
+
+    /// An animation that has already settled at the given value.
+    pub fn settled(value: T, now: instant::Instant) -> Self {
+        Animation {
+            from: value.clone(),
+            to: value,
+            start: now,
+            duration: std::time::Duration::from_secs(0),
+            easing: Easing::Linear,
+        }
+    }
+
+    /// The linear progress of the animation at the moment `now` within the range `0.0..=1.0`.
+    pub fn progress(&self, now: instant::Instant) -> Scalar {
+        if self.duration == std::time::Duration::from_secs(0) {
+            return 1.0;
+        }
+        if now <= self.start {
+            return 0.0;
+        }
+        let elapsed = duration_secs(now.duration_since(self.start));
+        let duration = duration_secs(self.duration);
+        if elapsed >= duration {
+            1.0
+        } else {
+            elapsed / duration
+        }
+    }
+
+    /// The moment at which the animation ends.
+    pub fn end(&self) -> instant::Instant {
+        self.start + self.duration
+    }
+
+    /// Whether or not the animation is still in progress at the moment `now`.
+    pub fn is_running(&self, now: instant::Instant) -> bool {
+        now < self.end()
+    }
+
+    /// The value of the animation at the moment `now`.
+    pub fn value(&self, now: instant::Instant) -> T {
+        let t = self.progress(now);
+        if t >= 1.0 {
+            return self.to.clone();
+        }
+        self.from.lerp(&self.to, self.easing.apply(t))
+    }
+
+}
+
+impl Animations {
+
+    /// Construct an empty set of **Animations**.
+    pub fn new() -> Self {
+        Animations { map: fnv::FnvHashMap::default() }
+    }
+
+    /// Produce the value of the property with the given `key` at the moment `now`.
+    ///
+    /// If the `target` differs from the target of the current animation, a new animation begins
+    /// from the property's current value towards the `target` over the given `duration`. The
+    /// first time a property is animated, it settles immediately at the `target`.
+    pub fn animate<T>(&mut self,
+                      key: Key,
+                      target: T,
+                      now: instant::Instant,
+                      duration: std::time::Duration,
+                      easing: Easing) -> T
+        where T: Animatable,
+    {
+        let existing = self.map.get(&key)
+            .and_then(|entry| entry.animation.downcast_ref::<Animation<T>>())
+            .cloned();
+        let animation = match existing {
+            Some(ref animation) if animation.to == target => return animation.value(now),
+            Some(animation) => Animation {
+                from: animation.value(now),
+                to: target,
+                start: now,
+                duration: duration,
+                easing: easing,
+            },
+            None => Animation::settled(target, now),
+        };
+        let value = animation.value(now);
+        let entry = Entry { end: animation.end(), animation: Box::new(animation) };
+        self.map.insert(key, entry);
+        value
+    }
+
+    /// The animation for the property with the given `key`, if there is one of type `T`.
+    pub fn get<T>(&self, key: Key) -> Option<&Animation<T>>
+        where T: Animatable,
+    {
+        self.map.get(&key).and_then(|entry| entry.animation.downcast_ref())
+    }
+
+    /// Whether or not any animation is still in progress at the moment `now`.
+    pub fn is_running(&self, now: instant::Instant) -> bool {
+        self.map.values().any(|entry| now < entry.end)
+    }
+
+    /// Remove the animations of all widgets that are not within the given set.
+    pub fn retain_widgets(&mut self, widgets: &fnv::FnvHashSet<widget::Id>) {
+        self.map.retain(|&(id, _), _| widgets.contains(&id));
+    }
+
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Animations::new()
+    }
+}
+
+impl std::fmt::Debug for Animations {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.map.keys()).finish()
+    }
+}
+
+
+impl Animatable for Scalar {
+    fn lerp(&self, target: &Self, t: Scalar) -> Self {
+        self + (target - self) * t
+    }
+}
+
+impl Animatable for Point {
+    fn lerp(&self, target: &Self, t: Scalar) -> Self {
+        [self[0].lerp(&target[0], t), self[1].lerp(&target[1], t)]
+    }
+}
+
+impl Animatable for Color {
+    fn lerp(&self, target: &Self, t: Scalar) -> Self {
+        let color::Rgba(r, g, b, a) = self.to_rgb();
+        let color::Rgba(tr, tg, tb, ta) = target.to_rgb();
+        let t = t as f32;
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        color::rgba(lerp(r, tr), lerp(g, tg), lerp(b, tb), lerp(a, ta))
+    }
+}
+
+
+fn duration_secs(duration: std::time::Duration) -> Scalar {
+    duration.as_secs() as Scalar + duration.subsec_nanos() as Scalar * 1e-9
+}
+
+
+#[test]
+fn animations_should_transition_towards_a_changed_target() {
+    let mut animations = Animations::new();
+    let key = (widget::Id::new(0), "x");
+    let start = instant::Instant::now();
+    let second = std::time::Duration::from_secs(1);
+    let half_second = std::time::Duration::from_millis(500);
+
+    // The first value settles immediately.
+    assert_eq!(animations.animate(key, 0.0, start, second, Easing::Linear), 0.0);
+    assert!(!animations.is_running(start));
+
+    // Changing the target begins an animation from the current value.
+    assert_eq!(animations.animate(key, 10.0, start, second, Easing::Linear), 0.0);
+    assert!(animations.is_running(start));
+    assert_eq!(animations.animate(key, 10.0, start + half_second, second, Easing::Linear), 5.0);
+    assert_eq!(animations.animate(key, 10.0, start + second, second, Easing::Linear), 10.0);
+    assert!(!animations.is_running(start + second));
+
+    // Animations of widgets that no longer exist are removed.
+    animations.retain_widgets(&fnv::FnvHashSet::default());
+    assert!(animations.get::<Scalar>(key).is_none());
+}
+
+#[test]
+fn easing_curves_should_begin_at_zero_and_end_at_one() {
+    let easings = [Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut,
+                   Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut, Easing::SineInOut];
+    for &easing in easings.iter() {
+        assert!(easing.apply(0.0).abs() < 1e-9, "{:?}", easing);
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-9, "{:?}", easing);
+    }
+}
@@ -0,0 +1,72 @@
+//! The source of time used by the `Ui` for all timing related behaviour.
+//!
+//! By default the `Ui` reads the time from the system via the `SystemClock`. A `ManualClock` may
+//! be given to the `UiBuilder` instead in order to drive the `Ui` with *virtual* time. This is
+//! useful for deterministically replaying recorded input, or for testing behaviour such as
+//! double-clicks and hover delays without having to sleep.
+
+use std;
+use std::sync::{Arc, Mutex};
+
+
+/// A source of the current time.
+pub trait Clock: std::fmt::Debug + Send {
+    /// The current moment in time.
+    fn now(&self) -> instant::Instant;
+}
+
+/// A **Clock** that reads the current time from the system.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SystemClock;
+
+/// A **Clock** whose time only changes when it is explicitly set or advanced.
+///
+/// **ManualClock**s are cheap to clone and all clones share the same time. This allows one clone
+/// to be given to the `UiBuilder` while another is retained in order to advance the time.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Arc<Mutex<instant::Instant>>,
+}
+
+
+impl Clock for SystemClock {
+    fn now(&self) -> instant::Instant {
+        instant::Instant::now()
+    }
+}
+
+impl ManualClock {
+
+    /// Construct a new **ManualClock** starting at the current system time.
+    pub fn new() -> Self {
+        ManualClock::starting_at(instant::Instant::now())
+    }
+
+    /// Construct a new **ManualClock** starting at the given moment.
+    pub fn starting_at(now: instant::Instant) -> Self {
+        ManualClock { now: Arc::new(Mutex::new(now)) }
+    }
+
+    /// Set the current time of the clock.
+    pub fn set(&self, now: instant::Instant) {
+        *self.now.lock().expect("failed to lock ManualClock") = now;
+    }
+
+    /// Move the current time of the clock forward by the given duration.
+    pub fn advance(&self, duration: std::time::Duration) {
+        *self.now.lock().expect("failed to lock ManualClock") += duration;
+    }
+
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> instant::Instant {
+        *self.now.lock().expect("failed to lock ManualClock")
+    }
+}
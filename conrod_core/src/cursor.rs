@@ -21,6 +21,8 @@ pub enum MouseCursor {
     Grab,
     /// Closed hand.
     Grabbing,
+    /// Indicates that the requested action will not be carried out, e.g. a drop is rejected.
+    NotAllowed,
     /// Vertical resize cursor.
     ResizeVertical,
     /// Horizontal resize cursor.
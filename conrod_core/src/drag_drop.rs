@@ -0,0 +1,117 @@
+//! Types related to dragging a payload from one widget and dropping it onto another.
+//!
+//! A drag-and-drop interaction looks like this:
+//!
+//! 1. A *source* widget calls `UiCell::start_drag` (usually in response to an `event::Drag`),
+//!    handing over a boxed payload along with a `Preview` to display beneath the cursor.
+//! 2. While the left mouse button remains down, the widget under the cursor is the *target*.
+//!    Targets receive `DragEnter`, `DragOver` and `DragLeave` events and may inspect the payload
+//!    via `Ui::drag_drop` to decide whether or not they accept it with `UiCell::accept_drag`.
+//! 3. When the mouse button is released over a target that accepted the drag, the target
+//!    receives a `Drop` event and may take ownership of the payload via
+//!    `UiCell::take_drag_payload`. The source receives a `DragEnd` event describing the target
+//!    that received the drop, if any.
+//!
+//! Pressing `Escape` cancels a drag that is in progress.
+
+use position::Point;
+use std;
+use std::any::Any;
+use widget;
+
+
+/// The graphical preview displayed beneath the cursor while a payload is being dragged.
+///
+/// The preview is drawn by the `widget::DragPreview` widget.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preview {
+    /// No preview is displayed.
+    None,
+    /// A text label is displayed beneath the cursor.
+    Label(String),
+}
+
+/// The stages of a drag-and-drop interaction.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Phase {
+    /// The payload is being dragged.
+    Dragging,
+    /// The payload was dropped onto the given target.
+    ///
+    /// The payload remains available until the end of the following `Ui::set_widgets` so that the
+    /// target may take it upon receiving its `Drop` event.
+    Dropped(widget::Id),
+}
+
+/// The state of a drag-and-drop interaction that is in progress.
+pub struct DragDrop {
+    /// The widget from which the payload is being dragged.
+    pub source: widget::Id,
+    /// The payload that is being dragged.
+    ///
+    /// This is `None` if the payload has already been taken by the target of a drop.
+    pub payload: Option<Box<Any + Send>>,
+    /// The preview displayed beneath the cursor.
+    pub preview: Preview,
+    /// The current position of the cursor.
+    pub xy: Point,
+    /// The widget currently under the cursor, if any.
+    pub target: Option<widget::Id>,
+    /// The `target` if it has accepted the drag via `UiCell::accept_drag`.
+    pub accepted_by: Option<widget::Id>,
+    /// The stage of the interaction.
+    pub phase: Phase,
+}
+
+impl DragDrop {
+
+    /// Begin a new drag of the given payload from the `source` widget.
+    pub fn new(source: widget::Id,
+               payload: Box<Any + Send>,
+               preview: Preview,
+               xy: Point,
+               target: Option<widget::Id>) -> Self
+    {
+        DragDrop {
+            source: source,
+            payload: Some(payload),
+            preview: preview,
+            xy: xy,
+            target: target,
+            accepted_by: None,
+            phase: Phase::Dragging,
+        }
+    }
+
+    /// Whether or not the payload is still being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.phase == Phase::Dragging
+    }
+
+    /// Whether or not the widget currently under the cursor has accepted the drag.
+    pub fn is_accepted(&self) -> bool {
+        self.target.is_some() && self.accepted_by == self.target
+    }
+
+    /// Borrow the payload as the given type `T` if the payload is of that type.
+    pub fn payload<T>(&self) -> Option<&T>
+        where T: Any,
+    {
+        self.payload.as_ref().and_then(|payload| payload.downcast_ref())
+    }
+
+}
+
+impl std::fmt::Debug for DragDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DragDrop")
+            .field("source", &self.source)
+            .field("has_payload", &self.payload.is_some())
+            .field("preview", &self.preview)
+            .field("xy", &self.xy)
+            .field("target", &self.target)
+            .field("accepted_by", &self.accepted_by)
+            .field("phase", &self.phase)
+            .finish()
+    }
+}
@@ -36,7 +36,8 @@
 //! types (`Button`, `ControllerAxisArgs`, `Key`, etc).
 
 use input;
-use position::{Dimensions, Point};
+use position::{Dimensions, Point, Scalar};
+use std::path::PathBuf;
 use utils::vec2_sub;
 use widget;
 
@@ -57,6 +58,7 @@ use widget;
 /// co-ordinates when converting to this event. Also be sure to invert the *y* axis of MouseScroll
 /// events.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Input {
     /// A button on some input device was pressed.
     Press(input::Button),
@@ -64,6 +66,10 @@ pub enum Input {
     Release(input::Button),
     /// The window was received to the given dimensions.
     Resize(f64, f64),
+    /// The window's scale factor, i.e. the number of physical pixels per logical point, changed.
+    ///
+    /// This occurs when the window is moved to a monitor with a different DPI.
+    ScaleFactor(f64),
     /// Some motion input was received (e.g. moving mouse or joystick axis).
     Motion(input::Motion),
     /// Input from a touch surface/screen.
@@ -74,6 +80,13 @@ pub enum Input {
     Focus(bool),
     /// The backed requested to redraw.
     Redraw,
+    /// A file was dropped onto the window from outside of the application.
+    DroppedFile(PathBuf),
+    /// A file is being dragged over the window from outside of the application.
+    HoveredFile(PathBuf),
+    /// A file that was being dragged over the window has either left the window or the drag was
+    /// cancelled.
+    HoveredFileCancelled,
 }
 
 
@@ -106,12 +119,17 @@ pub enum Ui {
     Touch(Option<widget::Id>, input::Touch),
     /// The window's dimensions were resized.
     WindowResized(Dimensions),
+    /// The window's scale factor changed to the given number of physical pixels per point.
+    ScaleFactorChanged(Scalar),
     /// Represents a pointing device being pressed and subsequently released while over the same
     /// location.
     Click(Option<widget::Id>, Click),
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(Option<widget::Id>, DoubleClick),
+    /// Three `Click` events with the same `button` and `xy`, each occurring within the
+    /// `theme.double_click_threshold` of the previous.
+    TripleClick(Option<widget::Id>, TripleClick),
     /// A user tapped a touch screen/surface.
     Tap(Option<widget::Id>, Tap),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
@@ -129,6 +147,53 @@ pub enum Ui {
     WidgetCapturesInputSource(widget::Id, input::Source),
     /// Indicates that the given widget has released the given user input source.
     WidgetUncapturesInputSource(widget::Id, input::Source),
+    /// The mouse cursor has moved over the given widget, making it the `widget_under_mouse`.
+    MouseEnter(widget::Id),
+    /// The mouse cursor has left the given widget, so that it is no longer the
+    /// `widget_under_mouse`.
+    MouseLeave(widget::Id),
+    /// The given widget began dragging a payload via `UiCell::start_drag`.
+    DragStart(widget::Id),
+    /// A dragged payload has moved over the given widget.
+    DragEnter(widget::Id),
+    /// A dragged payload has moved to the given location over the given widget.
+    DragOver(widget::Id, Point),
+    /// A dragged payload has moved off of the given widget.
+    DragLeave(widget::Id),
+    /// A dragged payload was dropped at the given location onto the given widget, which had
+    /// accepted the drag.
+    Drop(widget::Id, Point),
+    /// The drag started by the first widget has ended.
+    ///
+    /// The second widget is the target that received the drop, or `None` if the drag was
+    /// cancelled or released over a widget that did not accept it.
+    DragEnd(widget::Id, Option<widget::Id>),
+    /// A file was dropped onto the window, along with the widget that was under the mouse at the
+    /// time.
+    DroppedFile(Option<widget::Id>, PathBuf),
+    /// A file is being dragged over the window, along with the widget that was under the mouse at
+    /// the time.
+    HoveredFile(Option<widget::Id>, PathBuf),
+    /// A file that was being dragged over the window has left the window or the drag was
+    /// cancelled, along with the widget that was under the mouse at the time.
+    HoveredFileCancelled(Option<widget::Id>),
+    /// Two touches moved towards or away from each other, along with the widget under the
+    /// centroid of the touches when the gesture began.
+    Pinch(Option<widget::Id>, Pinch),
+    /// Two touches rotated around their centroid, along with the widget under the centroid of the
+    /// touches when the gesture began.
+    Rotate(Option<widget::Id>, Rotate),
+    /// A single touch was held in place for at least the `theme.long_press_threshold`, along with
+    /// the widget under the touch.
+    LongPress(Option<widget::Id>, LongPress),
+    /// A single touch moved quickly across the surface before ending, along with the widget under
+    /// the start of the touch.
+    Swipe(Option<widget::Id>, Swipe),
+    /// The keys of a registered keyboard shortcut were pressed, along with the widget that was
+    /// capturing the keyboard at the time.
+    ///
+    /// The key presses that make up the shortcut are not delivered as `Press` events.
+    Shortcut(Option<widget::Id>, input::shortcut::Id),
 }
 
 
@@ -157,6 +222,9 @@ pub enum Widget {
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(DoubleClick),
+    /// Three `Click` events with the same `button` and `xy`, each occurring within the
+    /// `theme.double_click_threshold` of the previous.
+    TripleClick(TripleClick),
     /// A user tapped the widget on a touch screen/surface.
     Tap(Tap),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
@@ -165,10 +233,53 @@ pub enum Widget {
     Scroll(Scroll),
     /// The window's dimensions were resized.
     WindowResized(Dimensions),
+    /// The window's scale factor changed to the given number of physical pixels per point.
+    ScaleFactorChanged(Scalar),
     /// The widget has captured the given input source.
     CapturesInputSource(input::Source),
     /// The widget has released the input source from capturing.
     UncapturesInputSource(input::Source),
+    /// The mouse cursor has moved over the widget.
+    ///
+    /// The widget is now the top-most widget under the mouse.
+    MouseEnter,
+    /// The mouse cursor has left the widget.
+    MouseLeave,
+    /// The widget began dragging a payload.
+    DragStart,
+    /// A dragged payload has moved over the widget.
+    DragEnter,
+    /// A dragged payload has moved to the given location over the widget.
+    DragOver(Point),
+    /// A dragged payload has moved off of the widget.
+    DragLeave,
+    /// A dragged payload was dropped onto the widget at the given location.
+    ///
+    /// The payload may be taken via `UiCell::take_drag_payload`.
+    Drop(Point),
+    /// A drag started by the widget has ended.
+    ///
+    /// Contains the widget that received the drop, or `None` if the drag was cancelled or
+    /// rejected.
+    DragEnd(Option<widget::Id>),
+    /// A file was dropped onto the widget from outside of the application.
+    DroppedFile(PathBuf),
+    /// A file from outside of the application is being dragged over the widget.
+    HoveredFile(PathBuf),
+    /// A file that was being dragged over the widget has left the window or the drag was
+    /// cancelled.
+    HoveredFileCancelled,
+    /// Two touches moved towards or away from each other over the widget.
+    Pinch(Pinch),
+    /// Two touches rotated around their centroid over the widget.
+    Rotate(Rotate),
+    /// A single touch was held in place over the widget.
+    LongPress(LongPress),
+    /// A single touch that began over the widget moved quickly across the surface before ending.
+    Swipe(Swipe),
+    /// The keys of a registered keyboard shortcut were pressed while the widget was capturing the
+    /// keyboard.
+    Shortcut(input::shortcut::Id),
 }
 
 /// Contains all relevant information for a Text event.
@@ -303,6 +414,19 @@ pub struct DoubleClick {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a triple click.
+///
+/// When handling this event, be sure to check that you are handling the intended `button` too.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TripleClick {
+    /// Which mouse button was clicked
+    pub button: input::MouseButton,
+    /// The position at which the mouse was released.
+    pub xy: Point,
+    /// Which modifier keys, if any, that were being held down when the user clicked
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// All relevant information for a touch-screen tap event.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Tap {
@@ -312,6 +436,63 @@ pub struct Tap {
     pub xy: Point,
 }
 
+/// All relevant information for a two-finger pinch gesture.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pinch {
+    /// The ratio between the current and the previous distance separating the two touches.
+    ///
+    /// Values greater than `1.0` indicate the touches are spreading apart (zooming in) while
+    /// values less than `1.0` indicate the touches are moving together (zooming out).
+    pub scale: Scalar,
+    /// The centroid of the two touches.
+    pub centre: Point,
+}
+
+/// All relevant information for a two-finger rotation gesture.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rotate {
+    /// The change in angle (in radians) of the line between the two touches since the previous
+    /// `Rotate` event. Positive values indicate a counter-clockwise rotation.
+    pub angle: Scalar,
+    /// The centroid of the two touches.
+    pub centre: Point,
+}
+
+/// All relevant information for a touch-screen long-press event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LongPress {
+    /// The unique identifier of the source of the touch.
+    pub id: input::touch::Id,
+    /// The position at which the finger is being held.
+    pub xy: Point,
+}
+
+/// The dominant direction of a `Swipe`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SwipeDirection {
+    /// Towards the left edge of the window.
+    Left,
+    /// Towards the right edge of the window.
+    Right,
+    /// Towards the top edge of the window.
+    Up,
+    /// Towards the bottom edge of the window.
+    Down,
+}
+
+/// All relevant information for a touch-screen swipe event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Swipe {
+    /// The unique identifier of the source of the touch.
+    pub id: input::touch::Id,
+    /// The dominant direction in which the finger travelled.
+    pub direction: SwipeDirection,
+    /// The average speed of the finger in points per second.
+    pub velocity: Scalar,
+    /// The position at which the finger first touched the surface.
+    pub xy: Point,
+}
+
 /// Holds all the relevant information about a scroll event
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Scroll {
@@ -432,6 +613,46 @@ impl Tap {
     }
 }
 
+impl Pinch {
+    /// Returns a copy of the `Pinch` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Pinch {
+            centre: vec2_sub(self.centre, xy),
+            ..*self
+        }
+    }
+}
+
+impl Rotate {
+    /// Returns a copy of the `Rotate` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Rotate {
+            centre: vec2_sub(self.centre, xy),
+            ..*self
+        }
+    }
+}
+
+impl LongPress {
+    /// Returns a copy of the `LongPress` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        LongPress {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl Swipe {
+    /// Returns a copy of the `Swipe` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Swipe {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 impl Click {
     /// Returns a copy of the Click relative to the given `xy`
     pub fn relative_to(&self, xy: Point) -> Click {
@@ -452,6 +673,16 @@ impl DoubleClick {
     }
 }
 
+impl TripleClick {
+    /// Returns a copy of the TripleClick relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> TripleClick {
+        TripleClick {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 impl Drag {
     /// Returns a copy of the Drag relative to the given `xy`
     pub fn relative_to(&self, xy: Point) -> Drag {
@@ -531,12 +762,42 @@ impl From<DoubleClick> for Widget {
     }
 }
 
+impl From<TripleClick> for Widget {
+    fn from(triple_click: TripleClick) -> Self {
+        Widget::TripleClick(triple_click)
+    }
+}
+
 impl From<Tap> for Widget {
     fn from(tap: Tap) -> Self {
         Widget::Tap(tap)
     }
 }
 
+impl From<Pinch> for Widget {
+    fn from(pinch: Pinch) -> Self {
+        Widget::Pinch(pinch)
+    }
+}
+
+impl From<Rotate> for Widget {
+    fn from(rotate: Rotate) -> Self {
+        Widget::Rotate(rotate)
+    }
+}
+
+impl From<LongPress> for Widget {
+    fn from(long_press: LongPress) -> Self {
+        Widget::LongPress(long_press)
+    }
+}
+
+impl From<Swipe> for Widget {
+    fn from(swipe: Swipe) -> Self {
+        Widget::Swipe(swipe)
+    }
+}
+
 impl From<Scroll> for Widget {
     fn from(scroll: Scroll) -> Self {
         Widget::Scroll(scroll)
@@ -28,6 +28,8 @@ pub struct Id(u32);
 pub struct Map<Img> {
     next_index: u32,
     map: HashMap<Img>,
+    /// Higher (or lower) resolution variants of images within the `map`, along with their scales.
+    variants: fnv::FnvHashMap<Id, Vec<(f64, Img)>>,
     /// Whether or not the `image::Map` will trigger a redraw the next time `Ui::draw` is called.
     ///
     /// This is automatically set to `true` when any method that takes `&mut self` is called.
@@ -59,6 +61,7 @@ impl<Img> Map<Img> {
         Map {
             next_index: 0,
             map: HashMap::<Img>::default(),
+            variants: fnv::FnvHashMap::default(),
             trigger_redraw: std::cell::Cell::new(true),
         }
     }
@@ -96,14 +99,59 @@ impl<Img> Map<Img> {
 
     /// Removes the given image from the map if it exists. Returns the image or None.
     ///
-    /// Any future use of the given `image::Id` will be invalid.
+    /// Any future use of the given `image::Id` will be invalid. All variants of the image are also
+    /// removed.
     ///
     /// Note: Calling this will trigger a redraw the next time `Ui::draw_if_changed` is called.
     pub fn remove(&mut self, id: Id) -> Option<Img> {
         self.trigger_redraw.set(true);
+        self.variants.remove(&id);
         self.map.remove(&id)
     }
 
+    /// Inserts a variant of the image at the given `image::Id` for displays with the given scale
+    /// factor, e.g. an `@2x` image with a `scale` of `2.0`.
+    ///
+    /// The image inserted via `insert` is considered to have a scale of `1.0`. Returns the variant
+    /// previously inserted at the same scale, if any.
+    ///
+    /// Note: Calling this will trigger a redraw the next time `Ui::draw_if_changed` is called.
+    pub fn insert_variant(&mut self, id: Id, scale: f64, img: Img) -> Option<Img> {
+        self.trigger_redraw.set(true);
+        let variants = self.variants.entry(id).or_insert_with(Vec::new);
+        match variants.iter().position(|&(s, _)| s == scale) {
+            Some(i) => Some(std::mem::replace(&mut variants[i].1, img)),
+            None => {
+                variants.push((scale, img));
+                None
+            },
+        }
+    }
+
+    /// Borrow the variant of the image at the given `image::Id` that is best suited to a display
+    /// with the given scale factor (see `Ui::scale_factor`), along with the variant's scale.
+    ///
+    /// This is the variant with the smallest scale that is no less than `scale_factor`, or the
+    /// variant with the largest scale if none are large enough. Source rectangles given in pixels
+    /// of the original image should be multiplied by the returned scale.
+    pub fn get_for_scale(&self, id: Id, scale_factor: f64) -> Option<(&Img, f64)> {
+        let base = self.map.get(&id).map(|img| (img, 1.0));
+        let variants = self.variants.get(&id).into_iter()
+            .flat_map(|variants| variants.iter().map(|&(scale, ref img)| (img, scale)));
+        base.into_iter().chain(variants).fold(None, |best, (img, scale)| match best {
+            None => Some((img, scale)),
+            Some((best_img, best_scale)) => {
+                let is_better = match (best_scale >= scale_factor, scale >= scale_factor) {
+                    (true, true) => scale < best_scale,
+                    (false, true) => true,
+                    (true, false) => false,
+                    (false, false) => scale > best_scale,
+                };
+                if is_better { Some((img, scale)) } else { Some((best_img, best_scale)) }
+            },
+        })
+    }
+
     /// Insert each of the images yielded by the given iterator and produce an iterator yielding
     /// their generated `Ids` in the same order.
     ///
@@ -0,0 +1,370 @@
+//! Recognition of multi-touch gestures from a series of `Touch` inputs.
+//!
+//! The `Recognizer` tracks every in-progress touch interaction by its `touch::Id` and interprets
+//! their movement as higher level gestures:
+//!
+//! - **Pinch** and **Rotate**: two touches moving relative to one another.
+//! - **LongPress**: a single touch held in place for some duration.
+//! - **Swipe**: a single touch moving quickly across the surface before ending.
+//!
+//! The `Ui` owns a `Recognizer` within its `input::Global` and delivers each recognised gesture to
+//! the widget under the gesture's `origin`.
+
+use event;
+use fnv;
+use input::touch;
+use position::{Point, Scalar};
+use std;
+use utils;
+
+
+/// The distance a touch may travel from its starting position while still being considered a
+/// candidate for a `LongPress`.
+pub const LONG_PRESS_TOLERANCE: Scalar = 10.0;
+
+/// The minimum distance a single touch must travel for it to be considered a `Swipe`.
+pub const SWIPE_MIN_DISTANCE: Scalar = 50.0;
+
+/// The minimum average speed (in points per second) a single touch must travel at for it to be
+/// considered a `Swipe`.
+pub const SWIPE_MIN_VELOCITY: Scalar = 300.0;
+
+
+/// A gesture interpreted from a series of `Touch` inputs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gesture {
+    /// Two touches moved towards or away from each other.
+    Pinch(event::Pinch),
+    /// Two touches rotated around their centroid.
+    Rotate(event::Rotate),
+    /// A single touch was held in place.
+    LongPress(event::LongPress),
+    /// A single touch moved quickly across the surface before ending.
+    Swipe(event::Swipe),
+}
+
+/// A `Gesture` along with the position at which the gesture began.
+///
+/// The `origin` is used to determine which widget receives the gesture so that a gesture is
+/// always delivered to the same widget for its duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Recognized {
+    /// The gesture that was recognised.
+    pub gesture: Gesture,
+    /// The position at which the gesture began.
+    ///
+    /// For two-finger gestures this is the centroid of the touches at the moment the second
+    /// touch began.
+    pub origin: Point,
+}
+
+/// Interprets `Touch` inputs as `Gesture`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recognizer {
+    /// All touch interactions that are currently in progress.
+    tracks: fnv::FnvHashMap<touch::Id, Track>,
+    /// The pair of touches being tracked for two-finger gestures.
+    ///
+    /// This is only `Some` while exactly two touches are in progress.
+    pair: Option<Pair>,
+    /// Whether or not more than one touch has been in progress at once since the surface was last
+    /// free of touches.
+    ///
+    /// Single-touch gestures are not recognised during a multi-touch interaction.
+    is_multi_touch: bool,
+    /// Whether or not a `LongPress` has been recognised for the current touch.
+    is_long_pressed: bool,
+}
+
+/// The state of a single in-progress touch.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Track {
+    start_time: instant::Instant,
+    start_xy: Point,
+    xy: Point,
+    /// Whether or not the touch has ever moved beyond the `LONG_PRESS_TOLERANCE`.
+    has_moved: bool,
+}
+
+/// The state of a pair of touches used to recognise two-finger gestures.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Pair {
+    ids: (touch::Id, touch::Id),
+    origin: Point,
+    distance: Scalar,
+    angle: Scalar,
+}
+
+
+impl Recognizer {
+
+    /// Construct a new **Recognizer** with no touches in progress.
+    pub fn new() -> Self {
+        Recognizer {
+            tracks: fnv::FnvHashMap::default(),
+            pair: None,
+            is_multi_touch: false,
+            is_long_pressed: false,
+        }
+    }
+
+    /// The number of touch interactions that are currently in progress.
+    pub fn touch_count(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Update the recognizer with the given `Touch` which occurred at the moment `now`.
+    ///
+    /// Returns all gestures that were recognised as a result.
+    pub fn touch(&mut self, touch: touch::Touch, now: instant::Instant) -> Vec<Recognized> {
+        let mut recognized = Vec::new();
+        match touch.phase {
+
+            touch::Phase::Start => {
+                let track = Track {
+                    start_time: now,
+                    start_xy: touch.xy,
+                    xy: touch.xy,
+                    has_moved: false,
+                };
+                self.tracks.insert(touch.id, track);
+                if self.tracks.len() > 1 {
+                    self.is_multi_touch = true;
+                }
+                self.pair = self.new_pair();
+            },
+
+            touch::Phase::Move => {
+                match self.tracks.get_mut(&touch.id) {
+                    Some(track) => {
+                        track.xy = touch.xy;
+                        if distance(track.start_xy, track.xy) > LONG_PRESS_TOLERANCE {
+                            track.has_moved = true;
+                        }
+                    },
+                    None => return recognized,
+                }
+
+                let pair = match self.pair {
+                    Some(pair) if pair.ids.0 == touch.id || pair.ids.1 == touch.id => pair,
+                    _ => return recognized,
+                };
+                let (a, b) = (self.tracks[&pair.ids.0].xy, self.tracks[&pair.ids.1].xy);
+                let new_distance = distance(a, b);
+                let new_angle = angle(a, b);
+                let centre = centroid(a, b);
+
+                if pair.distance > 0.0 && new_distance != pair.distance {
+                    let pinch = event::Pinch { scale: new_distance / pair.distance, centre: centre };
+                    recognized.push(Recognized { gesture: Gesture::Pinch(pinch), origin: pair.origin });
+                }
+
+                let delta_angle = normalise_angle(new_angle - pair.angle);
+                if delta_angle != 0.0 {
+                    let rotate = event::Rotate { angle: delta_angle, centre: centre };
+                    recognized.push(Recognized { gesture: Gesture::Rotate(rotate), origin: pair.origin });
+                }
+
+                self.pair = Some(Pair { distance: new_distance, angle: new_angle, ..pair });
+            },
+
+            touch::Phase::End => {
+                let track = match self.tracks.remove(&touch.id) {
+                    Some(track) => Track { xy: touch.xy, ..track },
+                    None => return recognized,
+                };
+                if !self.is_multi_touch && !self.is_long_pressed {
+                    if let Some(swipe) = swipe(touch.id, &track, now) {
+                        recognized.push(Recognized { gesture: Gesture::Swipe(swipe), origin: track.start_xy });
+                    }
+                }
+                self.end_touch();
+            },
+
+            touch::Phase::Cancel => {
+                if self.tracks.remove(&touch.id).is_some() {
+                    self.end_touch();
+                }
+            },
+
+        }
+        recognized
+    }
+
+    /// Check whether or not the single touch in progress has become a `LongPress` by the moment
+    /// `now`.
+    ///
+    /// As no input is received while a finger is held in place, this should be called regularly
+    /// (e.g. once per update) while a touch is in progress. At most one `LongPress` is recognised
+    /// per touch. See `long_press_deadline` for the moment at which this should next be called.
+    pub fn long_press(&mut self,
+                      now: instant::Instant,
+                      threshold: std::time::Duration) -> Option<Recognized>
+    {
+        match self.long_press_deadline(threshold) {
+            Some(deadline) if deadline <= now => (),
+            _ => return None,
+        }
+        let (&id, track) = self.tracks.iter().next().expect("no tracked touch");
+        self.is_long_pressed = true;
+        let long_press = event::LongPress { id: id, xy: track.xy };
+        Some(Recognized { gesture: Gesture::LongPress(long_press), origin: track.xy })
+    }
+
+    /// The moment at which the single touch in progress becomes a `LongPress` if it is held in
+    /// place until then.
+    ///
+    /// Returns `None` if no touch in progress may become a `LongPress`.
+    pub fn long_press_deadline(&self, threshold: std::time::Duration) -> Option<instant::Instant> {
+        if self.is_multi_touch || self.is_long_pressed || self.tracks.len() != 1 {
+            return None;
+        }
+        self.tracks.values()
+            .next()
+            .and_then(|track| match track.has_moved {
+                true => None,
+                false => Some(track.start_time + threshold),
+            })
+    }
+
+    /// Update the gesture state following the removal of a touch.
+    fn end_touch(&mut self) {
+        self.pair = self.new_pair();
+        if self.tracks.is_empty() {
+            self.is_multi_touch = false;
+            self.is_long_pressed = false;
+        }
+    }
+
+    /// Produce a new `Pair` if exactly two touches are in progress.
+    fn new_pair(&self) -> Option<Pair> {
+        if self.tracks.len() != 2 {
+            return None;
+        }
+        let mut ids = self.tracks.keys().cloned();
+        let (a_id, b_id) = (ids.next().unwrap(), ids.next().unwrap());
+        let (a_id, b_id) = if a_id < b_id { (a_id, b_id) } else { (b_id, a_id) };
+        let (a, b) = (self.tracks[&a_id].xy, self.tracks[&b_id].xy);
+        Some(Pair {
+            ids: (a_id, b_id),
+            origin: centroid(a, b),
+            distance: distance(a, b),
+            angle: angle(a, b),
+        })
+    }
+
+}
+
+impl Default for Recognizer {
+    fn default() -> Self {
+        Recognizer::new()
+    }
+}
+
+
+/// Produce a `Swipe` for the given ended touch if it travelled far and fast enough.
+fn swipe(id: touch::Id, track: &Track, now: instant::Instant) -> Option<event::Swipe> {
+    let delta = utils::vec2_sub(track.xy, track.start_xy);
+    let dist = distance(track.start_xy, track.xy);
+    if dist < SWIPE_MIN_DISTANCE {
+        return None;
+    }
+    let duration = now.duration_since(track.start_time);
+    let secs = duration.as_secs() as Scalar + duration.subsec_nanos() as Scalar * 1e-9;
+    let velocity = if secs > 0.0 { dist / secs } else { std::f64::INFINITY };
+    if velocity < SWIPE_MIN_VELOCITY {
+        return None;
+    }
+    let direction = match (delta[0].abs() >= delta[1].abs(), delta[0] > 0.0, delta[1] > 0.0) {
+        (true, true, _) => event::SwipeDirection::Right,
+        (true, false, _) => event::SwipeDirection::Left,
+        (false, _, true) => event::SwipeDirection::Up,
+        (false, _, false) => event::SwipeDirection::Down,
+    };
+    Some(event::Swipe { id: id, direction: direction, velocity: velocity, xy: track.start_xy })
+}
+
+fn distance(a: Point, b: Point) -> Scalar {
+    let delta = utils::vec2_sub(b, a);
+    (delta[0] * delta[0] + delta[1] * delta[1]).sqrt()
+}
+
+fn angle(a: Point, b: Point) -> Scalar {
+    let delta = utils::vec2_sub(b, a);
+    delta[1].atan2(delta[0])
+}
+
+fn centroid(a: Point, b: Point) -> Point {
+    [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0]
+}
+
+/// Wrap the given angle to the range `(-PI, PI]`.
+fn normalise_angle(mut angle: Scalar) -> Scalar {
+    use std::f64::consts::PI;
+    while angle > PI {
+        angle -= 2.0 * PI;
+    }
+    while angle <= -PI {
+        angle += 2.0 * PI;
+    }
+    angle
+}
+
+
+#[test]
+fn pinch_and_rotate_should_be_recognised_from_two_moving_touches() {
+    let now = instant::Instant::now();
+    let touch = |id, phase, xy| touch::Touch { phase: phase, id: touch::Id::new(id), xy: xy };
+    let mut recognizer = Recognizer::new();
+    assert!(recognizer.touch(touch(0, touch::Phase::Start, [-10.0, 0.0]), now).is_empty());
+    assert!(recognizer.touch(touch(1, touch::Phase::Start, [10.0, 0.0]), now).is_empty());
+
+    let recognized = recognizer.touch(touch(1, touch::Phase::Move, [30.0, 0.0]), now);
+    assert_eq!(recognized, vec![Recognized {
+        gesture: Gesture::Pinch(event::Pinch { scale: 2.0, centre: [10.0, 0.0] }),
+        origin: [0.0, 0.0],
+    }]);
+
+    let recognized = recognizer.touch(touch(1, touch::Phase::Move, [-10.0, 40.0]), now);
+    match recognized.last().map(|r| r.gesture) {
+        Some(Gesture::Rotate(rotate)) => {
+            let expected = std::f64::consts::PI / 2.0;
+            assert!((rotate.angle - expected).abs() < 1e-9);
+        },
+        other => panic!("expected a rotation, found {:?}", other),
+    }
+
+    // Lifting a finger should not produce a swipe while multi-touch.
+    assert!(recognizer.touch(touch(1, touch::Phase::End, [-10.0, 400.0]), now).is_empty());
+    assert!(recognizer.long_press(now + std::time::Duration::from_secs(10),
+                                  std::time::Duration::from_millis(500)).is_none());
+}
+
+#[test]
+fn long_press_and_swipe_should_be_recognised_from_a_single_touch() {
+    let now = instant::Instant::now();
+    let threshold = std::time::Duration::from_millis(500);
+    let touch = |id, phase, xy| touch::Touch { phase: phase, id: touch::Id::new(id), xy: xy };
+    let mut recognizer = Recognizer::new();
+
+    recognizer.touch(touch(0, touch::Phase::Start, [0.0, 0.0]), now);
+    assert!(recognizer.long_press(now + std::time::Duration::from_millis(100), threshold).is_none());
+    let long_press = recognizer.long_press(now + threshold, threshold).map(|r| r.gesture);
+    let expected = event::LongPress { id: touch::Id::new(0), xy: [0.0, 0.0] };
+    assert_eq!(long_press, Some(Gesture::LongPress(expected)));
+    assert!(recognizer.long_press(now + threshold * 2, threshold).is_none());
+    recognizer.touch(touch(0, touch::Phase::End, [0.0, 0.0]), now + threshold * 2);
+
+    let later = now + threshold * 4;
+    recognizer.touch(touch(1, touch::Phase::Start, [0.0, 0.0]), later);
+    recognizer.touch(touch(1, touch::Phase::Move, [0.0, -50.0]), later);
+    let end = later + std::time::Duration::from_millis(100);
+    let recognized = recognizer.touch(touch(1, touch::Phase::End, [0.0, -100.0]), end);
+    match recognized.last().map(|r| r.gesture) {
+        Some(Gesture::Swipe(swipe)) => {
+            assert_eq!(swipe.direction, event::SwipeDirection::Down);
+            assert!((swipe.velocity - 1000.0).abs() < 1e-6);
+        },
+        other => panic!("expected a swipe, found {:?}", other),
+    }
+}
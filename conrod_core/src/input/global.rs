@@ -21,6 +21,15 @@ pub struct Global {
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(instant::Instant, event::Click)>,
+    /// The number of consecutive clicks of the same mouse button at the same position, counted
+    /// from `1` at the press of the first click up to `3` for a triple-click.
+    ///
+    /// This is updated as soon as the mouse button is pressed, allowing widgets to respond to a
+    /// double or triple press (e.g. in order to begin selecting by word) before the
+    /// `DoubleClick` or `TripleClick` event is produced upon release.
+    pub click_count: u32,
+    /// Interprets the in-progress touch interactions as multi-touch gestures.
+    pub gestures: input::gesture::Recognizer,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -46,6 +55,8 @@ impl Global {
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            click_count: 0,
+            gestures: input::gesture::Recognizer::new(),
         }
     }
 
@@ -0,0 +1,311 @@
+//! Configurable key bindings for text editing.
+//!
+//! `KeyBindings` map a `Chord` (a key pressed while holding an exact set of modifiers) to the
+//! editing `Action` that it performs within widgets such as the `TextEdit`. The bindings used by
+//! all widgets are stored within the `Theme`'s `key_bindings` field.
+//!
+//! Presets are provided for the conventions of Windows and Linux, macOS and Emacs. By default the
+//! preset for the target platform is used.
+//!
+//! Text editing widgets interpret their key presses and text input via an `Interpreter`.
+
+use event;
+use input::{Key, ModifierKey};
+use input::shortcut::Chord;
+use std;
+
+
+/// A movement of the text cursor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Movement {
+    /// To the previous character.
+    Left,
+    /// To the next character.
+    Right,
+    /// To the closest position on the previous line.
+    Up,
+    /// To the closest position on the next line.
+    Down,
+    /// To the start of the current or previous word.
+    WordLeft,
+    /// To the end of the current or next word.
+    WordRight,
+    /// To the start of the current line.
+    LineStart,
+    /// To the end of the current line.
+    LineEnd,
+    /// To the start of the text.
+    DocumentStart,
+    /// To the end of the text.
+    DocumentEnd,
+}
+
+/// An editing action that may be bound to a `Chord`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Move the cursor, collapsing any selection.
+    Move(Movement),
+    /// Extend the selection by moving its end, beginning a new selection if necessary.
+    Select(Movement),
+    /// Delete the text between the cursor and the position reached by the movement.
+    ///
+    /// If some text is selected, the selection is deleted instead.
+    Delete(Movement),
+    /// Select all text.
+    SelectAll,
+    /// Insert a new line at the cursor, replacing any selection.
+    InsertNewline,
+}
+
+/// A table mapping `Chord`s to editing `Action`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    bindings: Vec<(Chord, Action)>,
+}
+
+/// Interprets the key presses and text received by a text editing widget.
+///
+/// A key press that performs some action often also produces text, e.g. `Return` produces a
+/// carriage return. The **Interpreter** ignores the text produced by such key presses so that it
+/// is not inserted in addition to the action being performed.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Interpreter {
+    is_text_suppressed: bool,
+}
+
+
+impl Movement {
+
+    /// Whether or not the movement is towards the start of the text.
+    pub fn is_backward(self) -> bool {
+        match self {
+            Movement::Left | Movement::Up | Movement::WordLeft |
+            Movement::LineStart | Movement::DocumentStart => true,
+            Movement::Right | Movement::Down | Movement::WordRight |
+            Movement::LineEnd | Movement::DocumentEnd => false,
+        }
+    }
+
+}
+
+impl KeyBindings {
+
+    /// A **KeyBindings** table with no bindings.
+    pub fn new() -> Self {
+        KeyBindings { bindings: Vec::new() }
+    }
+
+    /// The conventional bindings of the target platform.
+    ///
+    /// This is `KeyBindings::macos` on macOS and `KeyBindings::windows_linux` elsewhere.
+    pub fn platform() -> Self {
+        if cfg!(target_os = "macos") {
+            KeyBindings::macos()
+        } else {
+            KeyBindings::windows_linux()
+        }
+    }
+
+    /// The conventional bindings of Windows and most Linux desktop environments.
+    ///
+    /// - `Ctrl` moves by word.
+    /// - `Home` and `End` move to the start and end of the line, or of the text with `Ctrl`.
+    /// - `Ctrl+Backspace` and `Ctrl+Delete` delete by word.
+    /// - `Shift` extends the selection.
+    pub fn windows_linux() -> Self {
+        let ctrl = ModifierKey::CTRL;
+        KeyBindings::common()
+            .bind_movement(ctrl, Key::Left, Movement::WordLeft)
+            .bind_movement(ctrl, Key::Right, Movement::WordRight)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Home, Movement::LineStart)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::End, Movement::LineEnd)
+            .bind_movement(ctrl, Key::Home, Movement::DocumentStart)
+            .bind_movement(ctrl, Key::End, Movement::DocumentEnd)
+            .bind(Chord::new(ctrl, Key::Backspace), Action::Delete(Movement::WordLeft))
+            .bind(Chord::new(ctrl, Key::Delete), Action::Delete(Movement::WordRight))
+            .bind(Chord::new(ctrl, Key::A), Action::SelectAll)
+    }
+
+    /// The conventional bindings of macOS.
+    ///
+    /// - `Option` moves by word.
+    /// - `Cmd` moves to the start and end of the line, or of the text with `Up` and `Down`.
+    /// - `Home` and `End` move to the start and end of the text.
+    /// - `Option+Backspace` deletes by word and `Cmd+Backspace` deletes to the start of the line.
+    /// - The basic Emacs-style line movements `Ctrl+A` and `Ctrl+E` are also supported.
+    /// - `Shift` extends the selection.
+    pub fn macos() -> Self {
+        let (alt, cmd, ctrl) = (ModifierKey::ALT, ModifierKey::GUI, ModifierKey::CTRL);
+        KeyBindings::common()
+            .bind_movement(alt, Key::Left, Movement::WordLeft)
+            .bind_movement(alt, Key::Right, Movement::WordRight)
+            .bind_movement(cmd, Key::Left, Movement::LineStart)
+            .bind_movement(cmd, Key::Right, Movement::LineEnd)
+            .bind_movement(ctrl, Key::A, Movement::LineStart)
+            .bind_movement(ctrl, Key::E, Movement::LineEnd)
+            .bind_movement(cmd, Key::Up, Movement::DocumentStart)
+            .bind_movement(cmd, Key::Down, Movement::DocumentEnd)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Home, Movement::DocumentStart)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::End, Movement::DocumentEnd)
+            .bind(Chord::new(alt, Key::Backspace), Action::Delete(Movement::WordLeft))
+            .bind(Chord::new(alt, Key::Delete), Action::Delete(Movement::WordRight))
+            .bind(Chord::new(cmd, Key::Backspace), Action::Delete(Movement::LineStart))
+            .bind(Chord::new(cmd, Key::A), Action::SelectAll)
+    }
+
+    /// Bindings following the conventions of the Emacs text editor, in addition to the arrow,
+    /// `Home` and `End` keys.
+    ///
+    /// - `Ctrl+B`, `Ctrl+F`, `Ctrl+P` and `Ctrl+N` move by character and line.
+    /// - `Alt+B` and `Alt+F` move by word.
+    /// - `Ctrl+A` and `Ctrl+E` move to the start and end of the line.
+    /// - `Alt+<` and `Alt+>` move to the start and end of the text.
+    /// - `Ctrl+D`, `Alt+D` and `Alt+Backspace` delete by character and word.
+    /// - `Ctrl+K` deletes to the end of the line.
+    pub fn emacs() -> Self {
+        let (alt, ctrl) = (ModifierKey::ALT, ModifierKey::CTRL);
+        let alt_shift = ModifierKey::ALT | ModifierKey::SHIFT;
+        KeyBindings::common()
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Home, Movement::LineStart)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::End, Movement::LineEnd)
+            .bind(Chord::new(ctrl, Key::B), Action::Move(Movement::Left))
+            .bind(Chord::new(ctrl, Key::F), Action::Move(Movement::Right))
+            .bind(Chord::new(ctrl, Key::P), Action::Move(Movement::Up))
+            .bind(Chord::new(ctrl, Key::N), Action::Move(Movement::Down))
+            .bind(Chord::new(alt, Key::B), Action::Move(Movement::WordLeft))
+            .bind(Chord::new(alt, Key::F), Action::Move(Movement::WordRight))
+            .bind(Chord::new(ctrl, Key::A), Action::Move(Movement::LineStart))
+            .bind(Chord::new(ctrl, Key::E), Action::Move(Movement::LineEnd))
+            .bind(Chord::new(alt_shift, Key::Comma), Action::Move(Movement::DocumentStart))
+            .bind(Chord::new(alt_shift, Key::Period), Action::Move(Movement::DocumentEnd))
+            .bind(Chord::new(ctrl, Key::D), Action::Delete(Movement::Right))
+            .bind(Chord::new(alt, Key::D), Action::Delete(Movement::WordRight))
+            .bind(Chord::new(alt, Key::Backspace), Action::Delete(Movement::WordLeft))
+            .bind(Chord::new(ctrl, Key::K), Action::Delete(Movement::LineEnd))
+    }
+
+    /// The bindings shared by all presets: the arrow keys, `Backspace`, `Delete` and `Return`.
+    fn common() -> Self {
+        KeyBindings::new()
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Left, Movement::Left)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Right, Movement::Right)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Up, Movement::Up)
+            .bind_movement(ModifierKey::NO_MODIFIER, Key::Down, Movement::Down)
+            .bind(Chord::key(Key::Backspace), Action::Delete(Movement::Left))
+            .bind(Chord::key(Key::Delete), Action::Delete(Movement::Right))
+            .bind(Chord::key(Key::Return), Action::InsertNewline)
+            .bind(Chord::key(Key::NumPadEnter), Action::InsertNewline)
+    }
+
+    /// Bind the given chord to the given action, replacing any existing binding for the chord.
+    pub fn bind(mut self, chord: Chord, action: Action) -> Self {
+        self.unbind_chord(chord);
+        self.bindings.push((chord, action));
+        self
+    }
+
+    /// Bind the given modifiers and key to `Action::Move` and, with `Shift` also held, to
+    /// `Action::Select`.
+    pub fn bind_movement(self, modifiers: ModifierKey, key: Key, movement: Movement) -> Self {
+        let select_modifiers = modifiers | ModifierKey::SHIFT;
+        self.bind(Chord::new(modifiers, key), Action::Move(movement))
+            .bind(Chord::new(select_modifiers, key), Action::Select(movement))
+    }
+
+    /// Remove the binding for the given chord, returning its action if there was one.
+    pub fn unbind_chord(&mut self, chord: Chord) -> Option<Action> {
+        self.bindings.iter()
+            .position(|&(c, _)| c == chord)
+            .map(|idx| self.bindings.remove(idx).1)
+    }
+
+    /// The action bound to the given chord, if any.
+    pub fn action(&self, chord: Chord) -> Option<Action> {
+        self.bindings.iter().find(|&&(c, _)| c == chord).map(|&(_, action)| action)
+    }
+
+    /// All chords bound to the given action.
+    ///
+    /// This is useful for displaying the bindings of an action to the user.
+    pub fn chords(&self, action: Action) -> Vec<Chord> {
+        self.bindings.iter().filter(|&&(_, a)| a == action).map(|&(chord, _)| chord).collect()
+    }
+
+}
+
+impl Interpreter {
+
+    /// Construct a new **Interpreter** for the events of a single update.
+    pub fn new() -> Self {
+        Interpreter::default()
+    }
+
+    /// The action bound to the given chord, if any.
+    ///
+    /// If the chord is bound, any text produced by the same key press is ignored.
+    pub fn press(&mut self, bindings: &KeyBindings, chord: Chord) -> Option<Action> {
+        let action = bindings.action(chord);
+        self.is_text_suppressed = action.is_some();
+        action
+    }
+
+    /// Ignore any text produced by the current key press.
+    ///
+    /// This is useful for keys that the widget handles itself rather than via its `KeyBindings`.
+    pub fn suppress_text(&mut self) {
+        self.is_text_suppressed = true;
+    }
+
+    /// The string to be inserted for the given text event, if any.
+    ///
+    /// Returns `None` for text produced by a key press that performed some action, text entered
+    /// while holding `Ctrl` or `Cmd`, and text consisting only of control characters (other than
+    /// tabs).
+    pub fn text<'a>(&mut self, text: &'a event::Text) -> Option<&'a str> {
+        if std::mem::replace(&mut self.is_text_suppressed, false) {
+            return None;
+        }
+        let event::Text { ref string, modifiers } = *text;
+        if modifiers.intersects(ModifierKey::CTRL | ModifierKey::GUI)
+        || string.chars().all(|c| c.is_control() && c != '\t') {
+            return None;
+        }
+        // Ignore the private-use characters that OS X produces for the arrow keys in case they
+        // arrive separately from the key press.
+        match &string[..] {
+            "\u{f700}" | "\u{f701}" | "\u{f702}" | "\u{f703}" => None,
+            string => Some(string),
+        }
+    }
+
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings::platform()
+    }
+}
+
+
+#[test]
+fn presets_should_bind_selection_to_shift_movement() {
+    let bindings = KeyBindings::windows_linux();
+    let shift_ctrl = ModifierKey::SHIFT | ModifierKey::CTRL;
+    assert_eq!(bindings.action(Chord::ctrl(Key::Left)), Some(Action::Move(Movement::WordLeft)));
+    assert_eq!(bindings.action(Chord::new(shift_ctrl, Key::End)),
+               Some(Action::Select(Movement::DocumentEnd)));
+    assert_eq!(bindings.action(Chord::key(Key::Home)), Some(Action::Move(Movement::LineStart)));
+
+    let bindings = KeyBindings::macos();
+    let alt = ModifierKey::ALT;
+    assert_eq!(bindings.action(Chord::new(alt, Key::Left)), Some(Action::Move(Movement::WordLeft)));
+    assert_eq!(bindings.action(Chord::ctrl(Key::Left)), None);
+}
+
+#[test]
+fn binding_a_chord_should_replace_its_existing_binding() {
+    let bindings = KeyBindings::emacs()
+        .bind(Chord::ctrl(Key::K), Action::Delete(Movement::LineStart));
+    assert_eq!(bindings.action(Chord::ctrl(Key::K)), Some(Action::Delete(Movement::LineStart)));
+    assert_eq!(bindings.chords(Action::Delete(Movement::LineEnd)), vec![]);
+}
@@ -15,6 +15,10 @@
 //! middle of the widget's bounding `Rect`. `GlobalInput`, on the other hand, will never filter out
 //! any events, and will always provide them with coordinates relative to the window.
 
+pub mod gesture;
+pub mod key_bindings;
+pub mod record;
+pub mod shortcut;
 pub mod state;
 pub mod widget;
 pub mod global;
@@ -22,6 +26,7 @@ pub mod global;
 use Scalar;
 pub use self::state::State;
 pub use self::global::Global;
+pub use self::key_bindings::KeyBindings;
 pub use self::touch::Touch;
 pub use self::widget::Widget;
 
@@ -55,6 +60,7 @@ pub enum Source {
 /// Different kinds of motion input.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Motion {
     /// Absolute cursor position within the window.
     ///
@@ -75,10 +81,12 @@ pub mod touch {
 
     /// A type for uniquely identifying the source of a touch interaction.
     #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
     pub struct Id(u64);
 
     /// The stage of the touch interaction.
     #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
     pub enum Phase {
         /// The start of a touch interaction.
         Start,
@@ -102,6 +110,7 @@ pub mod touch {
     /// indicate that the same finger was used. `Id`s are only used to distinguish between
     /// overlapping touch interactions.
     #[derive(Copy, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
     pub struct Touch {
         /// The stage of the touch interaction.
         pub phase: Phase,
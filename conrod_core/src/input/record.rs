@@ -0,0 +1,172 @@
+//! Recording of the input received by a `Ui` and deterministic replay of that input.
+//!
+//! Recording begins with `Ui::start_recording`. From then on, every `event::Input` passed to
+//! `Ui::handle_event` is stored along with the time at which it was received, as is each call to
+//! `Ui::set_widgets`. `Ui::stop_recording` returns the resulting `Recording`.
+//!
+//! A `Player` feeds a `Recording` back into a fresh `Ui` driven by a `ManualClock` so that all
+//! timing-dependent behaviour (e.g. double-clicks, long-presses and hover delays) occurs exactly
+//! as it did during the recording.
+//!
+//! When the `serialize` feature is enabled, `Recording` implements serde's `Serialize` and
+//! `Deserialize` so that recordings may be stored, e.g. alongside a bug report or as a regression
+//! test.
+
+use clock::ManualClock;
+use event;
+use position::Dimensions;
+use std;
+use ui::{Ui, UiBuilder};
+
+
+/// A log of all input received by a `Ui` over some period.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Recording {
+    /// The dimensions of the window at the moment recording began.
+    pub window_dimensions: Dimensions,
+    /// Every recorded entry in the order in which they occurred.
+    pub entries: Vec<Entry>,
+}
+
+/// A single entry within a `Recording`.
+///
+/// Each entry is paired with the time elapsed since recording began.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Entry {
+    /// An `event::Input` was passed to `Ui::handle_event`.
+    Input(std::time::Duration, event::Input),
+    /// `Ui::set_widgets` was called.
+    SetWidgets(std::time::Duration),
+}
+
+/// Accumulates a `Recording` from the input received by a `Ui`.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    start: instant::Instant,
+    recording: Recording,
+}
+
+/// Feeds a `Recording` into a `Ui` with the same timing with which it was recorded.
+#[derive(Clone, Debug)]
+pub struct Player {
+    recording: Recording,
+    clock: ManualClock,
+    start: instant::Instant,
+    next_entry: usize,
+}
+
+
+impl Entry {
+
+    /// The time at which the entry occurred relative to the beginning of the recording.
+    pub fn time(&self) -> std::time::Duration {
+        match *self {
+            Entry::Input(time, _) | Entry::SetWidgets(time) => time,
+        }
+    }
+
+}
+
+impl Recorder {
+
+    /// Begin a new recording at the moment `start` within a window of the given dimensions.
+    pub fn new(start: instant::Instant, window_dimensions: Dimensions) -> Self {
+        Recorder {
+            start: start,
+            recording: Recording {
+                window_dimensions: window_dimensions,
+                entries: Vec::new(),
+            },
+        }
+    }
+
+    /// Record the given input which was received at the moment `now`.
+    pub fn input(&mut self, now: instant::Instant, input: event::Input) {
+        let time = now.duration_since(self.start);
+        self.recording.entries.push(Entry::Input(time, input));
+    }
+
+    /// Record a call to `Ui::set_widgets` at the moment `now`.
+    pub fn set_widgets(&mut self, now: instant::Instant) {
+        let time = now.duration_since(self.start);
+        self.recording.entries.push(Entry::SetWidgets(time));
+    }
+
+    /// The recording so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Finish recording and produce the resulting `Recording`.
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+
+}
+
+impl Player {
+
+    /// Construct a new **Player** for the given recording.
+    ///
+    /// Playback begins at the current system time.
+    pub fn new(recording: Recording) -> Self {
+        let start = instant::Instant::now();
+        Player {
+            recording: recording,
+            clock: ManualClock::starting_at(start),
+            start: start,
+            next_entry: 0,
+        }
+    }
+
+    /// The clock that drives the virtual time of playback.
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    /// A `UiBuilder` for a fresh `Ui` suitable for playing back the recording.
+    ///
+    /// The `Ui` will have the recorded window dimensions and be driven by the player's clock.
+    pub fn ui_builder(&self) -> UiBuilder {
+        UiBuilder::new(self.recording.window_dimensions)
+            .clock(self.clock.clone())
+    }
+
+    /// Whether or not every entry in the recording has been played.
+    pub fn is_finished(&self) -> bool {
+        self.next_entry >= self.recording.entries.len()
+    }
+
+    /// Feed each recorded input into the `Ui` until reaching the next recorded call to
+    /// `Ui::set_widgets`.
+    ///
+    /// The player's clock is set to the time of each entry as it is reached. Returns `true` if a
+    /// call to `set_widgets` was reached, in which case the caller should now instantiate their
+    /// widgets. Returns `false` once the end of the recording is reached.
+    pub fn play_until_set_widgets(&mut self, ui: &mut Ui) -> bool {
+        while let Some(entry) = self.recording.entries.get(self.next_entry) {
+            self.next_entry += 1;
+            self.clock.set(self.start + entry.time());
+            match *entry {
+                Entry::Input(_, ref input) => ui.handle_event(input.clone()),
+                Entry::SetWidgets(_) => return true,
+            }
+        }
+        false
+    }
+
+    /// Play the entire remainder of the recording into the `Ui`.
+    ///
+    /// `set_widgets` is called at each point at which `Ui::set_widgets` was called during the
+    /// recording.
+    pub fn play<F>(&mut self, ui: &mut Ui, mut set_widgets: F)
+        where F: FnMut(&mut Ui),
+    {
+        while self.play_until_set_widgets(ui) {
+            set_widgets(ui);
+        }
+    }
+
+}
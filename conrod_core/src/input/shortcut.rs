@@ -0,0 +1,399 @@
+//! Keyboard shortcuts (also known as accelerators) that are registered with the `Ui`.
+//!
+//! A `Shortcut` is a sequence of one or more `Chord`s, where each `Chord` is a set of modifier
+//! keys held while pressing some key, e.g. `Ctrl+S` or the two-step `Ctrl+K Ctrl+S`.
+//!
+//! Shortcuts are registered via `Ui::register_shortcut` along with a `Scope` describing when they
+//! are active. When the keys of a registered shortcut are pressed, the `Ui` produces an
+//! `event::Ui::Shortcut` event in place of the key presses, so that widgets capturing the keyboard
+//! (e.g. a `TextEdit`) never receive them.
+
+use input::{Key, ModifierKey};
+use std;
+use widget;
+
+
+/// A set of modifier keys held while pressing some `key`, e.g. `Ctrl+Shift+S`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Chord {
+    /// The exact set of modifiers that must be held.
+    pub modifiers: ModifierKey,
+    /// The key that must be pressed while the modifiers are held.
+    pub key: Key,
+}
+
+/// A sequence of one or more `Chord`s that must be pressed in order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    chords: Vec<Chord>,
+}
+
+/// Describes when a registered `Shortcut` is active.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// The shortcut is always active.
+    Global,
+    /// The shortcut is only active while the given widget, or one of its descendants, is
+    /// capturing the keyboard.
+    Widget(widget::Id),
+}
+
+/// A unique identifier for a registered `Shortcut`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(usize);
+
+/// The error returned when registering a `Shortcut` that would conflict with one that is already
+/// registered.
+///
+/// Two shortcuts conflict if they share a `Scope` and one is the same as, or begins with, the
+/// other. For example, `Ctrl+K` conflicts with `Ctrl+K Ctrl+S` as the latter could never be
+/// triggered.
+///
+/// Shortcuts within different scopes never conflict. If the same chords complete shortcuts within
+/// several active scopes, the shortcut within the innermost scope is triggered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    /// The already registered shortcut with which the new shortcut conflicts.
+    pub existing: Id,
+}
+
+/// The result of feeding a `Chord` to the `Registry`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Match {
+    /// The chord completed the shortcut with the given `Id`.
+    Complete(Id),
+    /// The chord began or continued a sequence that may complete some shortcut.
+    Partial,
+    /// The chord is not part of any active shortcut.
+    None,
+}
+
+/// Stores all registered shortcuts along with the progress of any partially entered sequence.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+    next_id: usize,
+    /// The chords of a partially entered sequence.
+    pending: Vec<Chord>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    id: Id,
+    shortcut: Shortcut,
+    scope: Scope,
+}
+
+
+impl Chord {
+
+    /// Construct a new **Chord** from the given modifiers and key.
+    pub fn new(modifiers: ModifierKey, key: Key) -> Self {
+        Chord { modifiers: modifiers, key: key }
+    }
+
+    /// A **Chord** consisting of the given key alone.
+    pub fn key(key: Key) -> Self {
+        Chord::new(ModifierKey::NO_MODIFIER, key)
+    }
+
+    /// A **Chord** of the given key while holding `Ctrl`.
+    pub fn ctrl(key: Key) -> Self {
+        Chord::new(ModifierKey::CTRL, key)
+    }
+
+}
+
+impl Shortcut {
+
+    /// A **Shortcut** triggered by the given chord.
+    pub fn new(chord: Chord) -> Self {
+        Shortcut { chords: vec![chord] }
+    }
+
+    /// Extend the **Shortcut** so that the given chord must be pressed after the existing chords.
+    pub fn then(mut self, chord: Chord) -> Self {
+        self.chords.push(chord);
+        self
+    }
+
+    /// The chords that make up the shortcut, in the order in which they must be pressed.
+    pub fn chords(&self) -> &[Chord] {
+        &self.chords
+    }
+
+    /// Whether or not the given sequence of chords begins this shortcut.
+    fn starts_with(&self, chords: &[Chord]) -> bool {
+        self.chords.len() >= chords.len() && &self.chords[..chords.len()] == chords
+    }
+
+}
+
+impl From<Chord> for Shortcut {
+    fn from(chord: Chord) -> Self {
+        Shortcut::new(chord)
+    }
+}
+
+impl Registry {
+
+    /// Construct an empty **Registry**.
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Register a new shortcut that is active within the given `scope`.
+    ///
+    /// Returns a `Conflict` if the shortcut conflicts with an existing shortcut within the same
+    /// scope, in which case the shortcut is not registered.
+    pub fn register<S>(&mut self, shortcut: S, scope: Scope) -> Result<Id, Conflict>
+        where S: Into<Shortcut>,
+    {
+        let shortcut = shortcut.into();
+        if let Some(existing) = self.conflict(&shortcut, scope) {
+            return Err(Conflict { existing: existing });
+        }
+        let id = Id(self.next_id);
+        self.next_id += 1;
+        self.entries.push(Entry { id: id, shortcut: shortcut, scope: scope });
+        Ok(id)
+    }
+
+    /// Remove the shortcut with the given `Id`.
+    ///
+    /// Returns the removed shortcut, or `None` if there was no shortcut with the given `Id`.
+    pub fn unregister(&mut self, id: Id) -> Option<Shortcut> {
+        let idx = match self.entries.iter().position(|entry| entry.id == id) {
+            Some(idx) => idx,
+            None => return None,
+        };
+        self.pending.clear();
+        Some(self.entries.remove(idx).shortcut)
+    }
+
+    /// The existing shortcut within the given scope that conflicts with the given shortcut, if
+    /// any.
+    pub fn conflict(&self, shortcut: &Shortcut, scope: Scope) -> Option<Id> {
+        self.entries.iter()
+            .find(|entry| {
+                entry.scope == scope
+                    && (entry.shortcut.starts_with(&shortcut.chords)
+                        || shortcut.starts_with(&entry.shortcut.chords))
+            })
+            .map(|entry| entry.id)
+    }
+
+    /// The shortcut registered with the given `Id`.
+    pub fn get(&self, id: Id) -> Option<&Shortcut> {
+        self.entries.iter().find(|entry| entry.id == id).map(|entry| &entry.shortcut)
+    }
+
+    /// The scope within which the shortcut with the given `Id` is active.
+    pub fn scope(&self, id: Id) -> Option<Scope> {
+        self.entries.iter().find(|entry| entry.id == id).map(|entry| entry.scope)
+    }
+
+    /// Whether or not a sequence of chords has been partially entered.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Feed a pressed chord to the registry.
+    ///
+    /// `scope_distance` returns `None` if the shortcuts of the given scope are currently inactive.
+    /// Otherwise it returns the distance of the scope from the widget capturing the keyboard, where
+    /// a `Global` scope is the most distant. If the chords complete or begin shortcuts within
+    /// several active scopes, the shortcut within the nearest scope takes precedence.
+    ///
+    /// If the chord does not continue a partially entered sequence, the sequence is abandoned and
+    /// the chord is checked as the beginning of a new sequence.
+    pub fn chord<F>(&mut self, chord: Chord, scope_distance: F) -> Match
+        where F: Fn(Scope) -> Option<usize>,
+    {
+        self.pending.push(chord);
+        let result = self.match_pending(&scope_distance);
+        if result == Match::None && self.pending.len() > 1 {
+            self.pending.clear();
+            self.pending.push(chord);
+            return self.match_pending(&scope_distance);
+        }
+        result
+    }
+
+    fn match_pending<F>(&mut self, scope_distance: &F) -> Match
+        where F: Fn(Scope) -> Option<usize>,
+    {
+        // The nearest shortcut completed by the pending chords and the nearest shortcut that the
+        // pending chords only begin.
+        let mut nearest_complete: Option<(usize, Id)> = None;
+        let mut nearest_partial: Option<usize> = None;
+        for entry in &self.entries {
+            let distance = match scope_distance(entry.scope) {
+                Some(distance) => distance,
+                None => continue,
+            };
+            if entry.shortcut.chords == self.pending {
+                if nearest_complete.map_or(true, |(nearest, _)| distance < nearest) {
+                    nearest_complete = Some((distance, entry.id));
+                }
+            } else if entry.shortcut.starts_with(&self.pending) {
+                if nearest_partial.map_or(true, |nearest| distance < nearest) {
+                    nearest_partial = Some(distance);
+                }
+            }
+        }
+        match (nearest_complete, nearest_partial) {
+            (Some((complete, _)), Some(partial)) if partial < complete => Match::Partial,
+            (Some((_, id)), _) => {
+                self.pending.clear();
+                Match::Complete(id)
+            },
+            (None, Some(_)) => Match::Partial,
+            (None, None) => {
+                self.pending.clear();
+                Match::None
+            },
+        }
+    }
+}
+
+
+/// The human-readable name of the given key, e.g. for display within a menu.
+pub fn key_name(key: Key) -> std::borrow::Cow<'static, str> {
+    let name = match key {
+        Key::D0 | Key::NumPad0 => "0",
+        Key::D1 | Key::NumPad1 => "1",
+        Key::D2 | Key::NumPad2 => "2",
+        Key::D3 | Key::NumPad3 => "3",
+        Key::D4 | Key::NumPad4 => "4",
+        Key::D5 | Key::NumPad5 => "5",
+        Key::D6 | Key::NumPad6 => "6",
+        Key::D7 | Key::NumPad7 => "7",
+        Key::D8 | Key::NumPad8 => "8",
+        Key::D9 | Key::NumPad9 => "9",
+        Key::Return | Key::NumPadEnter => "Enter",
+        Key::Escape => "Esc",
+        Key::Delete => "Del",
+        Key::PageUp => "Page Up",
+        Key::PageDown => "Page Down",
+        Key::Plus | Key::NumPadPlus => "+",
+        Key::Minus | Key::NumPadMinus => "-",
+        Key::Equals | Key::NumPadEquals => "=",
+        Key::Comma | Key::NumPadComma => ",",
+        Key::Period | Key::NumPadPeriod => ".",
+        Key::Slash | Key::NumPadDivide => "/",
+        Key::Backslash => "\\",
+        Key::Semicolon => ";",
+        Key::Quote => "'",
+        Key::Backquote => "`",
+        Key::LeftBracket => "[",
+        Key::RightBracket => "]",
+        key => return format!("{:?}", key).into(),
+    };
+    name.into()
+}
+
+/// The human-readable names of the modifier keys in the order in which they are displayed.
+#[cfg(target_os = "macos")]
+const MODIFIER_NAMES: [(ModifierKey, &'static str); 4] = [
+    (ModifierKey::CTRL, "Ctrl"),
+    (ModifierKey::ALT, "Option"),
+    (ModifierKey::SHIFT, "Shift"),
+    (ModifierKey::GUI, "Cmd"),
+];
+
+/// The human-readable names of the modifier keys in the order in which they are displayed.
+#[cfg(not(target_os = "macos"))]
+const MODIFIER_NAMES: [(ModifierKey, &'static str); 4] = [
+    (ModifierKey::CTRL, "Ctrl"),
+    (ModifierKey::GUI, "Super"),
+    (ModifierKey::ALT, "Alt"),
+    (ModifierKey::SHIFT, "Shift"),
+];
+
+impl std::fmt::Display for Chord {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for &(modifier, name) in MODIFIER_NAMES.iter() {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", key_name(self.key))
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, chord) in self.chords.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[test]
+fn registry_should_match_chord_sequences() {
+    let mut registry = Registry::new();
+    let save = registry.register(Chord::ctrl(Key::S), Scope::Global).unwrap();
+    let save_all = Shortcut::new(Chord::ctrl(Key::K)).then(Chord::ctrl(Key::S));
+    let save_all = registry.register(save_all, Scope::Global).unwrap();
+    let active = |_| Some(0);
+
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), &active), Match::Complete(save));
+    assert_eq!(registry.chord(Chord::ctrl(Key::K), &active), Match::Partial);
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), &active), Match::Complete(save_all));
+
+    // An unrelated chord abandons the sequence, but may begin another.
+    assert_eq!(registry.chord(Chord::ctrl(Key::K), &active), Match::Partial);
+    assert_eq!(registry.chord(Chord::key(Key::A), &active), Match::None);
+    assert!(!registry.is_pending());
+    assert_eq!(registry.chord(Chord::ctrl(Key::K), &active), Match::Partial);
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), &active), Match::Complete(save_all));
+
+    // Inactive scopes are ignored.
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), &|_| None), Match::None);
+}
+
+#[test]
+fn registry_should_prefer_shortcuts_within_the_nearest_scope() {
+    let mut registry = Registry::new();
+    let edit = widget::Id::new(0);
+    let global = registry.register(Chord::ctrl(Key::S), Scope::Global).unwrap();
+    let widget = registry.register(Chord::ctrl(Key::S), Scope::Widget(edit)).unwrap();
+    let distance = |is_editing| move |scope| match scope {
+        Scope::Global => Some(std::usize::MAX),
+        Scope::Widget(_) => if is_editing { Some(0) } else { None },
+    };
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), distance(true)), Match::Complete(widget));
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), distance(false)), Match::Complete(global));
+
+    // A sequence within a nearer scope takes precedence over a complete shortcut further out.
+    let k = registry.register(Chord::ctrl(Key::K), Scope::Global).unwrap();
+    let save_all = Shortcut::new(Chord::ctrl(Key::K)).then(Chord::ctrl(Key::S));
+    let save_all = registry.register(save_all, Scope::Widget(edit)).unwrap();
+    assert_eq!(registry.chord(Chord::ctrl(Key::K), distance(true)), Match::Partial);
+    assert_eq!(registry.chord(Chord::ctrl(Key::S), distance(true)), Match::Complete(save_all));
+    assert_eq!(registry.chord(Chord::ctrl(Key::K), distance(false)), Match::Complete(k));
+}
+
+#[test]
+fn registry_should_detect_conflicts_within_the_same_scope() {
+    let mut registry = Registry::new();
+    let k = registry.register(Chord::ctrl(Key::K), Scope::Global).unwrap();
+    let sequence = Shortcut::new(Chord::ctrl(Key::K)).then(Chord::ctrl(Key::S));
+    assert_eq!(registry.register(sequence.clone(), Scope::Global), Err(Conflict { existing: k }));
+    assert_eq!(registry.register(Chord::ctrl(Key::K), Scope::Global), Err(Conflict { existing: k }));
+    let scope = Scope::Widget(widget::Id::new(0));
+    assert!(registry.register(sequence, scope).is_ok());
+}
+
+#[test]
+fn shortcuts_should_display_as_human_readable_strings() {
+    let shift_ctrl = ModifierKey::CTRL | ModifierKey::SHIFT;
+    let shortcut = Shortcut::new(Chord::ctrl(Key::K)).then(Chord::new(shift_ctrl, Key::D1));
+    assert_eq!(shortcut.to_string(), "Ctrl+K Ctrl+Shift+1");
+}
@@ -36,6 +36,11 @@ pub struct State {
     /// If the mouse is currently over multiple widgets, this index will represent the top-most,
     /// non-graphic-child widget.
     pub widget_under_mouse: Option<widget::Id>,
+    /// The moment at which the current `widget_under_mouse` first became the widget under the
+    /// mouse.
+    ///
+    /// This is `None` whenever the `widget_under_mouse` is `None`.
+    pub widget_under_mouse_since: Option<instant::Instant>,
     /// Which modifier keys are being held down.
     pub modifiers: ModifierKey,
 }
@@ -50,6 +55,7 @@ impl State {
             widget_capturing_keyboard: None,
             widget_capturing_mouse: None,
             widget_under_mouse: None,
+            widget_under_mouse_since: None,
             modifiers: ModifierKey::NO_MODIFIER,
         }
     }
@@ -447,9 +447,45 @@ impl<'a> Iterator for Events<'a> {
                     }
                 },
 
+                event::Ui::MouseEnter(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseEnter),
+
+                event::Ui::MouseLeave(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseLeave),
+
+                event::Ui::DragStart(idx) if idx == self.idx =>
+                    return Some(event::Widget::DragStart),
+
+                event::Ui::DragEnter(idx) if idx == self.idx =>
+                    return Some(event::Widget::DragEnter),
+
+                event::Ui::DragOver(idx, xy) if idx == self.idx =>
+                    return Some(event::Widget::DragOver(utils::vec2_sub(xy, self.rect.xy()))),
+
+                event::Ui::DragLeave(idx) if idx == self.idx =>
+                    return Some(event::Widget::DragLeave),
+
+                event::Ui::Drop(idx, xy) if idx == self.idx =>
+                    return Some(event::Widget::Drop(utils::vec2_sub(xy, self.rect.xy()))),
+
+                event::Ui::DragEnd(idx, target) if idx == self.idx =>
+                    return Some(event::Widget::DragEnd(target)),
+
+                event::Ui::DroppedFile(idx, ref path) if idx == Some(self.idx) =>
+                    return Some(event::Widget::DroppedFile(path.clone())),
+
+                event::Ui::HoveredFile(idx, ref path) if idx == Some(self.idx) =>
+                    return Some(event::Widget::HoveredFile(path.clone())),
+
+                event::Ui::HoveredFileCancelled(idx) if idx == Some(self.idx) =>
+                    return Some(event::Widget::HoveredFileCancelled),
+
                 event::Ui::WindowResized(dim) =>
                     return Some(event::Widget::WindowResized(dim)),
 
+                event::Ui::ScaleFactorChanged(scale_factor) =>
+                    return Some(event::Widget::ScaleFactorChanged(scale_factor)),
+
                 event::Ui::Text(idx, ref text) if idx == Some(self.idx) =>
                     return Some(text.clone().into()),
 
@@ -471,9 +507,27 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) =>
                     return Some(double_click.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::TripleClick(idx, ref triple_click) if idx == Some(self.idx) =>
+                    return Some(triple_click.clone().relative_to(self.rect.xy()).into()),
+
                 event::Ui::Tap(idx, ref tap) if idx == Some(self.idx) =>
                     return Some(tap.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::Pinch(idx, pinch) if idx == Some(self.idx) =>
+                    return Some(pinch.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Rotate(idx, rotate) if idx == Some(self.idx) =>
+                    return Some(rotate.relative_to(self.rect.xy()).into()),
+
+                event::Ui::LongPress(idx, long_press) if idx == Some(self.idx) =>
+                    return Some(long_press.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Swipe(idx, swipe) if idx == Some(self.idx) =>
+                    return Some(swipe.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Shortcut(idx, shortcut) if idx == Some(self.idx) =>
+                    return Some(event::Widget::Shortcut(shortcut)),
+
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
                     return Some(drag.clone().relative_to(self.rect.xy()).into()),
 
@@ -1,6 +1,9 @@
 
 use color::{Color, hsl, hsla, rgb, rgba};
+use position::{Dimensions, Scalar};
+use text;
 use ui::Ui;
+use widget;
 
 /// Font size used throughout Conrod.
 pub type FontSize = u32;
@@ -53,3 +56,39 @@ pub trait Labelable<'a>: Sized {
     }
 
 }
+
+/// The dimensions of the given label when measured with the given font, along with the border on
+/// either side of the widget displaying it.
+///
+/// If no font is given, the first font within the `Ui` is used. Returns `None` if no font is
+/// available.
+pub fn bordered_dimensions(label: &str,
+                           maybe_font_id: Option<text::font::Id>,
+                           font_size: FontSize,
+                           border: Scalar,
+                           ui: &Ui) -> Option<Dimensions>
+{
+    let line_spacing = widget::text::Style::default().line_spacing(&ui.theme);
+    padded_dimensions(label, maybe_font_id, font_size, line_spacing, border, ui)
+}
+
+/// The dimensions of the given label when measured with the given font and `line_spacing`, along
+/// with the given `padding` on either side.
+///
+/// If no font is given, the first font within the `Ui` is used. Returns `None` if no font is
+/// available.
+pub fn padded_dimensions(label: &str,
+                         maybe_font_id: Option<text::font::Id>,
+                         font_size: FontSize,
+                         line_spacing: Scalar,
+                         padding: Scalar,
+                         ui: &Ui) -> Option<Dimensions>
+{
+    maybe_font_id
+        .or(ui.fonts.ids().next())
+        .and_then(|id| ui.fonts.get(id))
+        .map(|font| {
+            let dim = text::dimensions(label, font, font_size, line_spacing);
+            [dim[0] + padding * 2.0, dim[1] + padding * 2.0]
+        })
+}
@@ -14,6 +14,10 @@ extern crate fnv;
 extern crate num;
 extern crate input as piston_input;
 extern crate rusttype;
+#[cfg(feature = "regex")] extern crate regex;
+#[cfg(feature = "serialize")] extern crate serde;
+#[cfg(feature = "serialize")] #[macro_use] extern crate serde_derive;
+#[cfg(all(test, feature = "serialize"))] extern crate serde_json;
 
 pub use color::{Color, Colorable};
 pub use conrod_derive::*;
@@ -24,8 +28,11 @@ pub use theme::Theme;
 pub use ui::{Ui, UiCell, UiBuilder};
 pub use widget::{scroll, Widget};
 
+pub mod animation;
 mod border;
+pub mod clock;
 pub mod color;
+pub mod drag_drop;
 pub mod event;
 pub mod graph;
 pub mod guide;
@@ -34,6 +41,7 @@ pub mod input;
 mod label;
 pub mod position;
 pub mod render;
+pub mod testing;
 pub mod text;
 pub mod theme;
 mod ui;
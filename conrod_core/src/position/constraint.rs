@@ -0,0 +1,1106 @@
+//! Constraint-based layout.
+//!
+//! Relationships between widgets that cannot be described via a single **Position** or
+//! **Dimension** may be described as linear `Constraint`s between the anchors of each widget, for
+//! example:
+//!
+//! - `a.right().is_eq(b.left - 8.0)`
+//! - `a.width.is_ge(120.0)`
+//! - `a.width.is_eq(2.0 * b.width).strength(constraint::MEDIUM)`
+//!
+//! The constraints are solved by an incremental implementation of the Cassowary linear arithmetic
+//! constraint solving algorithm. Constraints that are not `REQUIRED` are satisfied as well as
+//! possible in order of their **Strength**.
+//!
+//! Each `Ui` owns a constraint `Layout` which may be accessed via `Ui::constraints_mut`. Widgets
+//! may be positioned and sized by the layout via `Positionable::xy_constrained` and
+//! `Sizeable::wh_constrained`. Widgets that are referred to by some constraint but are positioned
+//! or sized in some other manner have their resolved position and dimensions fed back into the
+//! layout as they are set.
+//!
+//! Constraints whose terms change between updates, e.g. a width that follows some application
+//! state, should be added via `Layout::constrain_for` so that each replaces the last. The anchors
+//! of widgets that are no longer set are removed at the end of each update, along with every
+//! constraint that refers to them.
+
+use fnv;
+use position::{Range, Rect, Scalar};
+use std;
+use std::collections::BTreeMap;
+use std::sync::atomic::{self, AtomicUsize};
+use widget;
+
+
+/// The strength of a `Constraint`.
+///
+/// When constraints conflict, those with a greater strength are favoured.
+pub type Strength = f64;
+
+/// The constraint must be satisfied.
+pub const REQUIRED: Strength = 1_001_001_000.0;
+/// A strong preference.
+pub const STRONG: Strength = 1_000_000.0;
+/// A medium preference.
+pub const MEDIUM: Strength = 1_000.0;
+/// A weak preference.
+pub const WEAK: Strength = 1.0;
+
+/// Coefficients smaller than this are considered to be zero.
+const EPSILON: Scalar = 1.0e-8;
+
+/// An unknown value that is solved for by the `Solver`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variable(usize);
+
+/// A linear expression of variables, i.e. `a * x + b * y + c`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    /// Each variable along with its coefficient.
+    pub terms: Vec<(Variable, Scalar)>,
+    /// The constant added to the sum of the terms.
+    pub constant: Scalar,
+}
+
+/// The relationship between the two sides of a `Constraint`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// The left-hand side is less than or equal to the right-hand side.
+    LessOrEqual,
+    /// The left-hand side is equal to the right-hand side.
+    Equal,
+    /// The left-hand side is greater than or equal to the right-hand side.
+    GreaterOrEqual,
+}
+
+/// A linear relationship between variables along with the strength at which it should hold.
+///
+/// Constraints are created via the `is_eq`, `is_le` and `is_ge` methods of `Variable` and
+/// `Expression` and are `REQUIRED` by default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraint {
+    /// The difference between the left and right-hand sides, compared against zero.
+    expression: Expression,
+    relation: Relation,
+    strength: Strength,
+}
+
+/// The errors that may occur while adding or removing constraints and edit variables.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The constraint has already been added to the solver.
+    DuplicateConstraint,
+    /// The constraint is `REQUIRED` but conflicts with some other `REQUIRED` constraint.
+    UnsatisfiableConstraint,
+    /// The constraint has not been added to the solver.
+    UnknownConstraint,
+    /// The variable has already been added to the solver as an edit variable.
+    DuplicateEditVariable,
+    /// The variable has not been added to the solver as an edit variable.
+    UnknownEditVariable,
+    /// Edit variables may not be `REQUIRED`.
+    RequiredEditVariable,
+}
+
+/// An incremental solver for a system of linear `Constraint`s.
+///
+/// Constraints may be added and removed at any time and the values of *edit* variables may be
+/// suggested, after which the value of each variable is immediately available via `value_of`.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    constraints: Vec<(Constraint, Tag)>,
+    rows: BTreeMap<Symbol, Row>,
+    vars: fnv::FnvHashMap<Variable, Symbol>,
+    edits: fnv::FnvHashMap<Variable, Edit>,
+    infeasible_rows: Vec<Symbol>,
+    objective: Row,
+    artificial: Option<Row>,
+    next_symbol_id: usize,
+}
+
+/// The variables that describe the rectangle occupied by a widget within a `Layout`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Anchors {
+    /// The position of the left edge.
+    pub left: Variable,
+    /// The position of the bottom edge.
+    pub bottom: Variable,
+    /// The width of the widget.
+    pub width: Variable,
+    /// The height of the widget.
+    pub height: Variable,
+}
+
+/// A set of constraints between the `Anchors` of widgets.
+///
+/// See the module documentation for details.
+#[derive(Clone, Debug)]
+pub struct Layout {
+    solver: Solver,
+    anchors: fnv::FnvHashMap<widget::Id, Anchors>,
+    /// The constraints added via `constrain_for`, by the widget and key under which they were
+    /// added.
+    keyed: fnv::FnvHashMap<(widget::Id, usize), Constraint>,
+}
+
+
+/// A symbol within the solver's tableau.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Symbol {
+    id: usize,
+    kind: SymbolKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum SymbolKind {
+    External,
+    Slack,
+    Error,
+    Dummy,
+}
+
+/// A row of the tableau, representing `constant + sum(coefficient * symbol)`.
+#[derive(Clone, Debug)]
+struct Row {
+    cells: BTreeMap<Symbol, Scalar>,
+    constant: Scalar,
+}
+
+/// The symbols introduced to the tableau by a single constraint.
+#[derive(Copy, Clone, Debug)]
+struct Tag {
+    marker: Symbol,
+    other: Option<Symbol>,
+}
+
+#[derive(Clone, Debug)]
+struct Edit {
+    constraint: Constraint,
+    tag: Tag,
+    constant: Scalar,
+}
+
+
+fn near_zero(value: Scalar) -> bool {
+    value.abs() < EPSILON
+}
+
+
+impl Variable {
+    /// Produce a new, unique **Variable**.
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Variable(NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+
+    /// A constraint requiring that the variable is equal to the given expression.
+    pub fn is_eq<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Expression::from(self).is_eq(rhs)
+    }
+
+    /// A constraint requiring that the variable is less than or equal to the given expression.
+    pub fn is_le<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Expression::from(self).is_le(rhs)
+    }
+
+    /// A constraint requiring that the variable is greater than or equal to the given expression.
+    pub fn is_ge<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Expression::from(self).is_ge(rhs)
+    }
+}
+
+impl Expression {
+    /// An expression with no terms.
+    pub fn constant(constant: Scalar) -> Self {
+        Expression { terms: Vec::new(), constant: constant }
+    }
+
+    /// A constraint requiring that the expression is equal to the given expression.
+    pub fn is_eq<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs, Relation::Equal, REQUIRED)
+    }
+
+    /// A constraint requiring that the expression is less than or equal to the given expression.
+    pub fn is_le<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs, Relation::LessOrEqual, REQUIRED)
+    }
+
+    /// A constraint requiring that the expression is greater than or equal to the given
+    /// expression.
+    pub fn is_ge<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs, Relation::GreaterOrEqual, REQUIRED)
+    }
+
+    /// Combine the terms of each variable and remove those with a zero coefficient.
+    fn normalized(self) -> Self {
+        let mut terms: BTreeMap<Variable, Scalar> = BTreeMap::new();
+        for (variable, coefficient) in self.terms {
+            *terms.entry(variable).or_insert(0.0) += coefficient;
+        }
+        Expression {
+            terms: terms.into_iter().filter(|&(_, c)| !near_zero(c)).collect(),
+            constant: self.constant,
+        }
+    }
+
+    fn scaled(mut self, scale: Scalar) -> Self {
+        for term in &mut self.terms {
+            term.1 *= scale;
+        }
+        self.constant *= scale;
+        self
+    }
+}
+
+impl From<Variable> for Expression {
+    fn from(variable: Variable) -> Self {
+        Expression { terms: vec![(variable, 1.0)], constant: 0.0 }
+    }
+}
+
+impl From<Scalar> for Expression {
+    fn from(constant: Scalar) -> Self {
+        Expression::constant(constant)
+    }
+}
+
+impl<T: Into<Expression>> std::ops::Add<T> for Expression {
+    type Output = Expression;
+    fn add(mut self, rhs: T) -> Expression {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl<T: Into<Expression>> std::ops::Sub<T> for Expression {
+    type Output = Expression;
+    fn sub(self, rhs: T) -> Expression {
+        self + rhs.into().scaled(-1.0)
+    }
+}
+
+impl std::ops::Mul<Scalar> for Expression {
+    type Output = Expression;
+    fn mul(self, rhs: Scalar) -> Expression {
+        self.scaled(rhs)
+    }
+}
+
+impl std::ops::Div<Scalar> for Expression {
+    type Output = Expression;
+    fn div(self, rhs: Scalar) -> Expression {
+        self.scaled(1.0 / rhs)
+    }
+}
+
+impl std::ops::Neg for Expression {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        self.scaled(-1.0)
+    }
+}
+
+impl<T: Into<Expression>> std::ops::Add<T> for Variable {
+    type Output = Expression;
+    fn add(self, rhs: T) -> Expression {
+        Expression::from(self) + rhs
+    }
+}
+
+impl<T: Into<Expression>> std::ops::Sub<T> for Variable {
+    type Output = Expression;
+    fn sub(self, rhs: T) -> Expression {
+        Expression::from(self) - rhs
+    }
+}
+
+impl std::ops::Mul<Scalar> for Variable {
+    type Output = Expression;
+    fn mul(self, rhs: Scalar) -> Expression {
+        Expression::from(self) * rhs
+    }
+}
+
+impl std::ops::Div<Scalar> for Variable {
+    type Output = Expression;
+    fn div(self, rhs: Scalar) -> Expression {
+        Expression::from(self) / rhs
+    }
+}
+
+impl std::ops::Neg for Variable {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        -Expression::from(self)
+    }
+}
+
+impl std::ops::Add<Variable> for Scalar {
+    type Output = Expression;
+    fn add(self, rhs: Variable) -> Expression {
+        rhs + self
+    }
+}
+
+impl std::ops::Add<Expression> for Scalar {
+    type Output = Expression;
+    fn add(self, rhs: Expression) -> Expression {
+        rhs + self
+    }
+}
+
+impl std::ops::Sub<Variable> for Scalar {
+    type Output = Expression;
+    fn sub(self, rhs: Variable) -> Expression {
+        -rhs + self
+    }
+}
+
+impl std::ops::Sub<Expression> for Scalar {
+    type Output = Expression;
+    fn sub(self, rhs: Expression) -> Expression {
+        -rhs + self
+    }
+}
+
+impl std::ops::Mul<Variable> for Scalar {
+    type Output = Expression;
+    fn mul(self, rhs: Variable) -> Expression {
+        rhs * self
+    }
+}
+
+impl std::ops::Mul<Expression> for Scalar {
+    type Output = Expression;
+    fn mul(self, rhs: Expression) -> Expression {
+        rhs * self
+    }
+}
+
+impl Constraint {
+    /// A constraint requiring that the given expression holds the given relation to zero.
+    pub fn new(expression: Expression, relation: Relation, strength: Strength) -> Self {
+        Constraint {
+            expression: expression.normalized(),
+            relation: relation,
+            strength: strength,
+        }
+    }
+
+    /// Specify the strength of the constraint.
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// The difference between the left and right-hand sides of the constraint.
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// The relationship between the two sides of the constraint.
+    pub fn relation(&self) -> Relation {
+        self.relation
+    }
+
+    /// The strength at which the constraint should hold.
+    pub fn get_strength(&self) -> Strength {
+        self.strength
+    }
+
+    fn is_required(&self) -> bool {
+        self.strength >= REQUIRED
+    }
+}
+
+impl std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::DuplicateConstraint => "The constraint has already been added.",
+            Error::UnsatisfiableConstraint => "The required constraint cannot be satisfied.",
+            Error::UnknownConstraint => "The constraint has not been added.",
+            Error::DuplicateEditVariable => "The edit variable has already been added.",
+            Error::UnknownEditVariable => "The edit variable has not been added.",
+            Error::RequiredEditVariable => "Edit variables may not be required.",
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", std::error::Error::description(self))
+    }
+}
+
+
+impl Row {
+    fn new(constant: Scalar) -> Self {
+        Row { cells: BTreeMap::new(), constant: constant }
+    }
+
+    /// Add the given value to the constant, returning the new constant.
+    fn add(&mut self, value: Scalar) -> Scalar {
+        self.constant += value;
+        self.constant
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: Scalar) {
+        let remove = {
+            let cell = self.cells.entry(symbol).or_insert(0.0);
+            *cell += coefficient;
+            near_zero(*cell)
+        };
+        if remove {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, row: &Row, coefficient: Scalar) {
+        self.constant += row.constant * coefficient;
+        for (&symbol, &c) in &row.cells {
+            self.insert_symbol(symbol, c * coefficient);
+        }
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        for c in self.cells.values_mut() {
+            *c = -*c;
+        }
+    }
+
+    /// Solve the row for the given symbol, which must be present within the row.
+    fn solve_for(&mut self, symbol: Symbol) {
+        let coefficient = -1.0 / self.cells.remove(&symbol).expect("symbol not within row");
+        self.constant *= coefficient;
+        for c in self.cells.values_mut() {
+            *c *= coefficient;
+        }
+    }
+
+    /// Solve the row for `rhs`, where the row currently describes the value of `lhs`.
+    fn solve_for_symbols(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    fn coefficient_for(&self, symbol: Symbol) -> Scalar {
+        self.cells.get(&symbol).cloned().unwrap_or(0.0)
+    }
+
+    /// Substitute the given symbol with the given row.
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        if let Some(coefficient) = self.cells.remove(&symbol) {
+            self.insert_row(row, coefficient);
+        }
+    }
+}
+
+
+impl Solver {
+
+    /// An empty **Solver**.
+    pub fn new() -> Self {
+        Solver {
+            constraints: Vec::new(),
+            rows: BTreeMap::new(),
+            vars: fnv::FnvHashMap::default(),
+            edits: fnv::FnvHashMap::default(),
+            infeasible_rows: Vec::new(),
+            objective: Row::new(0.0),
+            artificial: None,
+            next_symbol_id: 0,
+        }
+    }
+
+    /// Whether or not the given constraint has been added to the solver.
+    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
+        self.constraints.iter().any(|&(ref c, _)| c == constraint)
+    }
+
+    /// Add the given constraint to the solver.
+    ///
+    /// Returns an error if the constraint has already been added or if it is `REQUIRED` and
+    /// cannot be satisfied, in which case the solver is left unchanged.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> Result<(), Error> {
+        if self.has_constraint(&constraint) {
+            return Err(Error::DuplicateConstraint);
+        }
+
+        let (mut row, tag) = self.create_row(&constraint);
+        match self.choose_subject(&row, &tag) {
+            Some(subject) => {
+                row.solve_for(subject);
+                self.substitute(subject, &row);
+                self.rows.insert(subject, row);
+            },
+            None if row.cells.keys().all(|s| s.kind == SymbolKind::Dummy) => {
+                if !near_zero(row.constant) {
+                    self.remove_objective_effects(&constraint, &tag);
+                    return Err(Error::UnsatisfiableConstraint);
+                }
+                self.rows.insert(tag.marker, row);
+            },
+            None => if !self.add_with_artificial_variable(row) {
+                self.constraints.push((constraint, tag));
+                self.remove_constraint_at(self.constraints.len() - 1);
+                return Err(Error::UnsatisfiableConstraint);
+            },
+        }
+
+        self.constraints.push((constraint, tag));
+        self.optimize(false);
+        Ok(())
+    }
+
+    /// Remove the given constraint from the solver.
+    pub fn remove_constraint(&mut self, constraint: &Constraint) -> Result<(), Error> {
+        match self.constraints.iter().position(|&(ref c, _)| c == constraint) {
+            Some(i) => {
+                self.remove_constraint_at(i);
+                Ok(())
+            },
+            None => Err(Error::UnknownConstraint),
+        }
+    }
+
+    /// Add a variable whose value may be suggested via `suggest_value`.
+    ///
+    /// Suggested values are satisfied at the given strength, which may not be `REQUIRED`.
+    pub fn add_edit_variable(&mut self, variable: Variable, strength: Strength)
+        -> Result<(), Error>
+    {
+        if self.edits.contains_key(&variable) {
+            return Err(Error::DuplicateEditVariable);
+        }
+        if strength >= REQUIRED {
+            return Err(Error::RequiredEditVariable);
+        }
+        let constraint = Constraint::new(variable.into(), Relation::Equal, strength);
+        self.add_constraint(constraint.clone())?;
+        let tag = self.constraints.last().expect("constraint was added").1;
+        let edit = Edit { constraint: constraint, tag: tag, constant: 0.0 };
+        self.edits.insert(variable, edit);
+        Ok(())
+    }
+
+    /// Remove the given edit variable from the solver.
+    pub fn remove_edit_variable(&mut self, variable: Variable) -> Result<(), Error> {
+        match self.edits.remove(&variable) {
+            Some(edit) => self.remove_constraint(&edit.constraint),
+            None => Err(Error::UnknownEditVariable),
+        }
+    }
+
+    /// Remove the given variables from the solver, along with any edit variables and constraints
+    /// that refer to them.
+    pub fn remove_variables(&mut self, variables: &fnv::FnvHashSet<Variable>) {
+        for variable in variables {
+            if let Some(edit) = self.edits.remove(variable) {
+                let _ = self.remove_constraint(&edit.constraint);
+            }
+        }
+        let refers_to_variables = |constraint: &Constraint| {
+            constraint.expression.terms.iter().any(|&(v, _)| variables.contains(&v))
+        };
+        while let Some(i) = self.constraints.iter().position(|&(ref c, _)| refers_to_variables(c)) {
+            self.remove_constraint_at(i);
+        }
+        for variable in variables {
+            self.vars.remove(variable);
+        }
+    }
+
+    /// Whether or not the given variable has been added as an edit variable.
+    pub fn has_edit_variable(&self, variable: Variable) -> bool {
+        self.edits.contains_key(&variable)
+    }
+
+    /// Suggest a value for the given edit variable.
+    pub fn suggest_value(&mut self, variable: Variable, value: Scalar) -> Result<(), Error> {
+        let (tag, delta) = match self.edits.get_mut(&variable) {
+            Some(edit) => {
+                let delta = value - edit.constant;
+                edit.constant = value;
+                (edit.tag, delta)
+            },
+            None => return Err(Error::UnknownEditVariable),
+        };
+
+        if let Some(row) = self.rows.get_mut(&tag.marker) {
+            if row.add(-delta) < 0.0 {
+                self.infeasible_rows.push(tag.marker);
+            }
+        } else if let Some(row) = tag.other.and_then(|other| self.rows.get_mut(&other)) {
+            if row.add(delta) < 0.0 {
+                self.infeasible_rows.push(tag.other.unwrap());
+            }
+        } else {
+            for (&symbol, row) in &mut self.rows {
+                let coefficient = row.coefficient_for(tag.marker);
+                if coefficient != 0.0
+                && row.add(delta * coefficient) < 0.0
+                && symbol.kind != SymbolKind::External {
+                    self.infeasible_rows.push(symbol);
+                }
+            }
+        }
+
+        self.dual_optimize();
+        Ok(())
+    }
+
+    /// The current value of the given variable.
+    ///
+    /// Variables that are unknown to the solver have a value of `0.0`.
+    pub fn value_of(&self, variable: Variable) -> Scalar {
+        self.vars.get(&variable)
+            .and_then(|symbol| self.rows.get(symbol))
+            .map(|row| row.constant)
+            .unwrap_or(0.0)
+    }
+
+    fn new_symbol(&mut self, kind: SymbolKind) -> Symbol {
+        let id = self.next_symbol_id;
+        self.next_symbol_id += 1;
+        Symbol { id: id, kind: kind }
+    }
+
+    fn var_symbol(&mut self, variable: Variable) -> Symbol {
+        if let Some(&symbol) = self.vars.get(&variable) {
+            return symbol;
+        }
+        let symbol = self.new_symbol(SymbolKind::External);
+        self.vars.insert(variable, symbol);
+        symbol
+    }
+
+    /// Create a row for the given constraint, adding its error terms to the objective.
+    fn create_row(&mut self, constraint: &Constraint) -> (Row, Tag) {
+        let expression = &constraint.expression;
+        let mut row = Row::new(expression.constant);
+        for &(variable, coefficient) in &expression.terms {
+            let symbol = self.var_symbol(variable);
+            match self.rows.get(&symbol) {
+                Some(basic) => row.insert_row(basic, coefficient),
+                None => row.insert_symbol(symbol, coefficient),
+            }
+        }
+
+        let strength = constraint.strength;
+        let tag = match constraint.relation {
+            Relation::LessOrEqual | Relation::GreaterOrEqual => {
+                let coefficient = match constraint.relation {
+                    Relation::LessOrEqual => 1.0,
+                    _ => -1.0,
+                };
+                let slack = self.new_symbol(SymbolKind::Slack);
+                row.insert_symbol(slack, coefficient);
+                let other = if !constraint.is_required() {
+                    let error = self.new_symbol(SymbolKind::Error);
+                    row.insert_symbol(error, -coefficient);
+                    self.objective.insert_symbol(error, strength);
+                    Some(error)
+                } else {
+                    None
+                };
+                Tag { marker: slack, other: other }
+            },
+            Relation::Equal if !constraint.is_required() => {
+                let plus = self.new_symbol(SymbolKind::Error);
+                let minus = self.new_symbol(SymbolKind::Error);
+                row.insert_symbol(plus, -1.0);
+                row.insert_symbol(minus, 1.0);
+                self.objective.insert_symbol(plus, strength);
+                self.objective.insert_symbol(minus, strength);
+                Tag { marker: plus, other: Some(minus) }
+            },
+            Relation::Equal => {
+                let dummy = self.new_symbol(SymbolKind::Dummy);
+                row.insert_symbol(dummy, 1.0);
+                Tag { marker: dummy, other: None }
+            },
+        };
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+        (row, tag)
+    }
+
+    /// Choose the symbol for which the given row should be solved.
+    fn choose_subject(&self, row: &Row, tag: &Tag) -> Option<Symbol> {
+        if let Some(&symbol) = row.cells.keys().find(|s| s.kind == SymbolKind::External) {
+            return Some(symbol);
+        }
+        let is_pivotable = |symbol: Symbol| {
+            (symbol.kind == SymbolKind::Slack || symbol.kind == SymbolKind::Error)
+                && row.coefficient_for(symbol) < 0.0
+        };
+        Some(tag.marker).into_iter().chain(tag.other)
+            .find(|&symbol| is_pivotable(symbol))
+    }
+
+    /// Add the row to the tableau using an artificial variable.
+    ///
+    /// Returns `false` if the row could not be satisfied.
+    fn add_with_artificial_variable(&mut self, row: Row) -> bool {
+        let artificial = self.new_symbol(SymbolKind::Slack);
+        self.rows.insert(artificial, row.clone());
+        self.artificial = Some(row);
+        self.optimize(true);
+        let success = near_zero(self.artificial.take().expect("artificial objective").constant);
+
+        if let Some(mut row) = self.rows.remove(&artificial) {
+            if row.cells.is_empty() {
+                return success;
+            }
+            let entering = row.cells.keys()
+                .find(|s| s.kind == SymbolKind::Slack || s.kind == SymbolKind::Error)
+                .cloned();
+            let entering = match entering {
+                Some(entering) => entering,
+                None => return false,
+            };
+            row.solve_for_symbols(artificial, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+
+        for row in self.rows.values_mut() {
+            row.cells.remove(&artificial);
+        }
+        self.objective.cells.remove(&artificial);
+        success
+    }
+
+    /// Substitute the given symbol within every row, the objective and the artificial objective.
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for (&s, r) in &mut self.rows {
+            r.substitute(symbol, row);
+            if s.kind != SymbolKind::External && r.constant < 0.0 {
+                self.infeasible_rows.push(s);
+            }
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(ref mut artificial) = self.artificial {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    /// Optimize the system for the objective, or for the artificial objective if `artificial`.
+    fn optimize(&mut self, artificial: bool) {
+        loop {
+            let entering = {
+                let objective = match artificial {
+                    true => self.artificial.as_ref().expect("artificial objective"),
+                    false => &self.objective,
+                };
+                objective.cells.iter()
+                    .find(|&(s, &c)| s.kind != SymbolKind::Dummy && c < 0.0)
+                    .map(|(&s, _)| s)
+            };
+            let entering = match entering {
+                Some(entering) => entering,
+                None => return,
+            };
+            let leaving = self.leaving_row(entering).expect("the objective is unbounded");
+            let mut row = self.rows.remove(&leaving).expect("leaving row");
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// Restore feasibility after the constants of some rows have become negative.
+    fn dual_optimize(&mut self) {
+        while let Some(leaving) = self.infeasible_rows.pop() {
+            let entering = match self.rows.get(&leaving) {
+                Some(row) if row.constant < 0.0 => {
+                    let mut ratio = std::f64::MAX;
+                    let mut entering = None;
+                    for (&symbol, &c) in &row.cells {
+                        if c > 0.0 && symbol.kind != SymbolKind::Dummy {
+                            let r = self.objective.coefficient_for(symbol) / c;
+                            if r < ratio {
+                                ratio = r;
+                                entering = Some(symbol);
+                            }
+                        }
+                    }
+                    entering.expect("the dual optimize failed")
+                },
+                _ => continue,
+            };
+            let mut row = self.rows.remove(&leaving).expect("leaving row");
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// The row that should leave the basis when the given symbol enters.
+    fn leaving_row(&self, entering: Symbol) -> Option<Symbol> {
+        let mut ratio = std::f64::MAX;
+        let mut leaving = None;
+        for (&symbol, row) in &self.rows {
+            if symbol.kind != SymbolKind::External {
+                let c = row.coefficient_for(entering);
+                if c < 0.0 {
+                    let r = -row.constant / c;
+                    if r < ratio {
+                        ratio = r;
+                        leaving = Some(symbol);
+                    }
+                }
+            }
+        }
+        leaving
+    }
+
+    /// The row that should leave the basis in order to remove the given marker.
+    fn marker_leaving_row(&self, marker: Symbol) -> Option<Symbol> {
+        let (mut r1, mut r2) = (std::f64::MAX, std::f64::MAX);
+        let (mut first, mut second, mut third) = (None, None, None);
+        for (&symbol, row) in &self.rows {
+            let c = row.coefficient_for(marker);
+            if c == 0.0 {
+                continue;
+            }
+            if symbol.kind == SymbolKind::External {
+                third = Some(symbol);
+            } else if c < 0.0 {
+                let r = -row.constant / c;
+                if r < r1 {
+                    r1 = r;
+                    first = Some(symbol);
+                }
+            } else {
+                let r = row.constant / c;
+                if r < r2 {
+                    r2 = r;
+                    second = Some(symbol);
+                }
+            }
+        }
+        first.or(second).or(third)
+    }
+
+    fn remove_constraint_at(&mut self, i: usize) {
+        let (constraint, tag) = self.constraints.remove(i);
+        self.remove_objective_effects(&constraint, &tag);
+        if self.rows.remove(&tag.marker).is_none() {
+            if let Some(leaving) = self.marker_leaving_row(tag.marker) {
+                let mut row = self.rows.remove(&leaving).expect("leaving row");
+                row.solve_for_symbols(leaving, tag.marker);
+                self.substitute(tag.marker, &row);
+            }
+        }
+        self.optimize(false);
+    }
+
+    /// Remove the error terms of the given constraint from the objective.
+    fn remove_objective_effects(&mut self, constraint: &Constraint, tag: &Tag) {
+        let strength = constraint.strength;
+        for &marker in Some(tag.marker).iter().chain(tag.other.as_ref()) {
+            if marker.kind != SymbolKind::Error {
+                continue;
+            }
+            match self.rows.get(&marker) {
+                Some(row) => self.objective.insert_row(row, -strength),
+                None => self.objective.insert_symbol(marker, -strength),
+            }
+        }
+    }
+
+}
+
+
+impl Anchors {
+    fn new() -> Self {
+        Anchors {
+            left: Variable::new(),
+            bottom: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+
+    /// The position of the right edge.
+    pub fn right(&self) -> Expression {
+        self.left + self.width
+    }
+
+    /// The position of the top edge.
+    pub fn top(&self) -> Expression {
+        self.bottom + self.height
+    }
+
+    /// The position of the centre along the *x* axis.
+    pub fn x(&self) -> Expression {
+        self.left + self.width / 2.0
+    }
+
+    /// The position of the centre along the *y* axis.
+    pub fn y(&self) -> Expression {
+        self.bottom + self.height / 2.0
+    }
+}
+
+impl Layout {
+
+    /// An empty **Layout**.
+    pub fn new() -> Self {
+        Layout {
+            solver: Solver::new(),
+            anchors: fnv::FnvHashMap::default(),
+            keyed: fnv::FnvHashMap::default(),
+        }
+    }
+
+    /// The `Anchors` of the widget with the given `widget::Id`.
+    ///
+    /// The anchors are created upon first use, along with constraints requiring that the width and
+    /// height are non-negative. If the widget is not set during an update, its anchors are removed
+    /// at the end of that update along with every constraint that refers to them.
+    pub fn anchors(&mut self, id: widget::Id) -> Anchors {
+        if let Some(&anchors) = self.anchors.get(&id) {
+            return anchors;
+        }
+        let anchors = Anchors::new();
+        for &dimension in &[anchors.width, anchors.height] {
+            self.solver.add_constraint(dimension.is_ge(0.0))
+                .expect("a new variable is always satisfiable");
+        }
+        self.anchors.insert(id, anchors);
+        anchors
+    }
+
+    /// The `Anchors` of the widget with the given `widget::Id` if they have been created.
+    pub fn get_anchors(&self, id: widget::Id) -> Option<Anchors> {
+        self.anchors.get(&id).cloned()
+    }
+
+    /// Add the given constraint to the layout if it has not already been added.
+    ///
+    /// This allows for describing constraints each time widgets are set. Returns an error if the
+    /// constraint is `REQUIRED` and cannot be satisfied.
+    ///
+    /// A constraint that differs in any way from one added previously is a new constraint, so a
+    /// `REQUIRED` constraint whose constant changes between updates conflicts with its previous
+    /// form. Use `constrain_for` to describe such constraints instead.
+    pub fn constrain(&mut self, constraint: Constraint) -> Result<(), Error> {
+        match self.solver.add_constraint(constraint) {
+            Err(Error::DuplicateConstraint) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Add the given constraint to the layout under the given `key` for the widget with the given
+    /// `widget::Id`, replacing the constraint previously added under the same `id` and `key`.
+    ///
+    /// This allows for describing constraints whose terms change between updates. The constraint
+    /// is removed along with the widget's anchors once the widget is no longer set. Returns an
+    /// error if the constraint is `REQUIRED` and cannot be satisfied, in which case the previous
+    /// constraint is kept.
+    pub fn constrain_for(&mut self, id: widget::Id, key: usize, constraint: Constraint)
+        -> Result<(), Error>
+    {
+        let maybe_prev = self.keyed.remove(&(id, key));
+        if let Some(ref prev) = maybe_prev {
+            if *prev == constraint && self.solver.has_constraint(prev) {
+                self.keyed.insert((id, key), constraint);
+                return Ok(());
+            }
+            // The previous constraint may have been removed along with the anchors it refers to.
+            let _ = self.solver.remove_constraint(prev);
+        }
+        match self.solver.add_constraint(constraint.clone()) {
+            Ok(()) | Err(Error::DuplicateConstraint) => {
+                self.keyed.insert((id, key), constraint);
+                Ok(())
+            },
+            Err(err) => {
+                if let Some(prev) = maybe_prev {
+                    if self.solver.add_constraint(prev.clone()).is_ok() {
+                        self.keyed.insert((id, key), prev);
+                    }
+                }
+                Err(err)
+            },
+        }
+    }
+
+    /// Remove the anchors of every widget that is not within the given set, along with their edit
+    /// variables and every constraint that refers to them.
+    ///
+    /// This is called by the `Ui` at the end of each update with the set of widgets that were set.
+    pub fn retain_widgets(&mut self, ids: &fnv::FnvHashSet<widget::Id>) {
+        let Layout { ref mut solver, ref mut anchors, ref mut keyed } = *self;
+        let mut variables = fnv::FnvHashSet::default();
+        for (_, removed) in anchors.iter().filter(|&(id, _)| !ids.contains(id)) {
+            variables.extend(&[removed.left, removed.bottom, removed.width, removed.height]);
+        }
+        if variables.is_empty() {
+            return;
+        }
+        anchors.retain(|id, _| ids.contains(id));
+        solver.remove_variables(&variables);
+        keyed.retain(|&(id, _), constraint| ids.contains(&id) && solver.has_constraint(constraint));
+    }
+
+    /// Remove the given constraint from the layout.
+    pub fn remove_constraint(&mut self, constraint: &Constraint) -> Result<(), Error> {
+        self.solver.remove_constraint(constraint)
+    }
+
+    /// Whether or not the given constraint has been added to the layout.
+    pub fn has_constraint(&self, constraint: &Constraint) -> bool {
+        self.solver.has_constraint(constraint)
+    }
+
+    /// Suggest a value for the given variable, e.g. the position of a user-dragged divider.
+    ///
+    /// The variable is added to the layout as a `STRONG` edit variable if necessary.
+    pub fn suggest_value(&mut self, variable: Variable, value: Scalar) {
+        if !self.solver.has_edit_variable(variable) {
+            self.solver.add_edit_variable(variable, STRONG)
+                .expect("`STRONG` edit variables are always valid");
+        }
+        self.solver.suggest_value(variable, value)
+            .expect("the edit variable was added");
+    }
+
+    /// The current value of the given variable.
+    pub fn value_of(&self, variable: Variable) -> Scalar {
+        self.solver.value_of(variable)
+    }
+
+    /// The rectangle of the widget with the given `widget::Id` as solved by the layout.
+    ///
+    /// Returns `None` if the widget has no `Anchors` within the layout.
+    pub fn rect_of(&self, id: widget::Id) -> Option<Rect> {
+        self.anchors.get(&id).map(|anchors| {
+            let left = self.value_of(anchors.left);
+            let bottom = self.value_of(anchors.bottom);
+            let width = self.value_of(anchors.width);
+            let height = self.value_of(anchors.height);
+            Rect {
+                x: Range::new(left, left + width),
+                y: Range::new(bottom, bottom + height),
+            }
+        })
+    }
+
+    /// A reference to the underlying `Solver`.
+    pub fn solver(&self) -> &Solver {
+        &self.solver
+    }
+
+    /// A mutable reference to the underlying `Solver`.
+    pub fn solver_mut(&mut self) -> &mut Solver {
+        &mut self.solver
+    }
+
+}
@@ -8,7 +8,9 @@ pub use self::rect::{Corner, Rect};
 //pub use self::matrix::Matrix;
 
 
+pub mod constraint;
 //pub mod matrix;
+pub mod pixel;
 pub mod range;
 pub mod rect;
 
@@ -60,6 +62,11 @@ pub enum Position {
     Absolute(Scalar),
     /// A position relative to some other Widget.
     Relative(Relative, Option<widget::Id>),
+    /// The position of the `Anchors` of the widget at the given index as solved by the `Ui`'s
+    /// constraint `Layout`.
+    ///
+    /// See the `position::constraint` module for details.
+    Constrained(widget::Id),
 }
 
 /// Positions that are described as **Relative** to some other **Widget**.
@@ -137,6 +144,51 @@ pub enum Dimension {
     /// The `Option<Scalar>` is an optional padding argument which when `Some`, will subtract the
     /// scalar from both ends of the other widget's dimension.
     KidAreaOf(widget::Id, Option<Scalar>),
+    /// The dimension should be the given percentage of that of the widget at the given index.
+    ///
+    /// For example, `PercentOf(parent, 50.0)` is half of the parent's dimension.
+    PercentOf(widget::Id, Scalar),
+    /// The dimension of the `Anchors` of the widget at the given index as solved by the `Ui`'s
+    /// constraint `Layout`.
+    ///
+    /// See the `position::constraint` module for details.
+    Constrained(widget::Id),
+    /// The dimension should fit the widget's own content, as measured by the widget's
+    /// `Widget::content_dimensions` method.
+    ///
+    /// The `Option<Scalar>` is an optional padding argument which when `Some`, will add the scalar
+    /// to both ends of the content's dimension.
+    OfContent(Option<Scalar>),
+}
+
+/// Limits upon the dimensions of a widget, applied once its **Dimension**s have been resolved.
+///
+/// See the [**Sizeable**](./trait.Sizeable) trait for methods that allow for setting these limits.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SizeLimits {
+    /// The minimum width and height.
+    pub min: [Option<Scalar>; 2],
+    /// The maximum width and height.
+    pub max: [Option<Scalar>; 2],
+    /// The ratio of width to height to which the dimensions are locked.
+    ///
+    /// The dimensions are reduced to the largest dimensions with the given ratio that fit within
+    /// the clamped dimensions.
+    pub maybe_aspect_ratio: Option<Scalar>,
+}
+
+impl SizeLimits {
+    /// Clamp the given length along the given axis between the minimum and maximum.
+    ///
+    /// The maximum is favoured in the case that it is less than the minimum.
+    pub fn clamp(&self, axis: Axis, length: Scalar) -> Scalar {
+        let i = match axis {
+            Axis::X => 0,
+            Axis::Y => 1,
+        };
+        let length = self.min[i].map_or(length, |min| length.max(min));
+        self.max[i].map_or(length, |max| length.min(max))
+    }
 }
 
 /// Widgets that are positionable.
@@ -181,6 +233,26 @@ pub trait Positionable: Sized {
         self.xy([x, y])
     }
 
+    // Constrained positioning.
+
+    /// Set the *x* **Position** as the left edge of the given widget's `Anchors` within the `Ui`'s
+    /// constraint `Layout`.
+    fn x_constrained(self, idx: widget::Id) -> Self {
+        self.x_position(Position::Constrained(idx))
+    }
+
+    /// Set the *y* **Position** as the bottom edge of the given widget's `Anchors` within the
+    /// `Ui`'s constraint `Layout`.
+    fn y_constrained(self, idx: widget::Id) -> Self {
+        self.y_position(Position::Constrained(idx))
+    }
+
+    /// Set both **Position**s via the given widget's `Anchors` within the `Ui`'s constraint
+    /// `Layout`.
+    fn xy_constrained(self, idx: widget::Id) -> Self {
+        self.x_constrained(idx).y_constrained(idx)
+    }
+
     // Relative positioning.
 
     /// Set the *x* **Position** **Relative** to the previous widget.
@@ -685,6 +757,18 @@ pub trait Sizeable: Sized {
     /// The widget's length along the y axis as a Dimension.
     fn get_y_dimension(&self, ui: &Ui) -> Dimension;
 
+    /// The length along the x axis, if one was specified rather than left to the widget's default.
+    fn get_maybe_x_dimension(&self) -> Option<Dimension>;
+
+    /// The length along the y axis, if one was specified rather than left to the widget's default.
+    fn get_maybe_y_dimension(&self) -> Option<Dimension>;
+
+    /// Set the limits applied to the widget's dimensions.
+    fn size_limits(self, limits: SizeLimits) -> Self;
+
+    /// The limits applied to the widget's dimensions.
+    fn get_size_limits(&self) -> SizeLimits;
+
     // Provided defaults.
 
     /// Set the absolute width for the widget.
@@ -773,26 +857,165 @@ pub trait Sizeable: Sized {
         self.padded_kid_area_w_of(idx, pad).padded_kid_area_h_of(idx, pad)
     }
 
+    /// Set the width as the width of the given widget's `Anchors` within the `Ui`'s constraint
+    /// `Layout`.
+    fn w_constrained(self, idx: widget::Id) -> Self {
+        self.x_dimension(Dimension::Constrained(idx))
+    }
+
+    /// Set the height as the height of the given widget's `Anchors` within the `Ui`'s constraint
+    /// `Layout`.
+    fn h_constrained(self, idx: widget::Id) -> Self {
+        self.y_dimension(Dimension::Constrained(idx))
+    }
+
+    /// Set the dimensions as those of the given widget's `Anchors` within the `Ui`'s constraint
+    /// `Layout`.
+    fn wh_constrained(self, idx: widget::Id) -> Self {
+        self.w_constrained(idx).h_constrained(idx)
+    }
+
+    /// Set the width as the given percentage of the width of the widget at the given index.
+    fn w_percent_of(self, idx: widget::Id, percent: Scalar) -> Self {
+        self.x_dimension(Dimension::PercentOf(idx, percent))
+    }
+
+    /// Set the height as the given percentage of the height of the widget at the given index.
+    fn h_percent_of(self, idx: widget::Id, percent: Scalar) -> Self {
+        self.y_dimension(Dimension::PercentOf(idx, percent))
+    }
+
+    /// Set the dimensions as the given percentage of the dimensions of the widget at the given
+    /// index.
+    fn wh_percent_of(self, idx: widget::Id, percent: Scalar) -> Self {
+        self.w_percent_of(idx, percent).h_percent_of(idx, percent)
+    }
+
+    /// Set the width as the width of the widget's own content, e.g. its measured label.
+    ///
+    /// See `Dimension::OfContent` for details.
+    fn w_of_content(self) -> Self {
+        self.x_dimension(Dimension::OfContent(None))
+    }
+
+    /// Set the width as the width of the widget's own content padded at both ends by the given
+    /// Scalar.
+    fn padded_w_of_content(self, pad: Scalar) -> Self {
+        self.x_dimension(Dimension::OfContent(Some(pad)))
+    }
+
+    /// Set the height as the height of the widget's own content, e.g. its measured label.
+    fn h_of_content(self) -> Self {
+        self.y_dimension(Dimension::OfContent(None))
+    }
+
+    /// Set the height as the height of the widget's own content padded at both ends by the given
+    /// Scalar.
+    fn padded_h_of_content(self, pad: Scalar) -> Self {
+        self.y_dimension(Dimension::OfContent(Some(pad)))
+    }
+
+    /// Set the dimensions as the dimensions of the widget's own content.
+    fn wh_of_content(self) -> Self {
+        self.w_of_content().h_of_content()
+    }
+
+    /// Set the dimensions as the dimensions of the widget's own content with all four edges padded
+    /// by the given scalar.
+    fn padded_wh_of_content(self, pad: Scalar) -> Self {
+        self.padded_w_of_content(pad).padded_h_of_content(pad)
+    }
+
+    /// Set the minimum width of the widget.
+    fn min_w(self, w: Scalar) -> Self {
+        let mut limits = self.get_size_limits();
+        limits.min[0] = Some(w);
+        self.size_limits(limits)
+    }
+
+    /// Set the minimum height of the widget.
+    fn min_h(self, h: Scalar) -> Self {
+        let mut limits = self.get_size_limits();
+        limits.min[1] = Some(h);
+        self.size_limits(limits)
+    }
+
+    /// Set the minimum dimensions of the widget.
+    fn min_wh(self, wh: Dimensions) -> Self {
+        self.min_w(wh[0]).min_h(wh[1])
+    }
+
+    /// Set the maximum width of the widget.
+    fn max_w(self, w: Scalar) -> Self {
+        let mut limits = self.get_size_limits();
+        limits.max[0] = Some(w);
+        self.size_limits(limits)
+    }
+
+    /// Set the maximum height of the widget.
+    fn max_h(self, h: Scalar) -> Self {
+        let mut limits = self.get_size_limits();
+        limits.max[1] = Some(h);
+        self.size_limits(limits)
+    }
+
+    /// Set the maximum dimensions of the widget.
+    fn max_wh(self, wh: Dimensions) -> Self {
+        self.max_w(wh[0]).max_h(wh[1])
+    }
+
+    /// Lock the ratio of the widget's width to its height, e.g. `16.0 / 9.0`.
+    ///
+    /// The widget's dimensions are reduced to the largest dimensions with the given ratio that fit
+    /// within its resolved dimensions. An axis is only reduced to fit the other if the other's
+    /// **Dimension** was specified, as a widget's default length along one axis may itself depend
+    /// upon the other (e.g. the height of wrapped `Text`).
+    fn aspect_ratio(self, ratio: Scalar) -> Self {
+        let mut limits = self.get_size_limits();
+        limits.maybe_aspect_ratio = Some(ratio);
+        self.size_limits(limits)
+    }
+
     /// Get the absolute width of the widget as a Scalar value.
+    ///
+    /// This is the length described by the widget's *x* **Dimension** with its `SizeLimits`
+    /// applied.
     fn get_w(&self, ui: &Ui) -> Option<Scalar> {
-        match self.get_x_dimension(ui) {
-            Dimension::Absolute(width) => Some(width),
-            Dimension::Of(idx, None) => ui.w_of(idx),
-            Dimension::Of(idx, Some(pad)) => ui.w_of(idx).map(|w| w - pad * 2.0),
-            Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.w()),
-            Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.w() - pad * 2.0),
-        }
+        let limits = self.get_size_limits();
+        let clamped = |dimension, axis| length_of(dimension, axis, ui)
+            .map(|length| limits.clamp(axis, length));
+        clamped(self.get_x_dimension(ui), Axis::X).map(|w| {
+            let maybe_h = limits.maybe_aspect_ratio.and_then(|ratio| {
+                self.get_maybe_y_dimension()
+                    .and_then(|dimension| clamped(dimension, Axis::Y))
+                    .map(|h| (ratio, h))
+            });
+            match maybe_h {
+                Some((ratio, h)) => w.min(h * ratio),
+                None => w,
+            }
+        })
     }
 
     /// Get the height of the widget.
+    ///
+    /// This is the length described by the widget's *y* **Dimension** with its `SizeLimits`
+    /// applied.
     fn get_h(&self, ui: &Ui) -> Option<Scalar> {
-        match self.get_y_dimension(ui) {
-            Dimension::Absolute(height) => Some(height),
-            Dimension::Of(idx, None) => ui.h_of(idx),
-            Dimension::Of(idx, Some(pad)) => ui.h_of(idx).map(|w| w - pad * 2.0),
-            Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.h()),
-            Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.h() - pad * 2.0),
-        }
+        let limits = self.get_size_limits();
+        let clamped = |dimension, axis| length_of(dimension, axis, ui)
+            .map(|length| limits.clamp(axis, length));
+        clamped(self.get_y_dimension(ui), Axis::Y).map(|h| {
+            let maybe_w = limits.maybe_aspect_ratio.and_then(|ratio| {
+                self.get_maybe_x_dimension()
+                    .and_then(|dimension| clamped(dimension, Axis::X))
+                    .map(|w| (ratio, w))
+            });
+            match maybe_w {
+                Some((ratio, w)) => h.min(w / ratio),
+                None => h,
+            }
+        })
     }
 
     /// The dimensions for the widget.
@@ -802,6 +1025,26 @@ pub trait Sizeable: Sized {
 
 }
 
+/// Resolve the length described by the given **Dimension** along the given **Axis**.
+fn length_of(dimension: Dimension, axis: Axis, ui: &Ui) -> Option<Scalar> {
+    let length = |rect: Rect| match axis {
+        Axis::X => rect.w(),
+        Axis::Y => rect.h(),
+    };
+    match dimension {
+        Dimension::Absolute(length) => Some(length),
+        Dimension::Of(idx, None) => ui.rect_of(idx).map(length),
+        Dimension::Of(idx, Some(pad)) => ui.rect_of(idx).map(|r| length(r) - pad * 2.0),
+        Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(length),
+        Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| length(r) - pad * 2.0),
+        Dimension::PercentOf(idx, percent) => ui.rect_of(idx).map(|r| length(r) * percent / 100.0),
+        Dimension::Constrained(idx) => ui.constraints().rect_of(idx).map(length),
+        // The content of a widget may only be measured by the widget itself, so this is resolved
+        // to an `Absolute` dimension before the widget is set.
+        Dimension::OfContent(_) => None,
+    }
+}
+
 /// The distance between the inner edge of a border and the outer edge of the inner content.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Padding {
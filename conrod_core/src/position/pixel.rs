@@ -0,0 +1,52 @@
+//! Functions for aligning positions and lengths given in logical points to the grid of physical
+//! pixels on the display.
+//!
+//! The `Ui` measures everything in points with the origin at the centre of the window, while the
+//! display's pixels are aligned to the window's top-left corner. On displays with a scale factor
+//! other than `1.0`, or within windows whose dimensions are odd, edges that fall between pixels
+//! appear blurred. Snapping those edges to the pixel grid keeps lines and borders crisp.
+//!
+//! These are usually accessed via the `Ui::snap_point`, `Ui::snap_rect` and `Ui::snap_length`
+//! methods, which provide the `Ui`'s window dimensions and scale factor.
+
+use super::{Dimensions, Point, Range, Rect, Scalar};
+
+
+/// Snap the given position along a single axis to the nearest physical pixel boundary.
+///
+/// `origin` is the position of the window's leading edge along the axis (i.e. `-win_w / 2.0` for
+/// the *x* axis), to which the pixel grid is aligned.
+pub fn snap(value: Scalar, origin: Scalar, scale_factor: Scalar) -> Scalar {
+    ((value - origin) * scale_factor).round() / scale_factor + origin
+}
+
+/// Snap the given length to a whole number of physical pixels.
+///
+/// Lengths greater than zero are never snapped below a single physical pixel so that thin lines
+/// and borders remain visible.
+pub fn snap_length(length: Scalar, scale_factor: Scalar) -> Scalar {
+    if length <= 0.0 {
+        return length;
+    }
+    let pixels = (length * scale_factor).round();
+    if pixels < 1.0 { 1.0 / scale_factor } else { pixels / scale_factor }
+}
+
+/// Snap the given point to the nearest physical pixel corner within a window of the given
+/// dimensions.
+pub fn snap_point(xy: Point, window_dim: Dimensions, scale_factor: Scalar) -> Point {
+    [snap(xy[0], -window_dim[0] / 2.0, scale_factor),
+     snap(xy[1], -window_dim[1] / 2.0, scale_factor)]
+}
+
+/// Snap each edge of the given rectangle to the nearest physical pixel boundary within a window of
+/// the given dimensions.
+pub fn snap_rect(rect: Rect, window_dim: Dimensions, scale_factor: Scalar) -> Rect {
+    let snap_range = |range: Range, origin: Scalar| {
+        Range::new(snap(range.start, origin, scale_factor), snap(range.end, origin, scale_factor))
+    };
+    Rect {
+        x: snap_range(rect.x, -window_dim[0] / 2.0),
+        y: snap_range(rect.y, -window_dim[1] / 2.0),
+    }
+}
@@ -0,0 +1,211 @@
+//! Tools for driving a `Ui` headlessly, e.g. within unit tests.
+//!
+//! The primary type of this module is the `Driver`. Rather than requiring that tests compute raw
+//! window co-ordinates by hand, the `Driver` targets widgets by their `widget::Id`, looking up
+//! their location via `Ui::rect_of`. Each interaction is followed by a full `Ui::set_widgets`
+//! cycle, after which the events that were delivered to each widget may be inspected.
+//!
+//! ```
+//! # #[macro_use] extern crate conrod_core;
+//! # use conrod_core::{widget, Positionable, Sizeable, UiBuilder, Widget};
+//! # use conrod_core::testing::Driver;
+//! # widget_ids!(struct Ids { button });
+//! # fn main() {
+//! let mut ui = UiBuilder::new([400.0, 400.0]).build();
+//! let ids = Ids::new(ui.widget_id_generator());
+//! let mut driver = Driver::new(ui, 0, |ui, times_clicked| {
+//!     for _click in widget::Button::new().w_h(100.0, 40.0).middle().set(ids.button, ui) {
+//!         *times_clicked += 1;
+//!     }
+//! });
+//! driver.click(ids.button);
+//! assert_eq!(driver.app, 1);
+//! # }
+//! ```
+
+use event::{self, Input};
+use fnv;
+use input::{self, Button, Key, ModifierKey, Motion, MouseButton};
+use input::shortcut::Chord;
+use position::{Point, Scalar};
+use ui::{Ui, UiCell};
+use utils;
+use widget;
+
+
+/// Drives a `Ui` via interactions that target widgets by their `widget::Id`.
+///
+/// The `Driver` owns the `Ui` along with some application state `app` and the function used to
+/// instantiate widgets. Each interaction feeds the necessary `event::Input`s to the `Ui` and then
+/// performs a full `Ui::set_widgets` cycle via `update`.
+pub struct Driver<T, F> {
+    /// The `Ui` being driven.
+    pub ui: Ui,
+    /// The application state that is passed to the `set_widgets` function on each update.
+    pub app: T,
+    /// Instantiates all widgets.
+    set_widgets: F,
+    /// The events that were delivered to each widget during the last update.
+    events: fnv::FnvHashMap<widget::Id, Vec<event::Widget>>,
+}
+
+
+impl<T, F> Driver<T, F>
+    where F: FnMut(&mut UiCell, &mut T),
+{
+
+    /// Construct a new **Driver**.
+    ///
+    /// An initial update is performed so that all widgets have a location that may be targeted.
+    pub fn new(ui: Ui, app: T, set_widgets: F) -> Self {
+        let mut driver = Driver {
+            ui: ui,
+            app: app,
+            set_widgets: set_widgets,
+            events: fnv::FnvHashMap::default(),
+        };
+        driver.update();
+        driver
+    }
+
+    /// Perform a full `Ui::set_widgets` cycle, delivering all input received since the last
+    /// update.
+    ///
+    /// The events delivered to each widget are retained so that they may be inspected via
+    /// `Driver::events` until the next update.
+    pub fn update(&mut self) {
+        let Driver { ref mut ui, ref mut app, ref mut set_widgets, ref mut events } = *self;
+        events.clear();
+        for &id in ui.updated_widgets() {
+            let widget_events: Vec<_> = ui.widget_input(id).events().collect();
+            if !widget_events.is_empty() {
+                events.insert(id, widget_events);
+            }
+        }
+        let mut ui = ui.set_widgets();
+        set_widgets(&mut ui, app);
+    }
+
+    /// All events that were delivered to the given widget during the last update.
+    pub fn events(&self, id: widget::Id) -> &[event::Widget] {
+        self.events.get(&id).map(|events| &events[..]).unwrap_or(&[])
+    }
+
+    /// The absolute position of the centre of the given widget.
+    ///
+    /// **Panics** if no widget with the given `id` was instantiated during the last update.
+    pub fn xy_of(&self, id: widget::Id) -> Point {
+        self.ui.xy_of(id).expect("no widget exists for the given `widget::Id`")
+    }
+
+    /// Move the mouse to the given position relative to the centre of the given widget.
+    ///
+    /// This does not perform an update.
+    pub fn move_mouse(&mut self, id: widget::Id, rel_xy: Point) {
+        let xy = utils::vec2_add(self.xy_of(id), rel_xy);
+        self.ui.handle_event(Input::Motion(Motion::MouseCursor { x: xy[0], y: xy[1] }));
+    }
+
+    /// Move the mouse over the centre of the given widget and update.
+    pub fn hover(&mut self, id: widget::Id) {
+        self.move_mouse(id, [0.0, 0.0]);
+        self.update();
+    }
+
+    /// Click the left mouse button over the centre of the given widget and update.
+    pub fn click(&mut self, id: widget::Id) {
+        self.move_mouse(id, [0.0, 0.0]);
+        self.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        self.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        self.update();
+    }
+
+    /// Click the given widget in order to capture the keyboard, then enter the given text and
+    /// update.
+    pub fn type_text(&mut self, id: widget::Id, text: &str) {
+        self.click(id);
+        self.enter_text(text);
+    }
+
+    /// Enter the given text and update.
+    ///
+    /// The text is received by whichever widget is currently capturing the keyboard.
+    pub fn enter_text(&mut self, text: &str) {
+        self.ui.handle_event(Input::Text(text.to_string()));
+        self.update();
+    }
+
+    /// Press and release the given key and update.
+    ///
+    /// The key is received by whichever widget is currently capturing the keyboard.
+    pub fn press_key(&mut self, key: Key) {
+        self.ui.handle_event(Input::Press(Button::Keyboard(key)));
+        self.ui.handle_event(Input::Release(Button::Keyboard(key)));
+        self.update();
+    }
+
+    /// Press and release the given chord, holding its modifier keys throughout, and update.
+    ///
+    /// The chord is received by whichever widget is currently capturing the keyboard.
+    pub fn press_chord(&mut self, chord: Chord) {
+        let modifiers = [
+            (ModifierKey::CTRL, Key::LCtrl),
+            (ModifierKey::SHIFT, Key::LShift),
+            (ModifierKey::ALT, Key::LAlt),
+            (ModifierKey::GUI, Key::LGui),
+        ];
+        let held = modifiers.iter().filter(|&&(modifier, _)| chord.modifiers.contains(modifier));
+        for &(_, key) in held.clone() {
+            self.ui.handle_event(Input::Press(Button::Keyboard(key)));
+        }
+        self.ui.handle_event(Input::Press(Button::Keyboard(chord.key)));
+        self.ui.handle_event(Input::Release(Button::Keyboard(chord.key)));
+        for &(_, key) in held {
+            self.ui.handle_event(Input::Release(Button::Keyboard(key)));
+        }
+        self.update();
+    }
+
+    /// Drag the mouse with the left button held from `from` to `to`, both of which are relative
+    /// to the centre of the given widget.
+    ///
+    /// An update is performed after pressing the button, after moving the mouse and after
+    /// releasing the button, as many widgets inspect the state of the mouse while it is held.
+    /// `Driver::events` yields the events from the final update only.
+    pub fn drag(&mut self, id: widget::Id, from: Point, to: Point) {
+        let xy = self.xy_of(id);
+        let (from, to) = (utils::vec2_add(xy, from), utils::vec2_add(xy, to));
+        self.ui.handle_event(Input::Motion(Motion::MouseCursor { x: from[0], y: from[1] }));
+        self.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        self.update();
+        self.ui.handle_event(Input::Motion(Motion::MouseCursor { x: to[0], y: to[1] }));
+        self.update();
+        self.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        self.update();
+    }
+
+    /// Scroll vertically by `dy` with the mouse over the centre of the given widget and update.
+    ///
+    /// `dy` has the same orientation as the `y` of `input::Motion::Scroll`.
+    pub fn scroll(&mut self, id: widget::Id, dy: Scalar) {
+        self.move_mouse(id, [0.0, 0.0]);
+        self.ui.handle_event(Input::Motion(Motion::Scroll { x: 0.0, y: dy }));
+        self.update();
+    }
+
+    /// Feed the given touch to the `Ui` at the position relative to the centre of the given
+    /// widget.
+    ///
+    /// This does not perform an update.
+    pub fn touch(&mut self,
+                 id: widget::Id,
+                 touch_id: u64,
+                 phase: input::touch::Phase,
+                 rel_xy: Point)
+    {
+        let xy = utils::vec2_add(self.xy_of(id), rel_xy);
+        let touch = input::Touch { phase: phase, id: input::touch::Id::new(touch_id), xy: xy };
+        self.ui.handle_event(Input::Touch(touch));
+    }
+
+}
@@ -0,0 +1,125 @@
+use {Positionable, Sizeable, UiCell, Widget};
+use event::Input;
+use input::{Button, Key, ModifierKey, MouseButton};
+use input::shortcut::Chord;
+use std::cell::Cell;
+use testing::Driver;
+use tests::new_ui_with_font;
+use widget::{self, code_editor};
+
+
+struct App {
+    code_editor: widget::Id,
+    text: String,
+}
+
+fn set_widgets(ui: &mut UiCell, app: &mut App) {
+    for edit in widget::CodeEditor::new(&app.text)
+        .w_h(400.0, 300.0)
+        .middle()
+        .set(app.code_editor, ui)
+    {
+        app.text = edit;
+    }
+}
+
+fn new_driver(text: &str) -> Driver<App, fn(&mut UiCell, &mut App)> {
+    let mut ui = new_ui_with_font();
+    let code_editor = ui.widget_id_generator().next();
+    let app = App { code_editor: code_editor, text: text.to_string() };
+    Driver::new(ui, app, set_widgets)
+}
+
+
+#[test]
+fn new_lines_should_be_automatically_indented() {
+    let mut driver = new_driver("");
+    let code_editor = driver.app.code_editor;
+    driver.type_text(code_editor, "fn main() {}");
+
+    // Move between the braces before inserting the new line.
+    driver.press_key(Key::Left);
+    driver.press_key(Key::Return);
+    assert_eq!(driver.app.text, "fn main() {\n    \n}");
+
+    driver.enter_text("let x = 1;");
+    driver.press_key(Key::Return);
+    driver.enter_text("x");
+    assert_eq!(driver.app.text, "fn main() {\n    let x = 1;\n    x\n}");
+}
+
+#[test]
+fn tabs_should_indent_to_the_next_tab_stop() {
+    let mut driver = new_driver("");
+    let code_editor = driver.app.code_editor;
+    driver.type_text(code_editor, "ab");
+    driver.press_key(Key::Home);
+    driver.press_key(Key::Tab);
+    assert_eq!(driver.app.text, "    ab");
+
+    // With soft tabs, backspace removes the spaces back to the previous tab stop.
+    driver.enter_text("  ");
+    driver.press_key(Key::Backspace);
+    assert_eq!(driver.app.text, "    ab");
+
+    // Shift+Tab removes one level of indentation from the line.
+    driver.press_key(Key::Tab);
+    driver.press_chord(Chord::new(ModifierKey::SHIFT, Key::Tab));
+    assert_eq!(driver.app.text, "    ab");
+    driver.press_chord(Chord::new(ModifierKey::SHIFT, Key::Tab));
+    assert_eq!(driver.app.text, "ab");
+}
+
+#[test]
+fn double_clicking_should_select_a_word() {
+    let long_word: String = ::std::iter::repeat('b').take(60).collect();
+    let mut driver = new_driver(&format!("a {} c\nd", long_word));
+    let code_editor = driver.app.code_editor;
+
+    // Double-press within the long word on the first line and replace it.
+    driver.move_mouse(code_editor, [0.0, 145.0]);
+    for _ in 0..2 {
+        driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    }
+    driver.update();
+    driver.enter_text("x");
+    assert_eq!(driver.app.text, "a x c\nd");
+
+    // Word movement stops at the boundaries of the same words.
+    driver.press_chord(Chord::ctrl(Key::Left));
+    driver.press_chord(Chord::ctrl(Key::Left));
+    driver.enter_text("y");
+    assert_eq!(driver.app.text, "ya x c\nd");
+}
+
+#[test]
+fn only_visible_lines_should_be_highlighted() {
+    let text: String = (0..10_000).map(|i| format!("line {}\n", i)).collect();
+    let max_line = Cell::new(0);
+    let highlight = |_: &str, line_idx: usize, line: &str| {
+        max_line.set(::std::cmp::max(max_line.get(), line_idx));
+        vec![code_editor::Span { bytes: 0..line.find(' ').unwrap_or(0), color: ::color::RED }]
+    };
+    let mut ui = new_ui_with_font();
+    let code_editor = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, text, |ui, text| {
+        for edit in widget::CodeEditor::new(text)
+            .highlighter(&highlight)
+            .w_h(400.0, 300.0)
+            .middle()
+            .set(code_editor, ui)
+        {
+            *text = edit;
+        }
+    });
+    driver.update();
+    let first_max = max_line.get();
+    assert!(0 < first_max && first_max < 100, "{}", first_max);
+
+    // Jumping to the end of the text scrolls it into view.
+    driver.click(code_editor);
+    driver.press_chord(Chord::ctrl(Key::End));
+    driver.update();
+    assert_eq!(max_line.get(), 10_000);
+}
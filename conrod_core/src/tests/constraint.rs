@@ -0,0 +1,138 @@
+use {Positionable, Sizeable, UiBuilder, Widget};
+use position::Range;
+use position::constraint::{self, Error, Solver, Variable};
+use widget;
+
+
+#[test]
+fn solver_should_favour_stronger_constraints() {
+    let mut solver = Solver::new();
+    let (x, y) = (Variable::new(), Variable::new());
+    solver.add_constraint(x.is_ge(10.0)).unwrap();
+    solver.add_constraint(x.is_eq(5.0).strength(constraint::WEAK)).unwrap();
+    solver.add_constraint((x + y).is_eq(30.0)).unwrap();
+    solver.add_constraint(y.is_eq(2.0 * x).strength(constraint::MEDIUM)).unwrap();
+    assert_eq!(solver.value_of(x), 10.0);
+    assert_eq!(solver.value_of(y), 20.0);
+
+    // Removing the required minimum allows the weak preference to take effect.
+    solver.remove_constraint(&x.is_ge(10.0)).unwrap();
+    assert_eq!(solver.value_of(x), 10.0);
+    solver.remove_constraint(&y.is_eq(2.0 * x).strength(constraint::MEDIUM)).unwrap();
+    assert_eq!(solver.value_of(x), 5.0);
+    assert_eq!(solver.value_of(y), 25.0);
+}
+
+#[test]
+fn solver_should_reject_unsatisfiable_and_duplicate_constraints() {
+    let mut solver = Solver::new();
+    let x = Variable::new();
+    solver.add_constraint(x.is_eq(1.0)).unwrap();
+    assert_eq!(solver.add_constraint(x.is_eq(1.0)), Err(Error::DuplicateConstraint));
+    assert_eq!(solver.add_constraint(x.is_eq(2.0)), Err(Error::UnsatisfiableConstraint));
+    assert_eq!(solver.add_constraint(x.is_ge(3.0)), Err(Error::UnsatisfiableConstraint));
+    assert!(!solver.has_constraint(&x.is_ge(3.0)));
+    assert_eq!(solver.value_of(x), 1.0);
+    assert_eq!(solver.remove_constraint(&x.is_eq(2.0)), Err(Error::UnknownConstraint));
+}
+
+#[test]
+fn solver_should_update_incrementally_with_suggested_values() {
+    let mut solver = Solver::new();
+    let (left, mid, right) = (Variable::new(), Variable::new(), Variable::new());
+    solver.add_constraint((2.0 * mid).is_eq(left + right)).unwrap();
+    solver.add_constraint((left + 10.0).is_le(right)).unwrap();
+    solver.add_constraint(right.is_le(100.0)).unwrap();
+    solver.add_constraint(left.is_ge(0.0)).unwrap();
+    solver.add_edit_variable(mid, constraint::STRONG).unwrap();
+    assert_eq!(solver.add_edit_variable(mid, constraint::REQUIRED),
+               Err(Error::DuplicateEditVariable));
+
+    solver.suggest_value(mid, 50.0).unwrap();
+    assert_eq!(solver.value_of(mid), 50.0);
+    assert!(solver.value_of(right) - solver.value_of(left) >= 10.0);
+
+    // The required constraints keep `mid` within `[5, 95]`.
+    solver.suggest_value(mid, 2.0).unwrap();
+    assert_eq!(solver.value_of(mid), 5.0);
+    assert_eq!(solver.value_of(left), 0.0);
+    assert_eq!(solver.value_of(right), 10.0);
+    solver.suggest_value(mid, 99.0).unwrap();
+    assert_eq!(solver.value_of(mid), 95.0);
+    assert_eq!(solver.value_of(right), 100.0);
+}
+
+#[test]
+fn constrained_widgets_should_be_positioned_relative_to_the_widgets_they_refer_to() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    {
+        let layout = ui.constraints_mut();
+        let (a_anchors, b_anchors) = (layout.anchors(a), layout.anchors(b));
+        layout.constrain(b_anchors.left.is_eq(a_anchors.right() + 8.0)).unwrap();
+        layout.constrain(b_anchors.width.is_ge(120.0)).unwrap();
+        layout.constrain(b_anchors.width.is_eq(2.0 * a_anchors.width).strength(constraint::MEDIUM))
+            .unwrap();
+        layout.constrain(b_anchors.top().is_eq(a_anchors.top())).unwrap();
+        layout.constrain(b_anchors.height.is_eq(a_anchors.height)).unwrap();
+    }
+
+    let mut a_w = 50.0;
+    for _ in 0..2 {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([a_w, 20.0]).x_y(-100.0, 0.0).set(a, ui);
+        widget::Rectangle::fill([0.0, 0.0]).wh_constrained(b).xy_constrained(b).set(b, ui);
+        let rect = ui.rect_of(b).unwrap();
+        let left = -100.0 + a_w / 2.0 + 8.0;
+        assert_eq!(rect.x, Range::new(left, left + 120.0));
+        assert_eq!(rect.y, Range::new(-10.0, 10.0));
+        a_w = 40.0;
+    }
+
+    // Once twice the width of `a` exceeds the minimum, the medium constraint is satisfied.
+    let ui = &mut ui.set_widgets();
+    widget::Rectangle::fill([80.0, 20.0]).x_y(-100.0, 0.0).set(a, ui);
+    widget::Rectangle::fill([0.0, 0.0]).wh_constrained(b).xy_constrained(b).set(b, ui);
+    assert_eq!(ui.rect_of(b).unwrap().x, Range::new(-52.0, 108.0));
+}
+
+#[test]
+fn keyed_constraints_should_be_replaced_as_they_change_between_updates() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let a = ui.widget_id_generator().next();
+
+    for &w in &[100.0, 200.0, 150.0] {
+        let ui = &mut ui.set_widgets();
+        {
+            let layout = ui.constraints_mut();
+            let anchors = layout.anchors(a);
+            layout.constrain_for(a, 0, anchors.width.is_eq(w)).unwrap();
+            layout.constrain_for(a, 1, anchors.x().is_eq(0.0)).unwrap();
+            layout.constrain_for(a, 2, anchors.height.is_eq(20.0)).unwrap();
+            layout.constrain_for(a, 3, anchors.y().is_eq(0.0)).unwrap();
+        }
+        widget::Rectangle::fill([0.0, 0.0]).wh_constrained(a).xy_constrained(a).set(a, ui);
+        assert_eq!(ui.rect_of(a).unwrap().x, Range::new(-w / 2.0, w / 2.0));
+    }
+
+    // An unsatisfiable replacement leaves the previous constraint in place.
+    {
+        let layout = ui.constraints_mut();
+        let anchors = layout.get_anchors(a).unwrap();
+        layout.constrain(anchors.width.is_le(160.0)).unwrap();
+        assert_eq!(layout.constrain_for(a, 0, anchors.width.is_eq(170.0)),
+                   Err(Error::UnsatisfiableConstraint));
+        assert!(layout.has_constraint(&anchors.width.is_eq(150.0)));
+    }
+
+    // Once the widget is no longer set, its anchors and their constraints are removed.
+    let anchors = ui.constraints().get_anchors(a).unwrap();
+    ui.set_widgets();
+    assert!(ui.constraints().get_anchors(a).is_none());
+    assert!(!ui.constraints().has_constraint(&anchors.width.is_eq(150.0)));
+    assert!(!ui.constraints().has_constraint(&anchors.width.is_le(160.0)));
+    assert!(!ui.constraints().solver().has_edit_variable(anchors.width));
+}
@@ -0,0 +1,164 @@
+use {Positionable, Sizeable, UiBuilder, Widget};
+use testing::Driver;
+use widget;
+use widget::dock::{Layout, Node, Stack, Zone};
+
+
+#[test]
+fn docking_to_a_side_should_split_the_target_stack() {
+    let mut layout = Layout::new(Node::tabs(vec![1, 2]));
+    assert!(layout.dock(2, 1, Zone::Left));
+    assert_eq!(layout, Layout::new(Node::Row(vec![
+        (1.0, Node::tabs(vec![2])),
+        (1.0, Node::tabs(vec![1])),
+    ])));
+
+    // Docking along the axis of the existing split adds to it rather than nesting a new split.
+    layout.root = Some(Node::Row(vec![(1.0, Node::tabs(vec![2])), (1.0, Node::tabs(vec![1, 3]))]));
+    assert!(layout.dock(3, 1, Zone::Right));
+    assert_eq!(layout.root, Some(Node::Row(vec![
+        (1.0, Node::tabs(vec![2])),
+        (0.5, Node::tabs(vec![1])),
+        (0.5, Node::tabs(vec![3])),
+    ])));
+
+    // A panel alone in its stack cannot be docked beside itself.
+    assert!(!layout.dock(3, 3, Zone::Bottom));
+    assert!(layout.dock(3, 1, Zone::Bottom));
+    assert_eq!(layout.root, Some(Node::Row(vec![
+        (1.0, Node::tabs(vec![2])),
+        (0.5, Node::Column(vec![(1.0, Node::tabs(vec![1])), (1.0, Node::tabs(vec![3]))])),
+    ])));
+}
+
+#[test]
+fn removing_panels_should_collapse_empty_stacks_and_splits() {
+    let mut layout = Layout::new(Node::Row(vec![
+        (1.0, Node::tabs(vec![1])),
+        (3.0, Node::Column(vec![(1.0, Node::tabs(vec![2])), (1.0, Node::Row(vec![
+            (1.0, Node::tabs(vec![3])),
+            (3.0, Node::Tabs(Stack { panels: vec![4, 5], selected: 1 })),
+        ]))])),
+    ]));
+
+    // The column is left with a single row, which is flattened into the root row.
+    assert!(layout.remove(2));
+    assert_eq!(layout.root, Some(Node::Row(vec![
+        (1.0, Node::tabs(vec![1])),
+        (0.75, Node::tabs(vec![3])),
+        (2.25, Node::Tabs(Stack { panels: vec![4, 5], selected: 1 })),
+    ])));
+    assert!(layout.remove(5));
+    assert!(layout.remove(1));
+    assert!(layout.remove(3));
+    assert_eq!(layout, Layout::new(Node::tabs(vec![4])));
+    assert!(!layout.remove(3));
+    assert!(layout.remove(4));
+    assert_eq!(layout, Layout::default());
+}
+
+#[test]
+fn floating_panels_should_dock_back_into_the_layout() {
+    let mut layout = Layout::new(Node::tabs(vec![1, 2, 3]));
+    assert!(layout.float(2, [100.0, 50.0], [200.0, 100.0]));
+    assert!(layout.float(3, [0.0, 0.0], [200.0, 100.0]));
+    assert_eq!(layout.panels(), vec![1, 2, 3]);
+    assert_eq!(layout.floating[0].node, Node::tabs(vec![2]));
+
+    // Panels may be docked within floating windows too.
+    assert!(layout.dock(3, 2, Zone::Center));
+    assert_eq!(layout.floating.len(), 1);
+    assert_eq!(layout.floating[0].node, Node::Tabs(Stack { panels: vec![2, 3], selected: 1 }));
+    assert!(layout.select(2));
+    assert!(layout.dock(2, 1, Zone::Top));
+    assert!(layout.dock(3, 1, Zone::Center));
+    assert!(layout.floating.is_empty());
+    assert_eq!(layout.root, Some(Node::Column(vec![
+        (1.0, Node::tabs(vec![2])),
+        (1.0, Node::Tabs(Stack { panels: vec![1, 3], selected: 1 })),
+    ])));
+}
+
+#[test]
+fn dragging_a_tab_should_dock_or_tear_off_its_panel() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let dock = ui.widget_id_generator().next();
+    let layout = Layout::new(Node::tabs(vec![1, 2]));
+    let mut driver = Driver::new(ui, (layout, Vec::new()), |ui, app| {
+        let titles = [(1, "One"), (2, "Two")];
+        let (panels, maybe_layout) = widget::Dock::new(&app.0, &titles)
+            .tab_bar_height(24.0)
+            .max_tab_width(120.0)
+            .w_h(400.0, 200.0)
+            .middle()
+            .set(dock, ui);
+        if let Some(layout) = maybe_layout {
+            app.0 = layout;
+        }
+        app.1.clear();
+        for panel in panels {
+            app.1.push((panel.id, panel.widget_id));
+            panel.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+        }
+    });
+    driver.update();
+    assert_eq!(driver.app.1.len(), 1);
+    let (_, one) = driver.app.1[0];
+    assert_eq!(driver.ui.rect_of(one).map(|r| r.xy()), Some([0.0, -12.0]));
+
+    // Drag the second tab onto the left edge of the first panel.
+    let tab = [-20.0, 100.0];
+    driver.drag(one, tab, [-180.0, 0.0]);
+    assert_eq!(driver.app.0, Layout::new(Node::Row(vec![
+        (1.0, Node::tabs(vec![2])),
+        (1.0, Node::tabs(vec![1])),
+    ])));
+    driver.update();
+    assert_eq!(driver.app.1.iter().map(|&(id, _)| id).collect::<Vec<_>>(), vec![2, 1]);
+
+    // Dropping the panel outside of the dock tears it off into a floating window.
+    let (_, two) = driver.app.1[0];
+    driver.drag(two, [0.0, 100.0], [0.0, 250.0]);
+    assert_eq!(driver.app.0.root, Some(Node::tabs(vec![1])));
+    assert_eq!(driver.app.0.floating.len(), 1);
+    assert_eq!(driver.app.0.floating[0].node, Node::tabs(vec![2]));
+}
+
+#[test]
+fn empty_stacks_should_be_left_empty_when_set() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let dock = ui.widget_id_generator().next();
+    let layout = Layout::new(Node::Row(vec![(1.0, Node::tabs(vec![1])), (1.0, Node::tabs(vec![]))]));
+    let mut driver = Driver::new(ui, (layout, Vec::new()), |ui, app| {
+        let titles = [(1, "One")];
+        let (panels, _) = widget::Dock::new(&app.0, &titles)
+            .w_h(400.0, 200.0)
+            .middle()
+            .set(dock, ui);
+        app.1 = panels.map(|panel| panel.id).collect();
+    });
+    driver.update();
+    assert_eq!(driver.app.1, vec![1]);
+
+    // Removing a panel collapses any empty stacks along with it.
+    assert!(driver.app.0.remove(1));
+    assert_eq!(driver.app.0, Layout::default());
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn layouts_should_survive_a_serde_round_trip() {
+    use serde_json;
+
+    let mut layout = Layout::new(Node::Row(vec![
+        (1.0, Node::tabs(vec![1])),
+        (2.0, Node::Column(vec![
+            (1.0, Node::Tabs(Stack { panels: vec![2, 3], selected: 1 })),
+            (1.0, Node::tabs(vec![4])),
+        ])),
+    ]));
+    assert!(layout.float(4, [100.0, 50.0], [200.0, 100.0]));
+    let json = serde_json::to_string(&layout).unwrap();
+    let restored: Layout = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, layout);
+}
@@ -0,0 +1,111 @@
+use {Positionable, Sizeable, Ui, UiBuilder, UiCell, Widget};
+use event;
+use testing::Driver;
+use widget;
+
+
+struct Ids {
+    canvas: widget::Id,
+    button: widget::Id,
+    slider: widget::Id,
+    list: widget::Id,
+    items: Vec<widget::Id>,
+}
+
+struct App {
+    ids: Ids,
+    times_clicked: usize,
+    value: f32,
+}
+
+fn new_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+fn set_widgets(ui: &mut UiCell, app: &mut App) {
+    widget::Canvas::new().set(app.ids.canvas, ui);
+    for _click in widget::Button::new()
+        .w_h(100.0, 40.0)
+        .top_left_of(app.ids.canvas)
+        .set(app.ids.button, ui)
+    {
+        app.times_clicked += 1;
+    }
+    if let Some(value) = widget::Slider::new(app.value, 0.0, 1.0)
+        .w_h(200.0, 40.0)
+        .down_from(app.ids.button, 20.0)
+        .set(app.ids.slider, ui)
+    {
+        app.value = value;
+    }
+    let canvas = widget::Canvas::new()
+        .w_h(200.0, 200.0)
+        .down_from(app.ids.slider, 20.0)
+        .scroll_kids_vertically();
+    canvas.set(app.ids.list, ui);
+    for (i, &id) in app.ids.items.iter().enumerate() {
+        widget::Button::new()
+            .w_h(200.0, 100.0)
+            .mid_top_with_margin_on(app.ids.list, i as f64 * 100.0)
+            .set(id, ui);
+    }
+}
+
+fn new_driver() -> Driver<App, fn(&mut UiCell, &mut App)> {
+    let mut ui = new_ui();
+    let ids = {
+        let mut id_generator = ui.widget_id_generator();
+        Ids {
+            canvas: id_generator.next(),
+            button: id_generator.next(),
+            slider: id_generator.next(),
+            list: id_generator.next(),
+            items: (0..10).map(|_| id_generator.next()).collect(),
+        }
+    };
+    let app = App { ids: ids, times_clicked: 0, value: 0.0 };
+    Driver::new(ui, app, set_widgets)
+}
+
+
+#[test]
+fn clicking_a_widget_by_id_should_deliver_a_click_to_it() {
+    let mut driver = new_driver();
+    let button = driver.app.ids.button;
+    driver.click(button);
+    driver.click(button);
+    assert_eq!(driver.app.times_clicked, 2);
+    assert!(driver.events(button).iter().any(|e| match *e {
+        event::Widget::Click(_) => true,
+        _ => false,
+    }));
+}
+
+#[test]
+fn typed_text_should_be_delivered_to_the_clicked_widget() {
+    let mut driver = new_driver();
+    let button = driver.app.ids.button;
+    driver.type_text(button, "abc");
+    assert!(driver.events(button).iter().any(|e| match *e {
+        event::Widget::Text(ref text) => text.string == "abc",
+        _ => false,
+    }));
+}
+
+#[test]
+fn dragging_a_slider_should_update_its_value() {
+    let mut driver = new_driver();
+    let slider = driver.app.ids.slider;
+    driver.drag(slider, [-100.0, 0.0], [50.0, 0.0]);
+    assert!((driver.app.value - 0.75).abs() < 0.01, "value was {}", driver.app.value);
+}
+
+#[test]
+fn scrolling_over_a_scrollable_canvas_should_scroll_its_kids() {
+    let mut driver = new_driver();
+    let (list, first_item) = (driver.app.ids.list, driver.app.ids.items[0]);
+    let y = driver.xy_of(first_item)[1];
+    driver.scroll(list, 50.0);
+    driver.update();
+    assert!(driver.xy_of(first_item)[1] > y);
+}
@@ -0,0 +1,474 @@
+use {Borderable, Labelable, Positionable, Sizeable, UiBuilder, UiCell, Widget};
+use position::{Range, Scalar};
+use testing::Driver;
+use tests::new_ui_with_font;
+use text;
+use widget;
+use widget::flex::{self, CrossAlign, Justify, Params, Spec};
+use widget::grid_layout::{resolve_tracks, CellAlign, Placement, Track};
+
+
+fn spec(basis: Scalar, breadth: Scalar) -> Spec {
+    Spec {
+        basis: basis,
+        breadth: breadth,
+        grow: 0.0,
+        shrink: 1.0,
+        min: 0.0,
+        max: ::std::f64::MAX,
+        align: None,
+    }
+}
+
+fn params(length: Scalar, breadth: Scalar) -> Params {
+    Params {
+        length: length,
+        breadth: breadth,
+        spacing: 10.0,
+        line_spacing: 5.0,
+        justify: Justify::Start,
+        cross_align: CrossAlign::Start,
+        wrap: false,
+    }
+}
+
+fn main_ranges(slots: &[flex::Slot]) -> Vec<(Scalar, Scalar)> {
+    slots.iter().map(|slot| (slot.main.start, slot.main.end)).collect()
+}
+
+
+#[test]
+fn flex_items_should_grow_and_shrink_within_their_limits() {
+    // The free space is divided by the grow factors, but the second item stops at its max.
+    let specs = [
+        spec(20.0, 10.0),
+        Spec { grow: 1.0, max: 40.0, ..spec(20.0, 10.0) },
+        Spec { grow: 1.0, ..spec(20.0, 10.0) },
+    ];
+    let slots = flex::layout(&specs, &params(200.0, 50.0));
+    assert_eq!(main_ranges(&slots), vec![(0.0, 20.0), (30.0, 70.0), (80.0, 200.0)]);
+
+    // Overflow is absorbed in proportion to each item's basis, but not beyond its min.
+    let specs = [
+        spec(100.0, 10.0),
+        Spec { min: 90.0, ..spec(100.0, 10.0) },
+        spec(50.0, 10.0),
+    ];
+    let slots = flex::layout(&specs, &params(200.0, 50.0));
+    assert_eq!(main_ranges(&slots), vec![(0.0, 60.0), (70.0, 160.0), (170.0, 200.0)]);
+}
+
+#[test]
+fn flex_items_should_wrap_and_align_within_each_line() {
+    let specs = [
+        spec(60.0, 10.0),
+        Spec { align: Some(CrossAlign::End), ..spec(60.0, 10.0) },
+        spec(60.0, 30.0),
+        Spec { align: Some(CrossAlign::Stretch), ..spec(60.0, 10.0) },
+    ];
+    let params = Params { wrap: true, justify: Justify::End, ..params(140.0, 100.0) };
+    let slots = flex::layout(&specs, &params);
+
+    // Two items fit on each line, which are packed towards the end of the line.
+    assert_eq!(main_ranges(&slots), vec![(10.0, 70.0), (80.0, 140.0), (10.0, 70.0), (80.0, 140.0)]);
+
+    // Each line is as broad as its broadest item.
+    let cross: Vec<Range> = slots.iter().map(|slot| slot.cross).collect();
+    assert_eq!(cross, vec![Range::new(0.0, 10.0), Range::new(0.0, 10.0),
+                           Range::new(15.0, 45.0), Range::new(15.0, 45.0)]);
+}
+
+#[test]
+fn flex_row_should_position_items_as_they_are_set() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let row = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, Vec::new(), |ui, ids| {
+        let mut items = widget::Flex::row(3)
+            .spacing(10.0)
+            .cross_align(CrossAlign::Middle)
+            .w_h(300.0, 100.0)
+            .middle()
+            .set(row, ui);
+        ids.clear();
+        while let Some(item) = items.next() {
+            let item = match item.i {
+                1 => item.grow(1.0),
+                _ => item,
+            };
+            ids.push(item.widget_id);
+            item.set(widget::Rectangle::fill([50.0, 20.0]), ui);
+        }
+    });
+
+    // The layout settles once each item has been measured.
+    driver.update();
+    let rects: Vec<_> = driver.app.iter().map(|&id| driver.ui.rect_of(id).unwrap()).collect();
+    assert_eq!(rects[0].x, Range::new(-150.0, -100.0));
+    assert_eq!(rects[1].x, Range::new(-90.0, 90.0));
+    assert_eq!(rects[2].x, Range::new(100.0, 150.0));
+    assert_eq!(rects[1].y, Range::new(-10.0, 10.0));
+}
+
+/// Update the driven `Ui` as an event-driven loop would without any further input, i.e. only while
+/// a redraw deadline has been requested. Returns the number of updates performed.
+fn update_until_settled<T, F>(driver: &mut Driver<T, F>) -> usize
+    where F: FnMut(&mut UiCell, &mut T),
+{
+    let mut updates = 0;
+    while driver.ui.next_redraw_deadline().is_some() {
+        assert!(updates < 10, "the layout never settled");
+        driver.update();
+        updates += 1;
+    }
+    updates
+}
+
+#[test]
+fn flex_layout_should_settle_without_further_input() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let row = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, Vec::new(), |ui, ids| {
+        let mut items = widget::Flex::row(2)
+            .w_h(300.0, 100.0)
+            .middle()
+            .set(row, ui);
+        ids.clear();
+        while let Some(item) = items.next() {
+            let w = match item.i {
+                0 => 50.0,
+                _ => 100.0,
+            };
+            ids.push(item.widget_id);
+            item.grow(1.0).set(widget::Rectangle::fill([w, 20.0]), ui);
+        }
+    });
+
+    // The first item was laid out before the second had been measured.
+    assert_eq!(update_until_settled(&mut driver), 1);
+    let rects: Vec<_> = driver.app.iter().map(|&id| driver.ui.rect_of(id).unwrap()).collect();
+    assert_eq!(rects[0].x, Range::new(-150.0, -25.0));
+    assert_eq!(rects[1].x, Range::new(-25.0, 150.0));
+}
+
+fn placement(row: usize, col: usize, row_span: usize, col_span: usize, dim: [Scalar; 2])
+    -> Placement
+{
+    Placement { row: row, col: col, row_span: row_span, col_span: col_span, dim: dim }
+}
+
+#[test]
+fn grid_auto_columns_should_fit_the_widest_item() {
+    let columns = [Track::Auto, Track::Fraction(1.0)];
+    let placements = [
+        placement(0, 0, 1, 1, [60.0, 20.0]),
+        placement(0, 1, 1, 1, [10.0, 30.0]),
+        placement(1, 0, 1, 1, [80.0, 20.0]),
+        placement(1, 1, 1, 1, [10.0, 30.0]),
+    ];
+    let tracks = resolve_tracks(&columns, &[], &placements, [300.0, 200.0], [10.0, 5.0]);
+    assert_eq!(tracks.columns, vec![Range::new(0.0, 80.0), Range::new(90.0, 300.0)]);
+    assert_eq!(tracks.rows, vec![Range::new(0.0, 30.0), Range::new(35.0, 65.0)]);
+}
+
+#[test]
+fn grid_spanning_items_should_grow_the_auto_tracks_they_span() {
+    let columns = [Track::Fixed(50.0), Track::Auto, Track::Auto];
+    let rows = [Track::Fixed(20.0), Track::Fraction(1.0), Track::Fraction(3.0)];
+    let placements = [
+        placement(0, 0, 1, 3, [170.0, 20.0]),
+        placement(1, 1, 1, 1, [40.0, 10.0]),
+    ];
+    let tracks = resolve_tracks(&columns, &rows, &placements, [400.0, 100.0], [0.0, 0.0]);
+    assert_eq!(tracks.columns[0], Range::new(0.0, 50.0));
+    assert_eq!(tracks.columns[1], Range::new(50.0, 130.0));
+    assert_eq!(tracks.columns[2], Range::new(130.0, 170.0));
+    assert_eq!(tracks.rows, vec![Range::new(0.0, 20.0), Range::new(20.0, 40.0),
+                                 Range::new(40.0, 100.0)]);
+}
+
+#[test]
+fn grid_layout_should_position_items_within_their_cells() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let grid = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, Vec::new(), |ui, ids| {
+        let columns = [Track::Auto, Track::Fraction(1.0)];
+        let mut items = widget::GridLayout::new(&columns, &[], 3)
+            .gap(10.0)
+            .w_h(300.0, 200.0)
+            .middle()
+            .set(grid, ui);
+        ids.clear();
+        while let Some(item) = items.next() {
+            let (item, w) = match item.i {
+                0 => (item, 60.0),
+                1 => (item, 20.0),
+                _ => (item.span(1, 2).x_align(CellAlign::Middle).y_align(CellAlign::Start), 40.0),
+            };
+            ids.push(item.widget_id);
+            item.set(widget::Rectangle::fill([w, 20.0]), ui);
+        }
+    });
+
+    // The layout settles once each item has been measured.
+    assert_eq!(update_until_settled(&mut driver), 1);
+    let rects: Vec<_> = driver.app.iter().map(|&id| driver.ui.rect_of(id).unwrap()).collect();
+    assert_eq!(rects[0].x, Range::new(-150.0, -90.0));
+    assert_eq!(rects[1].x, Range::new(-80.0, 150.0));
+    assert_eq!(rects[1].y, Range::new(80.0, 100.0));
+    assert_eq!(rects[2].x, Range::new(-20.0, 20.0));
+    assert_eq!(rects[2].y, Range::new(50.0, 70.0));
+}
+
+#[test]
+fn sizeable_limits_should_clamp_and_lock_the_resolved_dimensions() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids: Vec<widget::Id> = {
+        let mut id_gen = ui.widget_id_generator();
+        (0..5).map(|_| id_gen.next()).collect()
+    };
+    let ui = &mut ui.set_widgets();
+    widget::Rectangle::fill([400.0, 300.0]).middle().set(ids[0], ui);
+    widget::Rectangle::fill([0.0, 0.0])
+        .wh_of(ids[0])
+        .min_w(500.0)
+        .max_h(100.0)
+        .set(ids[1], ui);
+    widget::Rectangle::fill([0.0, 0.0])
+        .w_percent_of(ids[0], 25.0)
+        .h_percent_of(ids[0], 50.0)
+        .set(ids[2], ui);
+    widget::Rectangle::fill([0.0, 0.0])
+        .wh_of(ids[0])
+        .aspect_ratio(16.0 / 9.0)
+        .set(ids[3], ui);
+    widget::Rectangle::fill([0.0, 0.0])
+        .wh_of(ids[0])
+        .max_w(200.0)
+        .aspect_ratio(1.0)
+        .top_left_of(ids[0])
+        .set(ids[4], ui);
+    assert_eq!(ui.wh_of(ids[1]), Some([500.0, 100.0]));
+    assert_eq!(ui.wh_of(ids[2]), Some([100.0, 150.0]));
+    assert_eq!(ui.wh_of(ids[3]), Some([400.0, 225.0]));
+    assert_eq!(ui.rect_of(ids[4]).map(|r| (r.x, r.y)),
+               Some((Range::new(-200.0, 0.0), Range::new(-50.0, 150.0))));
+}
+
+#[test]
+fn wrapped_text_should_resolve_its_height_with_and_without_an_aspect_ratio() {
+    let mut ui = new_ui_with_font();
+    let ids: Vec<widget::Id> = {
+        let mut id_gen = ui.widget_id_generator();
+        (0..3).map(|_| id_gen.next()).collect()
+    };
+    let ui = &mut ui.set_widgets();
+    let text = "hello world foo bar";
+    widget::Text::new(text).w(50.0).no_line_wrap().set(ids[0], ui);
+    widget::Text::new(text).w(50.0).wrap_by_word().set(ids[1], ui);
+    widget::Text::new(text).w(50.0).wrap_by_word().aspect_ratio(1.0).set(ids[2], ui);
+    let line_h = ui.h_of(ids[0]).unwrap();
+    let wrapped_h = ui.h_of(ids[1]).unwrap();
+    assert!(wrapped_h > line_h);
+    assert_eq!(ui.w_of(ids[1]), Some(50.0));
+    assert_eq!(ui.wh_of(ids[2]), Some([50.0, wrapped_h.min(50.0)]));
+}
+
+fn assert_weights(weights: &[Scalar], expected: &[Scalar]) {
+    assert_eq!(weights.len(), expected.len());
+    for (w, e) in weights.iter().zip(expected) {
+        assert!((w - e).abs() < 1.0e-9, "{:?} != {:?}", weights, expected);
+    }
+}
+
+#[test]
+fn splitter_dividers_should_resize_collapse_and_restore_panes() {
+    use event::Input;
+    use input::{Button, MouseButton};
+
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let splitter = ui.widget_id_generator().next();
+    let app = (vec![1.0, 3.0], Vec::new());
+    let mut driver = Driver::new(ui, app, |ui, app| {
+        let (panes, maybe_weights) = widget::Splitter::columns(&app.0)
+            .divider_thickness(6.0)
+            .w_h(406.0, 100.0)
+            .middle()
+            .set(splitter, ui);
+        if let Some(weights) = maybe_weights {
+            app.0 = weights;
+        }
+        app.1.clear();
+        for pane in panes {
+            app.1.push(pane.widget_id);
+            pane.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+        }
+    });
+    driver.update();
+    let first = driver.app.1[0];
+    let divider_offset = |driver: &Driver<_, _>| driver.ui.w_of(first).unwrap() / 2.0 + 3.0;
+    assert_eq!(driver.ui.w_of(first), Some(100.0));
+
+    let offset = divider_offset(&driver);
+    driver.drag(first, [offset, 0.0], [offset + 50.0, 10.0]);
+    assert_weights(&driver.app.0, &[1.5, 2.5]);
+    assert_eq!(driver.ui.w_of(first), Some(150.0));
+
+    // Neither pane may be dragged smaller than the minimum length.
+    let offset = divider_offset(&driver);
+    driver.drag(first, [offset, 0.0], [offset - 300.0, 0.0]);
+    assert_eq!(driver.ui.w_of(first), Some(20.0));
+    assert_weights(&driver.app.0, &[0.2, 3.8]);
+
+    let double_click = |driver: &mut Driver<_, _>| {
+        let offset = divider_offset(driver);
+        driver.move_mouse(first, [offset, 0.0]);
+        for _ in 0..2 {
+            driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+            driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        }
+        driver.update();
+        driver.update();
+    };
+    double_click(&mut driver);
+    assert_weights(&driver.app.0, &[0.0, 4.0]);
+    assert_eq!(driver.ui.w_of(first), Some(0.0));
+    double_click(&mut driver);
+    assert_weights(&driver.app.0, &[0.2, 3.8]);
+}
+
+#[test]
+fn splitter_panes_should_not_shrink_below_the_minimum_length() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let splitter = ui.widget_id_generator().next();
+    let app = (vec![0.2, 3.8, 0.0], 406.0, Vec::new());
+    let mut driver = Driver::new(ui, app, |ui, app| {
+        let (panes, _) = widget::Splitter::columns(&app.0)
+            .divider_thickness(3.0)
+            .min_pane_length(20.0)
+            .w_h(app.1, 100.0)
+            .middle()
+            .set(splitter, ui);
+        app.2.clear();
+        for pane in panes {
+            app.2.push(pane.widget_id);
+            pane.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+        }
+    });
+    driver.update();
+    let widths = |ui: &::Ui, ids: &[widget::Id]| -> Vec<Option<Scalar>> {
+        ids.iter().map(|&id| ui.w_of(id)).collect()
+    };
+    assert_eq!(widths(&driver.ui, &driver.app.2), vec![Some(20.0), Some(380.0), Some(0.0)]);
+
+    // Shrinking the splitter takes the length from the panes above the minimum, while the
+    // collapsed pane remains collapsed.
+    driver.app.1 = 206.0;
+    driver.update();
+    assert_eq!(widths(&driver.ui, &driver.app.2), vec![Some(20.0), Some(180.0), Some(0.0)]);
+
+    // Once the panes no longer fit, they share the length equally.
+    driver.app.1 = 36.0;
+    driver.update();
+    assert_eq!(widths(&driver.ui, &driver.app.2), vec![Some(15.0), Some(15.0), Some(0.0)]);
+}
+
+#[test]
+fn widgets_should_be_sized_to_fit_their_content() {
+    let mut ui = new_ui_with_font();
+    let font_id = ui.fonts.ids().next().unwrap();
+    let ids: Vec<widget::Id> = {
+        let mut id_gen = ui.widget_id_generator();
+        (0..3).map(|_| id_gen.next()).collect()
+    };
+    let (button, canvas, kid) = (ids[0], ids[1], ids[2]);
+    let mut driver = Driver::new(ui, "OK", move |ui, label| {
+        widget::Button::new()
+            .label(label)
+            .label_font_size(14)
+            .border(1.0)
+            .padded_w_of_content(8.0)
+            .h(30.0)
+            .top_left()
+            .set(button, ui);
+        widget::Canvas::new()
+            .pad(10.0)
+            .wh_of_content()
+            .middle()
+            .set(canvas, ui);
+        widget::Rectangle::fill([120.0, 40.0])
+            .top_left_of(canvas)
+            .parent(canvas)
+            .set(kid, ui);
+    });
+
+    // The button's width follows the measured label.
+    let label_w = |label: &str, ui: &::Ui| {
+        text::line::width(label, ui.fonts.get(font_id).unwrap(), 14)
+    };
+    for &label in &["OK", "Abbrechen und verwerfen"] {
+        driver.app = label;
+        driver.update();
+        let expected = label_w(label, &driver.ui) + 2.0 + 16.0;
+        assert_eq!(driver.ui.wh_of(button), Some([expected, 30.0]));
+    }
+
+    // Containers fit the bounding box of their kids as of the previous update.
+    driver.update();
+    assert_eq!(driver.ui.wh_of(canvas), Some([140.0, 60.0]));
+}
+
+#[test]
+fn content_sized_items_should_be_measured_by_layout_containers() {
+    let mut ui = new_ui_with_font();
+    let font_id = ui.fonts.ids().next().unwrap();
+    let (row, grid) = {
+        let mut id_gen = ui.widget_id_generator();
+        (id_gen.next(), id_gen.next())
+    };
+    let button = || {
+        widget::Button::new()
+            .label("Abbrechen")
+            .label_font_size(14)
+            .border(1.0)
+            .padded_w_of_content(8.0)
+            .h(30.0)
+    };
+    let mut driver = Driver::new(ui, (Vec::new(), Vec::new()), move |ui, ids| {
+        let mut items = widget::Flex::row(2)
+            .w_h(300.0, 100.0)
+            .mid_top()
+            .set(row, ui);
+        ids.0.clear();
+        while let Some(item) = items.next() {
+            ids.0.push(item.widget_id);
+            match item.i {
+                0 => { item.set(button(), ui); },
+                _ => item.grow(1.0).set(widget::Rectangle::fill([10.0, 20.0]), ui),
+            }
+        }
+
+        let columns = [Track::Auto, Track::Fraction(1.0)];
+        let mut items = widget::GridLayout::new(&columns, &[], 2)
+            .w_h(300.0, 100.0)
+            .mid_bottom()
+            .set(grid, ui);
+        ids.1.clear();
+        while let Some(item) = items.next() {
+            ids.1.push(item.widget_id);
+            match item.i {
+                0 => { item.set(button(), ui); },
+                _ => item.set(widget::Rectangle::fill([10.0, 20.0]), ui),
+            }
+        }
+    });
+    update_until_settled(&mut driver);
+
+    let button_w = text::line::width("Abbrechen", driver.ui.fonts.get(font_id).unwrap(), 14)
+        + 2.0 + 16.0;
+    let left = -150.0;
+    for ids in &[&driver.app.0, &driver.app.1] {
+        let rects: Vec<_> = ids.iter().map(|&id| driver.ui.rect_of(id).unwrap()).collect();
+        assert_eq!(rects[0].x, Range::new(left, left + button_w));
+        assert_eq!(rects[1].x.start, left + button_w);
+    }
+}
@@ -1,3 +1,23 @@
+mod code_editor;
+mod constraint;
+mod dock;
+mod driver;
+mod text_edit;
+mod tooltip;
 mod global_input;
+mod layout;
+mod modal;
 mod widget_input;
+mod window;
 mod ui;
+
+use {Ui, UiBuilder};
+
+
+/// An 800x600 `Ui` with a font loaded so that text may be measured and edited.
+fn new_ui_with_font() -> Ui {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(path).unwrap();
+    ui
+}
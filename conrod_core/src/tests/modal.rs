@@ -0,0 +1,231 @@
+use {Positionable, Sizeable, UiBuilder, Widget};
+use event::Input;
+use input::{Button, Key, MouseButton};
+use testing::Driver;
+use tests::new_ui_with_font;
+use widget;
+use widget::dialog::Response;
+use widget::modal::Dismiss;
+
+
+struct App {
+    is_open: bool,
+    under_clicks: usize,
+    inner_clicks: usize,
+    dismissals: Vec<Dismiss>,
+}
+
+#[test]
+fn modal_should_route_input_only_to_its_subtree() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let (under, modal, inner) = (ids[0], ids[1], ids[2]);
+    let app = App { is_open: true, under_clicks: 0, inner_clicks: 0, dismissals: Vec::new() };
+    let mut driver = Driver::new(ui, app, move |ui, app| {
+        let window = ui.window;
+        for _click in widget::Button::new().w_h(100.0, 40.0).x_y(-200.0, 0.0).set(under, ui) {
+            app.under_clicks += 1;
+        }
+        if app.is_open {
+            if let Some(dismiss) = widget::Modal::new().parent(window).set(modal, ui) {
+                app.dismissals.push(dismiss);
+            }
+            let clicks = widget::Button::new().w_h(100.0, 40.0).middle_of(modal).set(inner, ui);
+            app.inner_clicks += clicks.0 as usize;
+        }
+    });
+    driver.update();
+    driver.update();
+    assert_eq!(driver.ui.modal(), Some(modal));
+
+    // Clicking the widget beneath the layer dismisses the layer instead.
+    driver.click(under);
+    driver.update();
+    assert_eq!(driver.app.under_clicks, 0);
+    assert_eq!(driver.app.dismissals, vec![Dismiss::Backdrop]);
+
+    // Widgets within the layer receive input as usual.
+    driver.click(inner);
+    driver.update();
+    assert_eq!(driver.app.inner_clicks, 1);
+    assert_eq!(driver.app.dismissals, vec![Dismiss::Backdrop]);
+
+    driver.press_key(Key::Escape);
+    assert_eq!(driver.app.dismissals, vec![Dismiss::Backdrop, Dismiss::Escape]);
+
+    // Once closed, input reaches the rest of the `Ui` again.
+    driver.app.is_open = false;
+    driver.update();
+    assert_eq!(driver.ui.modal(), None);
+    driver.click(under);
+    driver.update();
+    assert_eq!(driver.app.under_clicks, 1);
+}
+
+#[test]
+fn closing_a_modal_should_return_the_keyboard_to_the_previous_widget() {
+    let mut ui = new_ui_with_font();
+    let field = ui.widget_id_generator().next();
+    let modal = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, (false, String::new()), move |ui, app| {
+        let window = ui.window;
+        if let Some(text) = widget::TextEdit::new(&app.1).w_h(200.0, 30.0).set(field, ui) {
+            app.1 = text;
+        }
+        if app.0 {
+            widget::Modal::new().parent(window).set(modal, ui);
+        }
+    });
+    driver.update();
+    driver.type_text(field, "a");
+    assert_eq!(driver.ui.global_input().current.widget_capturing_keyboard, Some(field));
+
+    driver.app.0 = true;
+    driver.update();
+    assert_eq!(driver.ui.global_input().current.widget_capturing_keyboard, Some(modal));
+    driver.enter_text("b");
+    assert_eq!(driver.app.1, "a");
+
+    driver.app.0 = false;
+    driver.update();
+    assert_eq!(driver.ui.global_input().current.widget_capturing_keyboard, Some(field));
+    driver.enter_text("c");
+    assert_eq!(driver.app.1, "ac");
+}
+
+#[test]
+fn dialogs_should_report_the_users_response() {
+    let mut ui = new_ui_with_font();
+    let confirm = ui.widget_id_generator().next();
+    let prompt = ui.widget_id_generator().next();
+    let app = (true, Vec::new(), String::new());
+    let mut driver = Driver::new(ui, app, move |ui, app| {
+        let window = ui.window;
+        if app.0 {
+            if let Some(response) = widget::dialog::Confirm::new("Discard changes?")
+                .parent(window)
+                .set(confirm, ui)
+            {
+                app.1.push(response);
+            }
+        } else {
+            let (edited, response) = widget::dialog::Prompt::new("Name:", &app.2)
+                .parent(window)
+                .set(prompt, ui);
+            if let Some(text) = edited {
+                app.2 = text;
+            }
+            app.1.extend(response);
+        }
+    });
+    driver.update();
+    driver.update();
+
+    driver.press_key(Key::Escape);
+    assert_eq!(driver.app.1, vec![Response::Cancel]);
+
+    // The "OK" button sits in the bottom-right corner of the 320x140 panel.
+    driver.move_mouse(confirm, [110.0, -45.0]);
+    driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    driver.update();
+    driver.update();
+    assert_eq!(driver.app.1, vec![Response::Cancel, Response::Ok]);
+
+    // The prompt's field captures the keyboard as soon as it opens.
+    driver.app.0 = false;
+    driver.app.1.clear();
+    driver.update();
+    driver.update();
+    driver.enter_text("Rusty");
+    driver.press_key(Key::Return);
+    driver.update();
+    assert_eq!(driver.app.2, "Rusty");
+    assert_eq!(driver.app.1, vec![Response::Ok]);
+}
+
+#[test]
+fn reopening_a_prompt_should_refocus_its_field() {
+    let mut ui = new_ui_with_font();
+    let prompt = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, (true, String::new()), move |ui, app| {
+        let window = ui.window;
+        if app.0 {
+            let (edited, _) = widget::dialog::Prompt::new("Name:", &app.1)
+                .parent(window)
+                .set(prompt, ui);
+            if let Some(text) = edited {
+                app.1 = text;
+            }
+        }
+    });
+    driver.update();
+    driver.update();
+    driver.enter_text("a");
+    assert_eq!(driver.app.1, "a");
+
+    driver.app.0 = false;
+    driver.update();
+    assert_eq!(driver.ui.modal(), None);
+
+    driver.app.0 = true;
+    driver.update();
+    driver.update();
+    driver.enter_text("b");
+    assert_eq!(driver.app.1, "ab");
+}
+
+#[test]
+fn shortcuts_outside_of_an_open_modal_should_be_inactive() {
+    use event;
+    use input::shortcut::{Chord, Scope};
+
+    let mut ui = new_ui_with_font();
+    let field = ui.widget_id_generator().next();
+    let modal = ui.widget_id_generator().next();
+    let save = ui.register_shortcut(Chord::ctrl(Key::S), Scope::Global).unwrap();
+    let find = ui.register_shortcut(Chord::ctrl(Key::F), Scope::Widget(field)).unwrap();
+    let close = ui.register_shortcut(Chord::ctrl(Key::W), Scope::Widget(modal)).unwrap();
+    let mut driver = Driver::new(ui, false, move |ui, &mut is_open| {
+        let window = ui.window;
+        widget::TextEdit::new("").w_h(200.0, 30.0).set(field, ui);
+        if is_open {
+            widget::Modal::new().parent(window).set(modal, ui);
+        }
+    });
+    let shortcuts = |driver: &Driver<_, _>, id| -> Vec<event::Widget> {
+        driver.events(id).iter()
+            .filter(|event| match **event {
+                event::Widget::Shortcut(_) => true,
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    };
+
+    driver.click(field);
+    driver.press_chord(Chord::ctrl(Key::F));
+    assert_eq!(shortcuts(&driver, field), vec![event::Widget::Shortcut(find)]);
+
+    // While the modal is open, neither global shortcuts nor those scoped to widgets outside of the
+    // modal are active.
+    driver.app = true;
+    driver.update();
+    assert_eq!(driver.ui.global_input().current.widget_capturing_keyboard, Some(modal));
+    for &key in &[Key::S, Key::F] {
+        driver.press_chord(Chord::ctrl(key));
+        assert_eq!(shortcuts(&driver, modal), vec![]);
+        assert_eq!(shortcuts(&driver, field), vec![]);
+    }
+    driver.press_chord(Chord::ctrl(Key::W));
+    assert_eq!(shortcuts(&driver, modal), vec![event::Widget::Shortcut(close)]);
+
+    driver.app = false;
+    driver.update();
+    driver.press_chord(Chord::ctrl(Key::S));
+    assert_eq!(shortcuts(&driver, field), vec![event::Widget::Shortcut(save)]);
+}
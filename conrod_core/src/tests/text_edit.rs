@@ -0,0 +1,194 @@
+use {Positionable, Sizeable, UiCell, Widget};
+use event::Input;
+use input::{Button, Key, KeyBindings, ModifierKey, MouseButton};
+use input::shortcut::Chord;
+use testing::Driver;
+use tests::new_ui_with_font;
+use text::search::{Edit, Query};
+use std;
+use widget::{self, text_edit};
+use widget::text_edit::SearchStep;
+
+
+struct App {
+    text_edit: widget::Id,
+    text: String,
+}
+
+fn set_widgets(ui: &mut UiCell, app: &mut App) {
+    for edit in widget::TextEdit::new(&app.text)
+        .w_h(400.0, 200.0)
+        .middle()
+        .set(app.text_edit, ui)
+    {
+        app.text = edit;
+    }
+}
+
+fn new_driver(key_bindings: KeyBindings) -> Driver<App, fn(&mut UiCell, &mut App)> {
+    let mut ui = new_ui_with_font();
+    ui.theme.key_bindings = key_bindings;
+    let text_edit = ui.widget_id_generator().next();
+    let app = App { text_edit: text_edit, text: String::new() };
+    Driver::new(ui, app, set_widgets)
+}
+
+
+#[test]
+fn text_edit_should_follow_the_emacs_key_bindings() {
+    let mut driver = new_driver(KeyBindings::emacs());
+    let text_edit = driver.app.text_edit;
+    driver.type_text(text_edit, "hello world");
+    assert_eq!(driver.app.text, "hello world");
+
+    driver.press_chord(Chord::ctrl(Key::A));
+    driver.enter_text("X");
+    assert_eq!(driver.app.text, "Xhello world");
+
+    driver.press_chord(Chord::new(ModifierKey::ALT, Key::D));
+    assert_eq!(driver.app.text, "X world");
+
+    driver.press_chord(Chord::ctrl(Key::K));
+    assert_eq!(driver.app.text, "X");
+}
+
+#[test]
+fn text_edit_should_follow_the_windows_and_linux_key_bindings() {
+    let mut driver = new_driver(KeyBindings::windows_linux());
+    let text_edit = driver.app.text_edit;
+    driver.type_text(text_edit, "one two three");
+
+    // Move to the start of the line and select the first word.
+    driver.press_key(Key::Home);
+    driver.press_chord(Chord::new(ModifierKey::CTRL | ModifierKey::SHIFT, Key::Right));
+    driver.enter_text("1");
+    assert_eq!(driver.app.text, "1 two three");
+
+    driver.press_key(Key::End);
+    driver.press_chord(Chord::new(ModifierKey::CTRL, Key::Backspace));
+    assert_eq!(driver.app.text, "1 two ");
+
+    // Unbound chords such as the old `Ctrl+E` have no effect.
+    driver.press_chord(Chord::ctrl(Key::E));
+    assert_eq!(driver.app.text, "1 two ");
+}
+
+/// Click the left mouse button the given number of times at the given position relative to the
+/// centre of the `TextEdit`, then update.
+fn click_times<T, F>(driver: &mut Driver<T, F>, id: widget::Id, rel_xy: [f64; 2], times: usize)
+    where F: FnMut(&mut UiCell, &mut T),
+{
+    driver.move_mouse(id, rel_xy);
+    for _ in 0..times {
+        driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    }
+    driver.update();
+}
+
+/// A position over the given character of the first line, assuming the `TextEdit` is
+/// left-justified and top-aligned.
+fn first_line_xy(char_x: f64) -> [f64; 2] {
+    [-200.0 + char_x, 90.0]
+}
+
+#[test]
+fn double_clicking_a_text_edit_should_select_a_word() {
+    let mut driver = new_driver(KeyBindings::windows_linux());
+    let text_edit = driver.app.text_edit;
+    driver.type_text(text_edit, "hello world");
+
+    // Double-click within "world" and replace it.
+    click_times(&mut driver, text_edit, first_line_xy(70.0), 2);
+    driver.enter_text("there");
+    assert_eq!(driver.app.text, "hello there");
+}
+
+#[test]
+fn triple_clicking_a_text_edit_should_select_a_paragraph() {
+    let mut driver = new_driver(KeyBindings::windows_linux());
+    let text_edit = driver.app.text_edit;
+    driver.type_text(text_edit, "one two");
+    driver.press_key(Key::Return);
+    driver.enter_text("three");
+
+    click_times(&mut driver, text_edit, first_line_xy(10.0), 3);
+    driver.enter_text("1 2");
+    assert_eq!(driver.app.text, "1 2\nthree");
+}
+
+#[test]
+fn dragging_after_a_double_press_should_extend_the_selection_by_words() {
+    let mut driver = new_driver(KeyBindings::windows_linux());
+    let text_edit = driver.app.text_edit;
+    driver.type_text(text_edit, "one two three");
+
+    // Double-press within "one", then drag into "two" before releasing.
+    click_times(&mut driver, text_edit, first_line_xy(10.0), 1);
+    driver.drag(text_edit, first_line_xy(10.0), first_line_xy(45.0));
+    driver.enter_text("1");
+    assert_eq!(driver.app.text, "1 three");
+}
+
+#[test]
+fn search_steps_should_select_each_match_in_turn() {
+    let mut ui = new_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let query = Query::text("ab").case_sensitive(false);
+    let app = ("ab cd AB ab".to_string(), None);
+    let mut driver = Driver::new(ui, app, |ui, &mut (ref mut text, ref mut step)| {
+        let mut text_edit_widget = widget::TextEdit::new(text).search(&query);
+        if let Some(step) = std::mem::replace(step, None) {
+            text_edit_widget = text_edit_widget.search_step(step);
+        }
+        for edit in text_edit_widget.w_h(400.0, 200.0).middle().set(text_edit, ui) {
+            *text = edit;
+        }
+    });
+    assert_eq!(text_edit::selected_chars(&driver.ui, text_edit), None);
+
+    let steps = [
+        (SearchStep::Next, 0..2),
+        (SearchStep::Next, 6..8),
+        (SearchStep::Previous, 0..2),
+        (SearchStep::Previous, 9..11),
+        (SearchStep::Next, 0..2),
+    ];
+    for &(step, ref expected) in steps.iter() {
+        driver.app.1 = Some(step);
+        driver.update();
+        assert_eq!(text_edit::selected_chars(&driver.ui, text_edit), Some(expected.clone()));
+    }
+
+    // Replace the selected match.
+    let selected = text_edit::selected_chars(&driver.ui, text_edit).unwrap();
+    let (new_text, edit) = query.replace_at(&driver.app.0, selected, "xyz").unwrap();
+    assert_eq!(new_text, "xyz cd AB ab");
+    assert_eq!(edit, Edit { chars: 0..2, replacement: "xyz".to_string() });
+}
+
+#[test]
+fn replacing_all_matches_should_report_each_edit() {
+    let text = "Über über ÜBER";
+    let query = Query::text("über");
+    assert_eq!(query.find_all(text).len(), 1);
+
+    let query = query.case_sensitive(false);
+    let (new_text, edits) = query.replace_all(text, "uber");
+    assert_eq!(new_text, "uber uber uber");
+    let ranges: Vec<_> = edits.iter().map(|edit| edit.chars.clone()).collect();
+    assert_eq!(ranges, vec![0..4, 5..9, 10..14]);
+
+    // Only an exact match may be replaced individually.
+    assert!(query.replace_at(text, 1..4, "x").is_none());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_replacements_should_expand_capture_groups() {
+    let query = Query::regex(r"(\w+)=(\d+)").unwrap();
+    let (new_text, edits) = query.replace_all("a=1, b=22", "$2=$1");
+    assert_eq!(new_text, "1=a, 22=b");
+    assert_eq!(edits[1], Edit { chars: 5..9, replacement: "22=b".to_string() });
+    assert!(Query::regex("(").is_err());
+}
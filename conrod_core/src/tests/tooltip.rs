@@ -0,0 +1,78 @@
+use {Positionable, Sizeable, UiBuilder, Widget};
+use clock::{Clock, ManualClock};
+use event::Input;
+use input::{Button, MouseButton};
+use std::time::Duration;
+use testing::Driver;
+use widget;
+
+
+#[test]
+fn tooltip_should_appear_once_its_target_has_been_hovered_for_the_delay() {
+    let clock = ManualClock::new();
+    let mut ui = UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let button = ui.widget_id_generator().next();
+    let tooltip = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, false, move |ui, is_visible| {
+        widget::Button::new().w_h(100.0, 40.0).middle().set(button, ui);
+        *is_visible = widget::Tooltip::new("Save", button)
+            .delay(Duration::from_millis(500))
+            .set(tooltip, ui);
+    });
+
+    driver.hover(button);
+    driver.update();
+    assert!(!driver.app);
+
+    // An update is requested for the moment the delay elapses, even if the mouse stays still.
+    clock.advance(Duration::from_millis(200));
+    driver.update();
+    assert!(!driver.app);
+    assert_eq!(driver.ui.next_redraw_deadline(),
+               Some(clock.now() + Duration::from_millis(300)));
+    clock.advance(Duration::from_millis(300));
+    driver.update();
+    assert!(driver.app);
+
+    // Leaving the target hides the tooltip.
+    driver.move_mouse(button, [200.0, 0.0]);
+    driver.update();
+    assert!(!driver.app);
+}
+
+#[test]
+fn tooltip_should_hide_on_press_until_its_target_is_hovered_again() {
+    let clock = ManualClock::new();
+    let mut ui = UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let button = ui.widget_id_generator().next();
+    let tooltip = ui.widget_id_generator().next();
+    let delay = Duration::from_millis(500);
+    let mut driver = Driver::new(ui, false, move |ui, is_visible| {
+        widget::Button::new().w_h(100.0, 40.0).middle().set(button, ui);
+        *is_visible = widget::Tooltip::new("Save", button)
+            .delay(delay)
+            .set(tooltip, ui);
+    });
+
+    driver.hover(button);
+    clock.advance(delay);
+    driver.update();
+    assert!(driver.app);
+
+    driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    driver.update();
+    assert!(!driver.app);
+    driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    clock.advance(delay);
+    driver.update();
+    assert!(!driver.app);
+
+    // Once the mouse leaves and returns, the tooltip appears after the delay again.
+    driver.move_mouse(button, [200.0, 0.0]);
+    driver.update();
+    driver.hover(button);
+    assert!(!driver.app);
+    clock.advance(delay);
+    driver.update();
+    assert!(driver.app);
+}
@@ -131,3 +131,567 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn mouse_enter_and_leave_events_should_be_created_when_the_widget_under_mouse_changes() {
+    let ui = &mut windowless_ui();
+
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    fn set_widgets(ui: &mut Ui, canvas: widget::Id, button: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Button::new()
+            .w_h(100.0, 100.0)
+            .middle_of(canvas)
+            .set(button, ui);
+    }
+
+    set_widgets(ui, canvas, button);
+    let button_xy = ui.xy_of(button).unwrap();
+    move_mouse_to_abs_coordinates(button_xy[0], button_xy[1], ui);
+    assert_eq!(Some(button), ui.hovered_widget());
+    assert!(ui.hover_duration_of(button).is_some());
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseEnter(button)));
+    {
+        let widget_input = ui.widget_input(button);
+        assert_eq!(Some(event::Widget::MouseEnter), widget_input.events().find(|e| {
+            *e == event::Widget::MouseEnter
+        }));
+    }
+
+    set_widgets(ui, canvas, button);
+    move_mouse_to_abs_coordinates(button_xy[0] + 200.0, button_xy[1], ui);
+    assert_eq!(Some(canvas), ui.hovered_widget());
+    assert!(ui.hover_duration_of(button).is_none());
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseLeave(button)));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseEnter(canvas)));
+}
+
+#[test]
+fn dragging_a_payload_onto_an_accepting_widget_should_drop_it() {
+    use drag_drop;
+
+    let ui = &mut windowless_ui();
+
+    let (canvas, source, target) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+
+    fn set_widgets<F>(ui: &mut Ui, canvas: widget::Id, source: widget::Id, target: widget::Id, f: F)
+        where F: FnOnce(&mut ::UiCell),
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Button::new().w_h(100.0, 100.0).mid_left_of(canvas).set(source, ui);
+        widget::Button::new().w_h(100.0, 100.0).mid_right_of(canvas).set(target, ui);
+        f(ui);
+    }
+
+    set_widgets(ui, canvas, source, target, |_| ());
+    let source_xy = ui.xy_of(source).unwrap();
+    let target_xy = ui.xy_of(target).unwrap();
+    move_mouse_to_abs_coordinates(source_xy[0], source_xy[1], ui);
+    press_mouse_button(MouseButton::Left, ui);
+
+    // Begin dragging from the source.
+    set_widgets(ui, canvas, source, target, |ui| {
+        let preview = drag_drop::Preview::Label("42".to_string());
+        assert!(ui.start_drag(source, Box::new(42u32), preview));
+    });
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::DragStart(source)));
+    assert!(ui.drag_drop().map_or(false, |d| d.is_dragging()));
+
+    // Move over the target which accepts payloads of type `u32`.
+    set_widgets(ui, canvas, source, target, |_| ());
+    move_mouse_to_abs_coordinates(target_xy[0], target_xy[1], ui);
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::DragLeave(source)));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::DragEnter(target)));
+    set_widgets(ui, canvas, source, target, |ui| {
+        let accept = ui.drag_drop().and_then(|d| d.payload::<u32>()).is_some();
+        ui.accept_drag(target, accept);
+    });
+    assert!(ui.drag_drop().map_or(false, |d| d.is_accepted()));
+
+    // Release the mouse to drop the payload onto the target.
+    release_mouse_button(MouseButton::Left, ui);
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::Drop(target, target_xy)));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::DragEnd(source, Some(target))));
+    {
+        let widget_input = ui.widget_input(target);
+        assert_eq!(Some(event::Widget::Drop([0.0, 0.0])), widget_input.events().find(|e| {
+            match *e { event::Widget::Drop(_) => true, _ => false }
+        }));
+    }
+
+    let mut payload = None;
+    set_widgets(ui, canvas, source, target, |ui| payload = ui.take_drag_payload(target));
+    assert_eq!(Some(42), payload.and_then(|p| p.downcast::<u32>().ok()).map(|p| *p));
+    assert!(ui.drag_drop().is_none());
+}
+
+#[test]
+fn dropped_files_should_be_delivered_to_the_widget_under_the_mouse() {
+    use std::path::PathBuf;
+
+    let ui = &mut windowless_ui();
+
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    fn set_widgets(ui: &mut Ui, canvas: widget::Id, button: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Button::new()
+            .w_h(100.0, 100.0)
+            .middle_of(canvas)
+            .set(button, ui);
+    }
+
+    set_widgets(ui, canvas, button);
+    let button_xy = ui.xy_of(button).unwrap();
+    move_mouse_to_abs_coordinates(button_xy[0], button_xy[1], ui);
+    set_widgets(ui, canvas, button);
+
+    let path = PathBuf::from("image.png");
+    test_handling_basic_input_event(ui, Input::HoveredFile(path.clone()));
+    test_handling_basic_input_event(ui, Input::DroppedFile(path.clone()));
+    let event = event::Ui::DroppedFile(Some(button), path.clone());
+    assert_event_was_pushed(ui, event::Event::Ui(event));
+    {
+        let widget_events: Vec<_> = ui.widget_input(button).events().collect();
+        assert!(widget_events.contains(&event::Widget::HoveredFile(path.clone())));
+        assert!(widget_events.contains(&event::Widget::DroppedFile(path.clone())));
+        let canvas_events: Vec<_> = ui.widget_input(canvas).events().collect();
+        assert!(!canvas_events.contains(&event::Widget::DroppedFile(path)));
+    }
+}
+
+#[test]
+fn a_recording_should_replay_double_clicks_with_the_recorded_timing() {
+    use clock::ManualClock;
+    use input::record::Player;
+
+    fn set_widgets(ui: &mut Ui, canvas: widget::Id, button: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Button::new()
+            .w_h(100.0, 100.0)
+            .middle_of(canvas)
+            .set(button, ui);
+    }
+
+    fn count_double_clicks(ui: &Ui, button: widget::Id) -> usize {
+        ui.global_input().events().ui().filter(|event| match **event {
+            event::Ui::DoubleClick(Some(id), _) => id == button,
+            _ => false,
+        }).count()
+    }
+
+    // Record a double click with a virtual clock so that the timing is deterministic.
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    ui.start_recording();
+    set_widgets(ui, canvas, button);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    clock.advance(std::time::Duration::from_millis(100));
+    left_click_mouse(ui);
+    assert_eq!(count_double_clicks(ui, button), 1);
+    set_widgets(ui, canvas, button);
+
+    // Click twice more with a delay longer than the double click threshold.
+    clock.advance(std::time::Duration::from_secs(1));
+    left_click_mouse(ui);
+    clock.advance(std::time::Duration::from_secs(1));
+    left_click_mouse(ui);
+    assert_eq!(count_double_clicks(ui, button), 0);
+    set_widgets(ui, canvas, button);
+    let recording = ui.stop_recording().unwrap();
+    assert!(!ui.is_recording());
+
+    // Replay the recording into a fresh `Ui`.
+    let mut player = Player::new(recording);
+    let ui = &mut player.ui_builder().build();
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    assert!(player.play_until_set_widgets(ui));
+    set_widgets(ui, canvas, button);
+    assert!(player.play_until_set_widgets(ui));
+    assert_eq!(count_double_clicks(ui, button), 1);
+    set_widgets(ui, canvas, button);
+    assert!(player.play_until_set_widgets(ui));
+    assert_eq!(count_double_clicks(ui, button), 0);
+    set_widgets(ui, canvas, button);
+    assert!(!player.play_until_set_widgets(ui));
+    assert!(player.is_finished());
+}
+
+#[test]
+fn double_clicks_should_respect_the_threshold_of_a_manual_clock() {
+    use clock::ManualClock;
+
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let threshold = ui.theme.double_click_threshold;
+    let is_double_click = |ui: &Ui| ui.global_input().events().ui().any(|event| match *event {
+        event::Ui::DoubleClick(_, _) => true,
+        _ => false,
+    });
+
+    left_click_mouse(ui);
+    clock.advance(threshold - std::time::Duration::from_millis(1));
+    left_click_mouse(ui);
+    assert!(is_double_click(ui));
+    ui.set_widgets();
+
+    left_click_mouse(ui);
+    clock.advance(threshold);
+    left_click_mouse(ui);
+    assert!(!is_double_click(ui));
+}
+
+#[test]
+fn ui_time_should_advance_with_each_call_to_set_widgets() {
+    use clock::ManualClock;
+
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let start = ui.time();
+
+    clock.advance(std::time::Duration::from_millis(16));
+    ui.set_widgets();
+    assert_eq!(ui.time(), start + std::time::Duration::from_millis(16));
+    assert_eq!(ui.frame_delta(), std::time::Duration::from_millis(16));
+
+    clock.advance(std::time::Duration::from_millis(20));
+    {
+        let ui = ui.set_widgets();
+        clock.advance(std::time::Duration::from_millis(5));
+        // The frame time remains constant throughout `set_widgets`.
+        assert_eq!(ui.time(), start + std::time::Duration::from_millis(36));
+    }
+    assert_eq!(ui.frame_delta(), std::time::Duration::from_millis(20));
+}
+
+#[test]
+fn tooltip_should_request_a_redraw_once_its_delay_has_passed() {
+    use clock::ManualClock;
+
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let (target, tooltip) = {
+        let mut id_gen = ui.widget_id_generator();
+        (id_gen.next(), id_gen.next())
+    };
+    let delay = ui.theme.tooltip_delay;
+
+    fn set_widgets(ui: &mut Ui, target: widget::Id, tooltip: widget::Id) -> bool {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).middle().set(target, ui);
+        widget::Tooltip::new("Hello", target).set(tooltip, ui)
+    }
+
+    assert_eq!(ui.next_redraw_deadline(), None);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    assert!(!set_widgets(ui, target, tooltip));
+    assert!(!set_widgets(ui, target, tooltip));
+
+    // While the target is hovered, the tooltip asks to be updated once the delay has passed.
+    let deadline = ui.time() + delay;
+    assert_eq!(ui.next_redraw_deadline(), Some(deadline));
+
+    clock.advance(delay);
+    assert!(set_widgets(ui, target, tooltip));
+    assert_eq!(ui.next_redraw_deadline(), None);
+}
+
+#[test]
+fn the_earliest_requested_redraw_deadline_should_be_kept_until_the_next_update() {
+    use clock::{Clock, ManualClock};
+
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let second = std::time::Duration::from_secs(1);
+    let now = clock.now();
+    {
+        let mut ui = ui.set_widgets();
+        ui.request_redraw_at(now + second * 2);
+        ui.request_redraw_after(second);
+        ui.request_redraw_at(now + second * 3);
+    }
+    assert_eq!(ui.next_redraw_deadline(), Some(now + second));
+    ui.set_widgets();
+    assert_eq!(ui.next_redraw_deadline(), None);
+}
+
+#[test]
+fn shortcuts_should_be_consumed_before_reaching_the_widget_capturing_the_keyboard() {
+    use input::shortcut::{Chord, Scope, Shortcut};
+
+    let ui = &mut UiBuilder::new([800.0, 600.0]).build();
+    let text_edit = ui.widget_id_generator().next();
+    let save = ui.register_shortcut(Chord::ctrl(Key::S), Scope::Global).unwrap();
+    let save_all = Shortcut::new(Chord::ctrl(Key::K)).then(Chord::ctrl(Key::S));
+    let save_all = ui.register_shortcut(save_all, Scope::Widget(text_edit)).unwrap();
+
+    fn set_widgets(ui: &mut Ui, text_edit: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::TextEdit::new("").w_h(200.0, 50.0).middle().set(text_edit, ui);
+    }
+
+    fn shortcuts(ui: &Ui) -> Vec<event::Ui> {
+        ui.global_input().events().ui()
+            .filter(|event| match **event {
+                event::Ui::Shortcut(_, _) | event::Ui::Press(_, _) => true,
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn press_chord(ui: &mut Ui, key: Key) {
+        ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+        ui.handle_event(Input::Press(Button::Keyboard(key)));
+        ui.handle_event(Input::Release(Button::Keyboard(key)));
+        ui.handle_event(Input::Release(Button::Keyboard(Key::LCtrl)));
+    }
+
+    set_widgets(ui, text_edit);
+
+    // The widget-scoped shortcut is inactive until the `TextEdit` captures the keyboard.
+    press_chord(ui, Key::K);
+    press_chord(ui, Key::S);
+    let ctrl = event::Button::Keyboard(Key::LCtrl);
+    let ctrl_press = event::Press { button: ctrl, modifiers: ModifierKey::NO_MODIFIER };
+    assert_eq!(shortcuts(ui), vec![
+        event::Ui::Press(None, ctrl_press),
+        event::Ui::Press(None, event::Press {
+            button: event::Button::Keyboard(Key::K),
+            modifiers: ModifierKey::CTRL,
+        }),
+        event::Ui::Press(None, ctrl_press),
+        event::Ui::Shortcut(None, save),
+    ]);
+    set_widgets(ui, text_edit);
+
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, text_edit);
+    press_chord(ui, Key::K);
+    press_chord(ui, Key::S);
+    let events = shortcuts(ui);
+    assert_eq!(events.last(), Some(&event::Ui::Shortcut(Some(text_edit), save_all)));
+
+    // The `TextEdit` only receives the shortcut, never the presses of its keys.
+    let widget_events: Vec<_> = ui.widget_input(text_edit).events()
+        .filter(|event| match *event {
+            event::Widget::Shortcut(_) => true,
+            event::Widget::Press(ref press) => press.button != ctrl,
+            _ => false,
+        })
+        .collect();
+    assert_eq!(widget_events, vec![event::Widget::Shortcut(save_all)]);
+}
+
+#[test]
+fn shortcuts_scoped_to_the_widget_capturing_the_keyboard_should_take_precedence() {
+    use input::shortcut::{Chord, Scope};
+
+    let ui = &mut UiBuilder::new([800.0, 600.0]).build();
+    let text_edit = ui.widget_id_generator().next();
+    let global = ui.register_shortcut(Chord::ctrl(Key::S), Scope::Global).unwrap();
+    let scoped = ui.register_shortcut(Chord::ctrl(Key::S), Scope::Widget(text_edit)).unwrap();
+
+    fn set_widgets(ui: &mut Ui, text_edit: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::TextEdit::new("").w_h(200.0, 50.0).middle().set(text_edit, ui);
+    }
+
+    fn press_ctrl_s(ui: &mut Ui) -> Vec<event::Ui> {
+        ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+        ui.handle_event(Input::Press(Button::Keyboard(Key::S)));
+        ui.handle_event(Input::Release(Button::Keyboard(Key::S)));
+        ui.handle_event(Input::Release(Button::Keyboard(Key::LCtrl)));
+        ui.global_input().events().ui()
+            .filter(|event| match **event {
+                event::Ui::Shortcut(_, _) => true,
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
+
+    set_widgets(ui, text_edit);
+    assert_eq!(press_ctrl_s(ui), vec![event::Ui::Shortcut(None, global)]);
+    set_widgets(ui, text_edit);
+
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, text_edit);
+    assert_eq!(press_ctrl_s(ui), vec![event::Ui::Shortcut(Some(text_edit), scoped)]);
+}
+
+#[test]
+fn consecutive_clicks_should_produce_double_and_triple_clicks() {
+    use clock::ManualClock;
+
+    let clock = ManualClock::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let click_kind = |ui: &Ui| ui.global_input().events().ui().filter_map(|event| match *event {
+        event::Ui::DoubleClick(_, _) => Some(2),
+        event::Ui::TripleClick(_, _) => Some(3),
+        _ => None,
+    }).next();
+
+    move_mouse_to_abs_coordinates(10.0, 10.0, ui);
+    let mut kinds = vec![];
+    for _ in 0..4 {
+        left_click_mouse(ui);
+        kinds.push((ui.global_input().click_count, click_kind(ui)));
+        ui.set_widgets();
+        clock.advance(std::time::Duration::from_millis(100));
+    }
+    assert_eq!(kinds, vec![(1, None), (2, Some(2)), (3, Some(3)), (1, None)]);
+
+    // Moving the mouse begins a new sequence.
+    left_click_mouse(ui);
+    ui.set_widgets();
+    move_mouse_to_abs_coordinates(20.0, 10.0, ui);
+    left_click_mouse(ui);
+    assert_eq!((ui.global_input().click_count, click_kind(ui)), (1, None));
+}
+
+#[test]
+fn scale_factor_changes_should_be_reported_and_used_for_pixel_snapping() {
+    use position::Rect;
+
+    let ui = &mut UiBuilder::new([801.0, 600.0]).build();
+    assert_eq!(ui.scale_factor(), 1.0);
+
+    // The pixel grid is aligned to the window's top-left corner, which lies on a half point here.
+    let rect = Rect::from_corners([-10.2, -10.2], [10.2, 10.4]);
+    assert_eq!(ui.snap_rect(rect), Rect::from_corners([-10.5, -10.0], [10.5, 10.0]));
+    assert_eq!(ui.snap_length(0.2), 1.0);
+
+    ui.handle_event(Input::ScaleFactor(2.0));
+    assert_eq!(ui.scale_factor(), 2.0);
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::ScaleFactorChanged(2.0)));
+    assert_eq!(ui.snap_rect(rect), Rect::from_corners([-10.0, -10.0], [10.0, 10.5]));
+    assert_eq!(ui.snap_length(0.2), 0.5);
+    assert_eq!(ui.snap_length(1.3), 1.5);
+
+    // Only changes to the scale factor are reported.
+    ui.set_widgets();
+    ui.handle_event(Input::ScaleFactor(2.0));
+    let changed = ui.global_input().events().ui().any(|event| match *event {
+        event::Ui::ScaleFactorChanged(_) => true,
+        _ => false,
+    });
+    assert!(!changed);
+}
+
+#[test]
+fn image_map_should_select_the_variant_best_suited_to_the_scale_factor() {
+    use image;
+
+    let mut map = image::Map::new();
+    let id = map.insert("logo.png");
+    assert_eq!(map.get_for_scale(id, 2.0), Some((&"logo.png", 1.0)));
+
+    map.insert_variant(id, 2.0, "logo@2x.png");
+    map.insert_variant(id, 3.0, "logo@3x.png");
+    assert_eq!(map.get_for_scale(id, 1.0), Some((&"logo.png", 1.0)));
+    assert_eq!(map.get_for_scale(id, 1.5), Some((&"logo@2x.png", 2.0)));
+    assert_eq!(map.get_for_scale(id, 2.0), Some((&"logo@2x.png", 2.0)));
+    assert_eq!(map.get_for_scale(id, 4.0), Some((&"logo@3x.png", 3.0)));
+    assert_eq!(map.get_for_scale(id, 0.5), Some((&"logo.png", 1.0)));
+
+    map.remove(id);
+    assert_eq!(map.get_for_scale(id, 2.0), None);
+}
+
+#[test]
+fn reshown_floating_widgets_should_be_brought_to_the_front() {
+    use testing::Driver;
+
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let popup = ui.widget_id_generator().next();
+    let window = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, (true, false), move |ui, app| {
+        if app.0 {
+            widget::Rectangle::fill([100.0, 100.0]).middle().floating(true).set(popup, ui);
+        }
+        if app.1 {
+            widget::Rectangle::fill([100.0, 100.0]).middle().floating(true).set(window, ui);
+        }
+    });
+
+    // The most recently cached floating widget is on top.
+    driver.app.1 = true;
+    driver.update();
+    driver.hover(window);
+    assert_eq!(driver.ui.hovered_widget(), Some(window));
+
+    // Once closed and re-opened, the pop-up is cached anew.
+    driver.app.0 = false;
+    driver.update();
+    driver.app.0 = true;
+    driver.update();
+    driver.update();
+    assert_eq!(driver.ui.hovered_widget(), Some(popup));
+}
+
+#[test]
+fn holding_a_touch_in_place_should_request_an_update_for_the_long_press() {
+    use clock::{Clock, ManualClock};
+    use input::touch::Phase;
+    use testing::Driver;
+
+    let clock = ManualClock::new();
+    let mut ui = UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let threshold = ui.theme.long_press_threshold;
+    let button = ui.widget_id_generator().next();
+    let mut driver = Driver::new(ui, Vec::new(), move |ui, long_presses| {
+        widget::Button::new().w_h(100.0, 40.0).middle().set(button, ui);
+        for event in ui.widget_input(button).events() {
+            if let event::Widget::LongPress(long_press) = event {
+                long_presses.push(long_press.xy);
+            }
+        }
+    });
+
+    // Press and hold. The `Ui` requests an update for the moment the press becomes a long press.
+    let pressed_at = clock.now();
+    driver.touch(button, 0, Phase::Start, [10.0, 0.0]);
+    driver.update();
+    assert_eq!(driver.ui.next_redraw_deadline(), Some(pressed_at + threshold));
+    clock.advance(threshold / 2);
+    driver.update();
+    assert!(driver.app.is_empty());
+
+    // Updating at the deadline recognises the long press without any further input.
+    clock.advance(threshold / 2);
+    driver.update();
+    assert_eq!(driver.app, vec![[10.0, 0.0]]);
+    assert_eq!(driver.ui.next_redraw_deadline(), None);
+
+    // Releasing produces no further long presses.
+    driver.touch(button, 0, Phase::End, [10.0, 0.0]);
+    clock.advance(threshold);
+    driver.update();
+    assert_eq!(driver.app, vec![[10.0, 0.0]]);
+    assert_eq!(driver.ui.next_redraw_deadline(), None);
+}
@@ -0,0 +1,89 @@
+use {Positionable, Sizeable, UiBuilder, Widget};
+use event::Input;
+use input::{Button, MouseButton};
+use position::Rect;
+use testing::Driver;
+use widget;
+use widget::window::Event;
+
+
+#[test]
+fn window_should_report_geometry_when_moved_resized_and_maximized() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let window = ui.widget_id_generator().next();
+    let app = Rect::from_xy_dim([0.0, 0.0], [200.0, 200.0]);
+    let mut driver = Driver::new(ui, (app, Vec::new()), |ui, app| {
+        let events = widget::Window::new("Inspector")
+            .keep_inside_parent(true)
+            .xy(app.0.xy())
+            .wh(app.0.dim())
+            .set(window, ui);
+        for &event in &events {
+            if let Event::Geometry(rect) = event {
+                app.0 = rect;
+            }
+        }
+        app.1.extend(events);
+    });
+    driver.update();
+
+    // Drag the title bar, 4px margin and 26px tall, to move the window.
+    driver.drag(window, [0.0, 83.0], [40.0, 33.0]);
+    assert_eq!(driver.app.0, Rect::from_xy_dim([40.0, -50.0], [200.0, 200.0]));
+
+    // The window may not be moved outside of its parent.
+    driver.drag(window, [0.0, 83.0], [0.0, 483.0]);
+    assert_eq!(driver.app.0, Rect::from_xy_dim([40.0, 200.0], [200.0, 200.0]));
+
+    // Drag the bottom-right corner to resize the window, leaving the top-left corner in place.
+    driver.drag(window, [94.0, -98.0], [144.0, -128.0]);
+    assert_eq!(driver.app.0, Rect::from_corners([-60.0, 70.0], [190.0, 300.0]));
+    driver.drag(window, [119.0, -113.0], [-500.0, 200.0]);
+    assert_eq!(driver.app.0, Rect::from_corners([-60.0, 266.0], [22.0, 300.0]));
+
+    // Maximize the window to fill its parent, then restore it.
+    let restored = driver.app.0;
+    let click = |driver: &mut Driver<_, _>, rel_xy| {
+        driver.move_mouse(window, rel_xy);
+        driver.ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        driver.ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        driver.update();
+        driver.update();
+    };
+    let maximize_button = |rect: Rect| [rect.w() / 2.0 - 41.0, rect.h() / 2.0 - 17.0];
+    driver.app.1.clear();
+    click(&mut driver, maximize_button(restored));
+    let maximized = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    assert_eq!(driver.app.1, vec![Event::Geometry(maximized), Event::Maximize]);
+    driver.app.1.clear();
+    click(&mut driver, maximize_button(maximized));
+    assert_eq!(driver.app.1, vec![Event::Geometry(restored), Event::Restore]);
+}
+
+#[test]
+fn clicking_a_widget_within_a_window_should_bring_the_window_to_the_front() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let mut driver = Driver::new(ui, (), move |ui, _| {
+        let window = ui.window;
+        widget::Window::new("A").x_y(-50.0, 0.0).w_h(200.0, 200.0).parent(window).set(ids[0], ui);
+        widget::Rectangle::fill([20.0, 20.0])
+            .bottom_left_of(ids[0])
+            .parent(ids[0])
+            .set(ids[2], ui);
+        widget::Window::new("B").x_y(50.0, 0.0).w_h(200.0, 200.0).parent(window).set(ids[1], ui);
+    });
+    driver.update();
+    driver.move_mouse(ids[1], [-40.0, 0.0]);
+    driver.update();
+    assert_eq!(driver.ui.global_input().current.widget_under_mouse, Some(ids[1]));
+
+    driver.click(ids[2]);
+    driver.move_mouse(ids[1], [-40.0, 0.0]);
+    driver.update();
+    assert_eq!(driver.ui.global_input().current.widget_under_mouse, Some(ids[0]));
+}
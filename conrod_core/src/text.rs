@@ -1,6 +1,6 @@
 //! Text layout logic.
 
-use {FontSize, Scalar};
+use {Dimensions, FontSize, Scalar};
 use std;
 
 // Re-export all relevant rusttype types here.
@@ -55,6 +55,18 @@ pub fn height(num_lines: usize, font_size: FontSize, line_spacing: Scalar) -> Sc
 }
 
 
+/// Determine the dimensions of the given unwrapped `text`, i.e. the width of its widest line and
+/// the total height of all of its lines.
+pub fn dimensions(text: &str, font: &Font, font_size: FontSize, line_spacing: Scalar) -> Dimensions {
+    let w = text.lines().fold(0.0, |max_w, line| {
+        let w = line::width(line, font, font_size);
+        if w > max_w { w } else { max_w }
+    });
+    let num_lines = std::cmp::max(text.lines().count(), 1);
+    [w, height(num_lines, font_size, line_spacing)]
+}
+
+
 /// Produce an iterator yielding each line within the given `text` as a new `&str`, where the
 /// start and end indices into each line are provided by the given iterator.
 pub fn lines<I>(text: &str, ranges: I) -> Lines<I>
@@ -475,6 +487,7 @@ pub mod glyph {
 /// Logic related to the positioning of the cursor within text.
 pub mod cursor {
     use FontSize;
+    use input::key_bindings::Movement;
     use position::{Range, Rect, Scalar, Point, Align};
     use std;
 
@@ -527,57 +540,59 @@ pub mod cursor {
         pub char: usize,
     }
 
+    /// The units by which text may be selected via the mouse.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    pub enum Unit {
+        /// Selected via a double click.
+        Word,
+        /// Selected via a triple click.
+        Paragraph,
+    }
+
+    /// A selection begun by a double or triple click, which is extended by whole units while
+    /// dragging.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct UnitSelection<I> {
+        /// The unit by which the selection is extended.
+        pub unit: Unit,
+        /// The start of the initially selected unit.
+        pub start: I,
+        /// The end of the initially selected unit.
+        pub end: I,
+    }
+
 
     impl Index {
 
-        /// The cursor index of the beginning of the word (block of non-whitespace) before `self`.
+        /// The cursor index of the start of the word before `self`.
         ///
         /// If `self` is at the beginning of the line, call previous, which returns the last
         /// index position of the previous line, or None if it's the first line
         ///
-        /// If `self` points to whitespace, skip past that whitespace, then return the index of 
-        /// the start of the word that precedes the whitespace
-        ///
-        /// If `self` is in the middle or end of a word, return the index of the start of that word
+        /// Otherwise, this is the `word_start_before` `self` within its line.
         pub fn previous_word_start<I>(self, text: &str, mut line_infos: I) -> Option<Self>
             where I: Iterator<Item=super::line::Info>,
         {
             let Index { line, char } = self;
             if char > 0 {
-                line_infos.nth(line).and_then(|line_info| {
-                    let line_count = line_info.char_range().count();
-                    let mut chars_rev = (&text[line_info.byte_range()]).chars().rev();
-                    if char != line_count {
-                        chars_rev.nth(line_count - char - 1);
-                    }
-                    let mut new_char = 0;
-                    let mut hit_non_whitespace = false;
-                    for (i, char_) in chars_rev.enumerate() {
-                        // loop until word starts, then continue until the word ends
-                        if !char_.is_whitespace() { hit_non_whitespace = true; }
-                        if char_.is_whitespace() && hit_non_whitespace {
-                            new_char = char - i;
-                            break
-                        }
-                    }
-                    Some(Index { line: line, char: new_char })
+                line_infos.nth(line).map(|line_info| {
+                    let line_text = &text[line_info.byte_range()];
+                    let start = word_start_before(line_text, byte_of_char(line_text, char));
+                    Index { line: line, char: line_text[..start].chars().count() }
                 })
             } else {
                 self.previous(line_infos)
             }
         }
 
-        /// The cursor index of the end of the first word (block of non-whitespace) after `self`.
+        /// The cursor index of the end of the word after `self`.
         ///
         /// If `self` is at the end of the text, this returns `None`.
         ///
         /// If `self` is at the end of a line other than the last, this returns the first index of
         /// the next line.
         ///
-        /// If `self` points to whitespace, skip past that whitespace, then return the index of 
-        /// the end of the word after the whitespace
-        ///
-        /// If `self` is in the middle or start of a word, return the index of the end of that word
+        /// Otherwise, this is the `word_end_after` `self` within its line.
         pub fn next_word_end<I>(self, text: &str, mut line_infos: I) -> Option<Self>
             where I: Iterator<Item=super::line::Info>,
         {
@@ -586,27 +601,61 @@ pub mod cursor {
                 .and_then(|line_info| {
                     let line_count = line_info.char_range().count();
                     if char < line_count {
-                        let mut chars = (&text[line_info.byte_range()]).chars();
-                        let mut new_char = line_count;
-                        let mut hit_non_whitespace = false;
-                        if char != 0 {
-                            chars.nth(char - 1);
-                        }
-                        for (i, char_) in chars.enumerate() {
-                            // loop until word starts, then continue until the word ends
-                            if !char_.is_whitespace() { hit_non_whitespace = true; }
-                            if char_.is_whitespace() && hit_non_whitespace {
-                                new_char = char + i;
-                                break
-                            }
-                        }
-                        Some(Index { line: line, char: new_char })
+                        let line_text = &text[line_info.byte_range()];
+                        let end = word_end_after(line_text, byte_of_char(line_text, char));
+                        Some(Index { line: line, char: line_text[..end].chars().count() })
                     } else {
                         line_infos.next().map(|_| Index { line: line + 1, char: 0 })
                     }
                 })
         }
 
+        /// The range of the word (or block of whitespace or punctuation) surrounding `self`.
+        ///
+        /// This is the `word_range_at` `self` within its line.
+        ///
+        /// Returns `None` if there is no line at `self.line`.
+        pub fn word_range<I>(self, text: &str, mut line_infos: I) -> Option<(Self, Self)>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).map(|line_info| {
+                let line_count = line_info.end_char() - line_info.start_char;
+                let line_text = &text[line_info.byte_range()];
+                let line_text = &line_text[..byte_of_char(line_text, line_count)];
+                let range = word_range_at(line_text, byte_of_char(line_text, char));
+                let start = line_text[..range.start].chars().count();
+                let end = line_text[..range.end].chars().count();
+                (Index { line: line, char: start }, Index { line: line, char: end })
+            })
+        }
+
+        /// The range of the paragraph containing `self`, i.e. all lines between the surrounding
+        /// newline characters (or the start and end of the text) including those that are
+        /// wrapped.
+        ///
+        /// Returns `None` if there is no line at `self.line`.
+        pub fn paragraph_range<I>(self, line_infos: I) -> Option<(Self, Self)>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let mut start_line = 0;
+            for (i, line_info) in line_infos.enumerate() {
+                let is_wrap = match line_info.end_break {
+                    super::line::Break::Wrap { .. } => true,
+                    _ => false,
+                };
+                if i >= self.line && !is_wrap {
+                    let end_char = line_info.end_char() - line_info.start_char;
+                    let start = Index { line: start_line, char: 0 };
+                    return Some((start, Index { line: i, char: end_char }));
+                }
+                if !is_wrap {
+                    start_line = i + 1;
+                }
+            }
+            None
+        }
+
         /// The cursor index that comes before `self`.
         ///
         /// If `self` is at the beginning of the text, this returns `None`.
@@ -693,6 +742,158 @@ pub mod cursor {
     }
 
 
+    impl Unit {
+
+        /// The unit selected by a press with the given number of consecutive clicks, if any.
+        pub fn from_click_count(click_count: u32) -> Option<Self> {
+            match click_count {
+                2 => Some(Unit::Word),
+                3 => Some(Unit::Paragraph),
+                _ => None,
+            }
+        }
+
+    }
+
+    impl<I> UnitSelection<I>
+        where I: Copy + Ord,
+    {
+
+        /// The `(anchor, head)` of the selection after dragging to `idx`, where `unit_at_idx` is
+        /// the range of the unit surrounding `idx`.
+        ///
+        /// The selection always includes the initially selected unit, extending it by whole units
+        /// towards `idx`.
+        pub fn extend(&self, idx: I, unit_at_idx: (I, I)) -> (I, I) {
+            if idx < self.start {
+                (self.end, unit_at_idx.0)
+            } else {
+                (self.start, std::cmp::max(unit_at_idx.1, self.end))
+            }
+        }
+
+    }
+
+    /// Whether or not the given character belongs to a word, i.e. is alphanumeric or `_`.
+    pub fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    /// Characters are grouped as either word characters, whitespace or other punctuation when
+    /// moving or selecting by word.
+    fn char_group(c: char) -> u8 {
+        if is_word_char(c) { 0 } else if c.is_whitespace() { 1 } else { 2 }
+    }
+
+    /// The byte index of the given `char` index within `text`, or the length of `text` if the
+    /// index lies beyond its end.
+    fn byte_of_char(text: &str, char: usize) -> usize {
+        text.char_indices().nth(char).map(|(i, _)| i).unwrap_or(text.len())
+    }
+
+    /// The byte index of the start of the word before the byte index `idx` within `text`.
+    ///
+    /// Any whitespace before `idx` is skipped, after which the index moves to the start of the
+    /// block of word characters or punctuation that precedes it.
+    pub fn word_start_before(text: &str, idx: usize) -> usize {
+        let mut chars = text[..idx].char_indices().rev().skip_while(|&(_, c)| c.is_whitespace());
+        let (mut start, group) = match chars.next() {
+            Some((i, c)) => (i, char_group(c)),
+            None => return 0,
+        };
+        for (i, c) in chars {
+            if char_group(c) != group {
+                break;
+            }
+            start = i;
+        }
+        start
+    }
+
+    /// The byte index of the end of the word after the byte index `idx` within `text`.
+    ///
+    /// Any whitespace after `idx` is skipped, after which the index moves to the end of the block
+    /// of word characters or punctuation that follows it.
+    pub fn word_end_after(text: &str, idx: usize) -> usize {
+        let mut chars = text[idx..].char_indices()
+            .map(|(i, c)| (idx + i, c))
+            .skip_while(|&(_, c)| c.is_whitespace());
+        let (mut end, group) = match chars.next() {
+            Some((i, c)) => (i + c.len_utf8(), char_group(c)),
+            None => return text.len(),
+        };
+        for (i, c) in chars {
+            if char_group(c) != group {
+                break;
+            }
+            end = i + c.len_utf8();
+        }
+        end
+    }
+
+    /// The byte range of the word (or block of whitespace or punctuation) surrounding the byte
+    /// index `idx` within `text`.
+    ///
+    /// The range spans the block of characters belonging to the same group as the character
+    /// following `idx`, or the character preceding `idx` if it is at the end of the `text`.
+    pub fn word_range_at(text: &str, idx: usize) -> std::ops::Range<usize> {
+        let (at, c) = match text[idx..].chars().next() {
+            Some(c) => (idx, c),
+            None => match text.chars().next_back() {
+                Some(c) => (text.len() - c.len_utf8(), c),
+                None => return idx..idx,
+            },
+        };
+        let group = char_group(c);
+        let start = text[..at].char_indices().rev()
+            .take_while(|&(_, c)| char_group(c) == group)
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(at);
+        let end = text[at..].char_indices()
+            .take_while(|&(_, c)| char_group(c) == group)
+            .last()
+            .map(|(i, c)| at + i + c.len_utf8())
+            .unwrap_or(at);
+        start..end
+    }
+
+    /// The position reached by applying the given `movement` to the selection between `anchor`
+    /// and `head`, collapsing the selection.
+    ///
+    /// If the selection is empty, this is the position reached by moving the `head`. Otherwise,
+    /// moving by a single character or line collapses the selection to its start or end, while
+    /// all other movements begin from the start or end of the selection.
+    pub fn move_selection<I, F>(anchor: I, head: I, movement: Movement, move_index: F) -> I
+        where I: Copy + Ord,
+              F: FnOnce(I, Movement) -> I,
+    {
+        if anchor == head {
+            return move_index(head, movement);
+        }
+        let idx = match movement.is_backward() {
+            true => std::cmp::min(anchor, head),
+            false => std::cmp::max(anchor, head),
+        };
+        match movement {
+            Movement::Left | Movement::Right | Movement::Up | Movement::Down => idx,
+            _ => move_index(idx, movement),
+        }
+    }
+
+    /// The ordered range removed by deleting in the direction of the given `movement` from the
+    /// selection between `anchor` and `head`.
+    ///
+    /// If the selection is empty, this is the range between the `head` and the position reached
+    /// by moving it. Otherwise, the selection itself is removed.
+    pub fn delete_range<I, F>(anchor: I, head: I, movement: Movement, move_index: F) -> (I, I)
+        where I: Copy + Ord,
+              F: FnOnce(I, Movement) -> I,
+    {
+        let other = if anchor == head { move_index(head, movement) } else { anchor };
+        (std::cmp::min(head, other), std::cmp::max(head, other))
+    }
+
     /// Every possible cursor position within each line of text yielded by the given iterator.
     ///
     /// Yields `(xs, y_range)`, where `y_range` is the `Range` occupied by the line across the *y*
@@ -1502,3 +1703,218 @@ pub mod line {
     }
 
 }
+
+
+/// Searching text for the matches of some query and replacing them.
+///
+/// All ranges are given both in bytes, for slicing the `str`, and in `char`s, which is the unit
+/// used by the `cursor` and `glyph` modules.
+pub mod search {
+    use std;
+    use std::ops::Range;
+    #[cfg(feature = "regex")]
+    use regex;
+
+    /// Describes the text to search for.
+    #[derive(Clone, Debug)]
+    pub struct Query {
+        pattern: Pattern,
+        case_sensitive: bool,
+    }
+
+    /// The pattern matched by a **Query**.
+    #[derive(Clone, Debug)]
+    enum Pattern {
+        /// Match the given text exactly.
+        Text(String),
+        /// Match the given regular expression.
+        #[cfg(feature = "regex")]
+        Regex(regex::Regex),
+    }
+
+    /// A single match of a **Query** within some text.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct Match {
+        /// The range of bytes occupied by the match.
+        pub bytes: Range<usize>,
+        /// The range of `char`s occupied by the match.
+        pub chars: Range<usize>,
+    }
+
+    /// Describes the replacement of a range of some original text.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct Edit {
+        /// The range of `char`s within the original text that was replaced.
+        pub chars: Range<usize>,
+        /// The text that was inserted in place of the range.
+        pub replacement: String,
+    }
+
+
+    impl Query {
+
+        /// A query matching the given text exactly.
+        ///
+        /// The query is case sensitive by default.
+        pub fn text(text: &str) -> Self {
+            Query {
+                pattern: Pattern::Text(text.to_string()),
+                case_sensitive: true,
+            }
+        }
+
+        /// A query matching the given regular expression.
+        ///
+        /// The query is case sensitive by default. Returns an `Err` if the pattern is invalid.
+        #[cfg(feature = "regex")]
+        pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+            let regex = regex::Regex::new(pattern)?;
+            Ok(Query {
+                pattern: Pattern::Regex(regex),
+                case_sensitive: true,
+            })
+        }
+
+        /// Specify whether or not the query distinguishes between upper and lower case.
+        pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+            #[cfg(feature = "regex")]
+            {
+                if let Pattern::Regex(ref mut regex) = self.pattern {
+                    *regex = regex::RegexBuilder::new(regex.as_str())
+                        .case_insensitive(!case_sensitive)
+                        .build()
+                        .expect("the pattern was validated when the query was constructed");
+                }
+            }
+            self.case_sensitive = case_sensitive;
+            self
+        }
+
+        /// Whether or not the query distinguishes between upper and lower case.
+        pub fn is_case_sensitive(&self) -> bool {
+            self.case_sensitive
+        }
+
+        /// All non-overlapping, non-empty matches of the query within the given text in order.
+        pub fn find_all(&self, text: &str) -> Vec<Match> {
+            self.matches(text, None).into_iter().map(|(m, _)| m).collect()
+        }
+
+        /// Replace the match occupying the given range of `char`s with the given `replacement`.
+        ///
+        /// For regular expressions, the replacement may refer to capture groups, e.g. `$1`.
+        ///
+        /// Returns the new text along with the edit that was made, or `None` if no match
+        /// occupies exactly the given range.
+        pub fn replace_at(&self, text: &str, chars: Range<usize>, replacement: &str)
+            -> Option<(String, Edit)>
+        {
+            self.matches(text, Some(replacement))
+                .into_iter()
+                .find(|&(ref m, _)| m.chars == chars)
+                .map(|(m, replacement)| {
+                    let edit = Edit { chars: m.chars, replacement: replacement };
+                    let new_text = apply(text, std::slice::from_ref(&edit));
+                    (new_text, edit)
+                })
+        }
+
+        /// Replace every match of the query within the given text with the given `replacement`.
+        ///
+        /// For regular expressions, the replacement may refer to capture groups, e.g. `$1`.
+        ///
+        /// Returns the new text along with the edits that were made in order. The range of each
+        /// edit refers to the original text.
+        pub fn replace_all(&self, text: &str, replacement: &str) -> (String, Vec<Edit>) {
+            let edits: Vec<_> = self.matches(text, Some(replacement))
+                .into_iter()
+                .map(|(m, replacement)| Edit { chars: m.chars, replacement: replacement })
+                .collect();
+            let new_text = apply(text, &edits);
+            (new_text, edits)
+        }
+
+        /// Produce each match along with its expanded replacement, if one was given.
+        fn matches(&self, text: &str, replacement: Option<&str>) -> Vec<(Match, String)> {
+            match self.pattern {
+                Pattern::Text(ref pattern) => {
+                    let matches = find_text(text, pattern, self.case_sensitive);
+                    let replacement = replacement.unwrap_or("");
+                    matches.into_iter().map(|m| (m, replacement.to_string())).collect()
+                },
+                #[cfg(feature = "regex")]
+                Pattern::Regex(ref regex) => {
+                    let mut matches = Vec::new();
+                    let (mut byte, mut char) = (0, 0);
+                    for captures in regex.captures_iter(text) {
+                        let whole = captures.get(0).expect("the whole match is always present");
+                        if whole.start() == whole.end() {
+                            continue;
+                        }
+                        let start_char = char + text[byte..whole.start()].chars().count();
+                        let end_char = start_char + whole.as_str().chars().count();
+                        let mut expanded = String::new();
+                        if let Some(replacement) = replacement {
+                            captures.expand(replacement, &mut expanded);
+                        }
+                        let m = Match {
+                            bytes: whole.start()..whole.end(),
+                            chars: start_char..end_char,
+                        };
+                        matches.push((m, expanded));
+                        byte = whole.end();
+                        char = end_char;
+                    }
+                    matches
+                },
+            }
+        }
+
+    }
+
+    /// Find all non-overlapping occurrences of `pattern` within `text`.
+    fn find_text(text: &str, pattern: &str, case_sensitive: bool) -> Vec<Match> {
+        let eq = |a: char, b: char| if case_sensitive {
+            a == b
+        } else {
+            a == b || a.to_lowercase().eq(b.to_lowercase())
+        };
+        let pattern: Vec<char> = pattern.chars().collect();
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut matches = Vec::new();
+        if pattern.is_empty() {
+            return matches;
+        }
+        let mut i = 0;
+        while i + pattern.len() <= chars.len() {
+            let is_match = chars[i..i + pattern.len()].iter()
+                .zip(&pattern)
+                .all(|(&(_, a), &b)| eq(a, b));
+            if is_match {
+                let end = i + pattern.len();
+                let end_byte = chars.get(end).map(|&(byte, _)| byte).unwrap_or(text.len());
+                matches.push(Match { bytes: chars[i].0..end_byte, chars: i..end });
+                i = end;
+            } else {
+                i += 1;
+            }
+        }
+        matches
+    }
+
+    /// Apply the given ordered, non-overlapping edits to the given text.
+    pub fn apply(text: &str, edits: &[Edit]) -> String {
+        let mut new_text = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        let mut char_idx = 0;
+        for edit in edits {
+            new_text.extend(chars.by_ref().take(edit.chars.start - char_idx));
+            chars.by_ref().take(edit.chars.end - edit.chars.start).count();
+            new_text.push_str(&edit.replacement);
+            char_idx = edit.chars.end;
+        }
+        new_text.extend(chars);
+        new_text
+    }
+
+}
@@ -6,6 +6,7 @@ use Scalar;
 use color::{Color, BLACK, WHITE};
 use position::{Align, Direction, Padding, Position, Relative};
 use fnv;
+use input::KeyBindings;
 use std;
 use std::any::Any;
 use text;
@@ -54,6 +55,21 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The duration for which the mouse must hover over a widget before its `Tooltip` appears.
+    pub tooltip_delay: std::time::Duration,
+    /// The duration for which a touch must be held in place before a `LongPress` event is
+    /// generated.
+    pub long_press_threshold: std::time::Duration,
+    /// The default duration of the animated transitions of widgets that support them.
+    ///
+    /// A duration of zero disables these transitions.
+    pub transition_duration: std::time::Duration,
+    /// The duration for which the text cursor of a `TextEdit` is alternately shown and hidden.
+    ///
+    /// A duration of zero disables blinking.
+    pub caret_blink_interval: std::time::Duration,
+    /// The key bindings used by text editing widgets such as the `TextEdit`.
+    pub key_bindings: KeyBindings,
 }
 
 /// The defaults for a specific widget.
@@ -105,6 +121,11 @@ impl Theme {
             widget_styling: fnv::FnvHashMap::default(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            tooltip_delay: std::time::Duration::from_millis(600),
+            long_press_threshold: std::time::Duration::from_millis(500),
+            transition_duration: std::time::Duration::from_secs(0),
+            caret_blink_interval: std::time::Duration::from_millis(530),
+            key_bindings: KeyBindings::default(),
         }
     }
 
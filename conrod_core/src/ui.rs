@@ -1,8 +1,12 @@
+use animation::{self, Animatable};
+use clock::{self, Clock};
 use color::Color;
+use drag_drop;
 use event;
-use graph::{self, Graph};
+use graph::{self, Graph, Walker};
 use input;
-use position::{self, Align, Direction, Dimensions, Padding, Point, Position, Range, Rect, Scalar};
+use position::{self, Align, Dimension, Direction, Dimensions, Padding, Point, Position, Range, Rect,
+               Scalar};
 use render;
 use std;
 use std::sync::atomic::{self, AtomicUsize};
@@ -34,7 +38,16 @@ pub struct UiBuilder {
     ///
     /// If this field is `None` when `build` is called, these collections will be initialised with
     /// no pre-reserved size and will instead grow organically as needed.
-    pub maybe_widgets_capacity: Option<usize>
+    pub maybe_widgets_capacity: Option<usize>,
+    /// The source of time used for all timing related behaviour within the `Ui`.
+    ///
+    /// If this field is `None` when `build` is called, the `clock::SystemClock` will be used.
+    pub maybe_clock: Option<Box<Clock>>,
+    /// The number of physical pixels per logical point on the display on which the window
+    /// initially appears.
+    ///
+    /// If this field is `None` when `build` is called, a scale factor of `1.0` will be used.
+    pub maybe_scale_factor: Option<Scalar>,
 }
 
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
@@ -77,15 +90,40 @@ pub struct Ui {
     /// We use this to compare against the newly generated `updated_widgets` to see whether or not
     /// we require re-drawing.
     prev_updated_widgets: fnv::FnvHashSet<widget::Id>,
-    /// Scroll events that have been emitted during a call to `Ui::set_widgets`. These are usually
-    /// emitted by some widget like the `Scrollbar`.
+    /// Events that have been emitted during a call to `Ui::set_widgets`. These are usually
+    /// `Scroll` events emitted by some widget like the `Scrollbar`, or drag-and-drop events.
     ///
     /// These events will be drained and pushed onto the end of the `global_input` event buffer at
     /// the end of the `Ui::set_widgets` method. This ensures that the events are received by the
     /// target widgets during the next call to `Ui::set_widgets`.
-    pending_scroll_events: Vec<event::Ui>,
+    pending_events: Vec<event::Ui>,
     /// Mouse cursor
     mouse_cursor: cursor::MouseCursor,
+    /// The drag-and-drop interaction that is currently in progress, if any.
+    drag_drop: Option<drag_drop::DragDrop>,
+    /// The source of time used for all timing related behaviour.
+    clock: Box<Clock>,
+    /// The moment at which the current (or most recent) call to `set_widgets` began.
+    frame_time: instant::Instant,
+    /// The duration between the beginning of the previous and current calls to `set_widgets`.
+    frame_delta: std::time::Duration,
+    /// Records all received input while a recording is in progress.
+    maybe_recorder: Option<input::record::Recorder>,
+    /// The animations of all animated widget properties.
+    animations: animation::Animations,
+    /// The earliest moment at which some widget has requested that the `Ui` be updated and redrawn.
+    maybe_redraw_deadline: Option<instant::Instant>,
+    /// All registered keyboard shortcuts.
+    shortcuts: input::shortcut::Registry,
+    /// Keys whose presses were consumed by a shortcut and that have not yet been released.
+    shortcut_keys: Vec<input::Key>,
+    /// Constraints between the anchors of widgets that are positioned or sized by constraint.
+    constraints: position::constraint::Layout,
+    /// The open modal layers from bottom to top, each along with the widget that was capturing
+    /// the keyboard when the layer was opened.
+    modals: Vec<(widget::Id, Option<widget::Id>)>,
+    /// The number of physical pixels per logical point on the window's current display.
+    scale_factor: Scalar,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -127,7 +165,9 @@ impl UiBuilder {
         UiBuilder {
             window_dimensions: window_dimensions,
             maybe_theme: None,
-            maybe_widgets_capacity: None
+            maybe_widgets_capacity: None,
+            maybe_clock: None,
+            maybe_scale_factor: None,
         }
     }
 
@@ -157,6 +197,28 @@ impl UiBuilder {
         self
     }
 
+    /// The source of time used for all timing related behaviour within the `Ui`.
+    ///
+    /// By default, the `clock::SystemClock` is used. A `clock::ManualClock` may be given in order
+    /// to drive the `Ui` with virtual time.
+    pub fn clock<C>(mut self, clock: C) -> Self
+        where C: Clock + 'static,
+    {
+        self.maybe_clock = Some(Box::new(clock));
+        self
+    }
+
+    /// The number of physical pixels per logical point on the display on which the window
+    /// initially appears.
+    ///
+    /// If this field is `None` when `build` is called, a scale factor of `1.0` will be used. Later
+    /// changes are delivered via `Input::ScaleFactor`. Renderers use the same factor to choose
+    /// the variant of each image within the `image::Map` (see `image::Map::get_for_scale`).
+    pub fn scale_factor(mut self, scale_factor: Scalar) -> Self {
+        self.maybe_scale_factor = Some(scale_factor);
+        self
+    }
+
     /// Build **Ui** from the given builder
     pub fn build(self) -> Ui {
         Ui::new(self)
@@ -173,6 +235,8 @@ impl Ui {
             window_dimensions,
             maybe_widgets_capacity,
             maybe_theme,
+            maybe_clock,
+            maybe_scale_factor,
         } = builder;
 
         let (mut widget_graph, depth_order, updated_widgets) =
@@ -187,6 +251,8 @@ impl Ui {
 
         let window = widget_graph.add_placeholder();
         let prev_updated_widgets = updated_widgets.clone();
+        let clock = maybe_clock.unwrap_or_else(|| Box::new(clock::SystemClock));
+        let frame_time = clock.now();
         Ui {
             widget_graph: widget_graph,
             theme: maybe_theme.unwrap_or_else(|| Theme::default()),
@@ -194,6 +260,7 @@ impl Ui {
             window: window,
             win_w: window_dimensions[0],
             win_h: window_dimensions[1],
+            scale_factor: maybe_scale_factor.unwrap_or(1.0),
             maybe_prev_widget_id: None,
             maybe_current_parent_id: None,
             num_redraw_frames: SAFE_REDRAW_COUNT,
@@ -203,8 +270,19 @@ impl Ui {
             updated_widgets: updated_widgets,
             prev_updated_widgets: prev_updated_widgets,
             global_input: input::Global::new(),
-            pending_scroll_events: Vec::new(),
+            pending_events: Vec::new(),
             mouse_cursor: cursor::MouseCursor::Arrow,
+            drag_drop: None,
+            clock: clock,
+            frame_time: frame_time,
+            frame_delta: std::time::Duration::from_secs(0),
+            maybe_recorder: None,
+            animations: animation::Animations::new(),
+            maybe_redraw_deadline: None,
+            shortcuts: input::shortcut::Registry::new(),
+            shortcut_keys: Vec::new(),
+            constraints: position::constraint::Layout::new(),
+            modals: Vec::new(),
         }
     }
 
@@ -328,14 +406,32 @@ impl Ui {
     /// remains "pinned" and will continue to capture the mouse until it is
     /// released.
     ///
+    /// If the `widget_under_mouse` has changed, a `MouseLeave` event is emitted for the previous
+    /// widget and a `MouseEnter` event is emitted for the new one.
+    ///
     /// Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
     fn track_widget_under_mouse_and_update_capturing(&mut self) {
+        let prev_widget_under_mouse = self.global_input.current.widget_under_mouse;
         self.global_input.current.widget_under_mouse =
-            graph::algo::pick_widgets(&self.depth_order.indices,
-                                      self.global_input.current.mouse.xy)
-                                      .next(&self.widget_graph,
-                                            &self.depth_order.indices,
-                                            &self.theme);
+            self.pick_widget(self.global_input.current.mouse.xy);
+
+        // Check to see whether the mouse has left one widget and entered another.
+        let widget_under_mouse = self.global_input.current.widget_under_mouse;
+        if widget_under_mouse != prev_widget_under_mouse {
+            if let Some(idx) = prev_widget_under_mouse {
+                let event = event::Ui::MouseLeave(idx).into();
+                self.global_input.push_event(event);
+            }
+            if let Some(idx) = widget_under_mouse {
+                let event = event::Ui::MouseEnter(idx).into();
+                self.global_input.push_event(event);
+            }
+            self.global_input.current.widget_under_mouse_since =
+                widget_under_mouse.map(|_| self.clock.now());
+        }
+
+        // If a payload is being dragged, the widget under the mouse is the drop target.
+        self.track_drag_target();
 
         // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
         // under mouse.
@@ -364,6 +460,282 @@ impl Ui {
         }
     }
 
+    /// If a payload is being dragged, update the drop target to the widget under the mouse,
+    /// emitting `DragLeave` and `DragEnter` events if it has changed.
+    fn track_drag_target(&mut self) {
+        let widget_under_mouse = self.global_input.current.widget_under_mouse;
+        let drag_drop = match self.drag_drop {
+            Some(ref mut drag_drop) if drag_drop.is_dragging() => drag_drop,
+            _ => return,
+        };
+        drag_drop.xy = self.global_input.current.mouse.xy;
+        if drag_drop.target == widget_under_mouse {
+            return;
+        }
+        if let Some(idx) = drag_drop.target {
+            self.global_input.push_event(event::Ui::DragLeave(idx).into());
+        }
+        if let Some(idx) = widget_under_mouse {
+            self.global_input.push_event(event::Ui::DragEnter(idx).into());
+        }
+        drag_drop.target = widget_under_mouse;
+        drag_drop.accepted_by = None;
+    }
+
+    /// Complete the drag that is in progress (if any) as the left mouse button is released.
+    ///
+    /// If the target has accepted the drag, the payload is dropped onto it. Otherwise the drag is
+    /// cancelled.
+    fn release_drag(&mut self) {
+        let (source, target, xy) = match self.drag_drop {
+            Some(ref drag_drop) if drag_drop.is_accepted() && drag_drop.is_dragging() =>
+                (drag_drop.source, drag_drop.target, drag_drop.xy),
+            Some(ref drag_drop) if drag_drop.is_dragging() => return self.cancel_drag(),
+            _ => return,
+        };
+        if let Some(idx) = target {
+            self.global_input.push_event(event::Ui::Drop(idx, xy).into());
+            self.global_input.push_event(event::Ui::DragEnd(source, Some(idx)).into());
+            if let Some(ref mut drag_drop) = self.drag_drop {
+                drag_drop.phase = drag_drop::Phase::Dropped(idx);
+            }
+        }
+    }
+
+    /// Cancel the drag that is in progress (if any), emitting `DragLeave` for the current target
+    /// and `DragEnd` for the source.
+    pub fn cancel_drag(&mut self) {
+        let is_dragging = self.drag_drop.as_ref().map_or(false, |d| d.is_dragging());
+        if !is_dragging {
+            return;
+        }
+        if let Some(drag_drop) = self.drag_drop.take() {
+            if let Some(idx) = drag_drop.target {
+                self.global_input.push_event(event::Ui::DragLeave(idx).into());
+            }
+            let event = event::Ui::DragEnd(drag_drop.source, None);
+            self.global_input.push_event(event.into());
+        }
+    }
+
+    /// The drag-and-drop interaction that is currently in progress, if any.
+    ///
+    /// Drop targets may use this to inspect the payload when deciding whether or not to accept a
+    /// drag via `UiCell::accept_drag`.
+    pub fn drag_drop(&self) -> Option<&drag_drop::DragDrop> {
+        self.drag_drop.as_ref()
+    }
+
+    /// Push the event for the given recognised gesture, delivered to the widget under the
+    /// gesture's origin.
+    fn push_gesture_event(&mut self, recognized: input::gesture::Recognized) {
+        use input::gesture::Gesture;
+        let widget = self.pick_widget(recognized.origin);
+        let event = match recognized.gesture {
+            Gesture::Pinch(pinch) => event::Ui::Pinch(widget, pinch),
+            Gesture::Rotate(rotate) => event::Ui::Rotate(widget, rotate),
+            Gesture::LongPress(long_press) => event::Ui::LongPress(widget, long_press),
+            Gesture::Swipe(swipe) => event::Ui::Swipe(widget, swipe),
+        };
+        self.global_input.push_event(event.into());
+    }
+
+    /// Check whether or not the single touch in progress (if any) has been held long enough to
+    /// become a `LongPress`.
+    fn poll_long_press(&mut self) {
+        let now = self.clock.now();
+        let threshold = self.theme.long_press_threshold;
+        if let Some(recognized) = self.global_input.gestures.long_press(now, threshold) {
+            self.push_gesture_event(recognized);
+        }
+    }
+
+    /// The source of time used for all timing related behaviour within the `Ui`.
+    pub fn clock(&self) -> &Clock {
+        &*self.clock
+    }
+
+    /// The moment at which the current (or most recent) call to `Ui::set_widgets` began.
+    ///
+    /// This remains constant for the duration of each `set_widgets` stage, providing all widgets
+    /// with a shared notion of the current frame's time for animation.
+    pub fn time(&self) -> instant::Instant {
+        self.frame_time
+    }
+
+    /// The duration between the beginning of the previous call to `Ui::set_widgets` and the
+    /// beginning of the current (or most recent) call.
+    ///
+    /// During the first call to `set_widgets` this is the duration since the `Ui` was built.
+    pub fn frame_delta(&self) -> std::time::Duration {
+        self.frame_delta
+    }
+
+    /// Whether or not any widget property animated via `UiCell::animate` is still transitioning.
+    ///
+    /// Event loops that wait for input before updating should continue to update while this is
+    /// `true`.
+    pub fn is_animating(&self) -> bool {
+        self.animations.is_running(self.clock.now())
+    }
+
+    /// The moment by which the `Ui` should next be updated via `Ui::set_widgets` and redrawn,
+    /// even if no new input has been received.
+    ///
+    /// This is the earliest moment requested via `UiCell::request_redraw_at` or
+    /// `UiCell::request_redraw_after` during the last call to `set_widgets`, or the moment at which
+    /// a touch held in place would become a long press, or the current time while
+    /// `Ui::is_animating`. Returns `None` if no future update is required.
+    ///
+    /// Event loops that block while waiting for input may use this as the timeout of their wait.
+    pub fn next_redraw_deadline(&self) -> Option<instant::Instant> {
+        if self.is_animating() {
+            return Some(self.clock.now());
+        }
+        self.maybe_redraw_deadline
+    }
+
+    /// Begin recording all input received via `Ui::handle_event`, along with each call to
+    /// `Ui::set_widgets`.
+    ///
+    /// If a recording is already in progress, it is discarded and a new one begins.
+    ///
+    /// See the `input::record` module for details.
+    pub fn start_recording(&mut self) {
+        let recorder = input::record::Recorder::new(self.clock.now(), [self.win_w, self.win_h]);
+        self.maybe_recorder = Some(recorder);
+    }
+
+    /// Whether or not a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.maybe_recorder.is_some()
+    }
+
+    /// Finish the recording that is in progress, if any.
+    pub fn stop_recording(&mut self) -> Option<input::record::Recording> {
+        self.maybe_recorder.take().map(|recorder| recorder.finish())
+    }
+
+    /// Register a keyboard shortcut that is active within the given `scope`.
+    ///
+    /// Once the keys of the shortcut are pressed, an `event::Ui::Shortcut` is produced with the
+    /// returned `Id`. The key presses themselves are consumed, so that they never reach the widget
+    /// capturing the keyboard.
+    ///
+    /// Returns a `Conflict` if the shortcut is the same as, or begins with, another shortcut
+    /// within the same scope (or vice versa), in which case it is not registered. Shortcuts within
+    /// different scopes may overlap, in which case the shortcut scoped to the widget nearest to
+    /// the widget capturing the keyboard takes precedence over those further out. While a modal
+    /// layer is open, only the shortcuts scoped to widgets within the layer are active.
+    ///
+    /// See the `input::shortcut` module for details.
+    pub fn register_shortcut<S>(&mut self, shortcut: S, scope: input::shortcut::Scope)
+        -> Result<input::shortcut::Id, input::shortcut::Conflict>
+        where S: Into<input::shortcut::Shortcut>,
+    {
+        self.shortcuts.register(shortcut, scope)
+    }
+
+    /// Remove the keyboard shortcut with the given `Id`, returning it if it was registered.
+    pub fn unregister_shortcut(&mut self, id: input::shortcut::Id)
+        -> Option<input::shortcut::Shortcut>
+    {
+        self.shortcuts.unregister(id)
+    }
+
+    /// All registered keyboard shortcuts.
+    ///
+    /// This is useful for displaying the shortcut of an action, e.g. within a menu.
+    pub fn shortcuts(&self) -> &input::shortcut::Registry {
+        &self.shortcuts
+    }
+
+    /// The constraint layout used to resolve `Position::Constrained` and `Dimension::Constrained`.
+    ///
+    /// See the `position::constraint` module for details.
+    pub fn constraints(&self) -> &position::constraint::Layout {
+        &self.constraints
+    }
+
+    /// A mutable reference to the constraint layout, used to add and remove constraints.
+    pub fn constraints_mut(&mut self) -> &mut position::constraint::Layout {
+        &mut self.constraints
+    }
+
+    /// Check the given input against the registered keyboard shortcuts.
+    ///
+    /// Returns `true` if the input was consumed by a shortcut, in which case it should not be
+    /// interpreted any further.
+    fn consume_shortcut_input(&mut self, input: &event::Input) -> bool {
+        use event::Input;
+        use input::Button;
+        use input::shortcut::{Chord, Match, Scope};
+
+        match *input {
+            // Modifier keys are never consumed so that the modifier state remains up to date.
+            Input::Press(Button::Keyboard(key)) if filter_modifier(key).is_none() => {
+                let Ui { ref mut shortcuts, ref widget_graph, ref global_input, ref modals, .. } =
+                    *self;
+                let capturing = global_input.current.widget_capturing_keyboard;
+                let chord = Chord::new(global_input.current.modifiers, key);
+                let maybe_modal = modals.last().map(|&(id, _)| id);
+                // Widget scopes are only active while the widget or one of its descendants is
+                // capturing the keyboard, in which case the innermost scope takes precedence.
+                // While a modal layer is open, only the scopes of widgets within the layer are
+                // active.
+                let scope_distance = |scope| match scope {
+                    Scope::Global => match maybe_modal {
+                        Some(_) => None,
+                        None => Some(std::usize::MAX),
+                    },
+                    Scope::Widget(id) if maybe_modal.map_or(false, |modal| {
+                        id != modal && !widget_graph.does_recursive_depth_edge_exist(modal, id)
+                    }) => None,
+                    Scope::Widget(id) => capturing.and_then(|capturing| {
+                        if capturing == id {
+                            return Some(0);
+                        }
+                        let mut parents = widget_graph.depth_parent_recursion(capturing);
+                        let mut distance = 1;
+                        while let Some(parent) = parents.next_node(widget_graph) {
+                            if parent == id {
+                                return Some(distance);
+                            }
+                            distance += 1;
+                        }
+                        None
+                    }),
+                };
+                let event = match shortcuts.chord(chord, scope_distance) {
+                    Match::Complete(id) => Some(event::Ui::Shortcut(capturing, id)),
+                    Match::Partial => None,
+                    Match::None => return false,
+                };
+                if let Some(event) = event {
+                    self.global_input.push_event(event.into());
+                }
+                self.shortcut_keys.push(key);
+                true
+            },
+
+            // Consume the release of any key whose press was consumed.
+            Input::Release(Button::Keyboard(key)) => {
+                match self.shortcut_keys.iter().position(|&k| k == key) {
+                    Some(idx) => {
+                        self.shortcut_keys.remove(idx);
+                        true
+                    },
+                    None => false,
+                }
+            },
+
+            // Text is produced by the press of a consumed key.
+            Input::Text(_) => !self.shortcut_keys.is_empty(),
+
+            _ => false,
+        }
+    }
+
     /// Handle raw window events and update the `Ui` state accordingly.
     ///
     /// This occurs within several stages:
@@ -382,27 +754,34 @@ impl Ui {
     /// `RawEvent` that can be used by the `Ui`.
     pub fn handle_event(&mut self, event: event::Input) {
         use event::Input;
-        use input::{Button, Key, ModifierKey, Motion};
+        use input::{Button, Key, Motion};
         use input::state::mouse::Button as MouseButton;
 
-        // A function for filtering `ModifierKey`s.
-        fn filter_modifier(key: Key) -> Option<ModifierKey> {
-            match key {
-                Key::LCtrl | Key::RCtrl => Some(ModifierKey::CTRL),
-                Key::LShift | Key::RShift => Some(ModifierKey::SHIFT),
-                Key::LAlt | Key::RAlt => Some(ModifierKey::ALT),
-                Key::LGui | Key::RGui => Some(ModifierKey::GUI),
-                _ => None
-            }
-        }
-
         // Here we handle all user input given to conrod.
         //
         // Not only do we store the `Input` event as an `Event::Raw`, we also use them to
         // interpret higher level events such as `Click` or `Drag`.
         //
         // Finally, we also ensure that the `current_state` is up-to-date.
+        if let Some(ref mut recorder) = self.maybe_recorder {
+            recorder.input(self.clock.now(), event.clone());
+        }
         self.global_input.push_event(event.clone().into());
+
+        // Keys that make up a registered shortcut take precedence over all widgets.
+        if self.consume_shortcut_input(&event) {
+            return;
+        }
+
+        // Interpret touches as multi-touch gestures.
+        if let Input::Touch(touch) = event {
+            self.poll_long_press();
+            let now = self.clock.now();
+            for recognized in self.global_input.gestures.touch(touch, now) {
+                self.push_gesture_event(recognized);
+            }
+        }
+
         match event {
 
             // Some button was pressed, whether keyboard, mouse or some other device.
@@ -441,6 +820,25 @@ impl Ui {
                         }
                     }
 
+                    // Count the consecutive clicks of the same button at the same position,
+                    // each within the `double_click_threshold` of the last.
+                    let now = self.clock.now();
+                    let is_consecutive = self.global_input.last_click
+                        .map_or(false, |(last_time, last_click)| {
+                            // TODO: Work out how to get this threshold from the user's system
+                            // preferences.
+                            let threshold = self.theme.double_click_threshold;
+                            last_click.button == mouse_button
+                                && last_click.xy == mouse_xy
+                                && now.duration_since(last_time) < threshold
+                        });
+
+                    // A triple click completes a sequence, so that the next press begins anew.
+                    self.global_input.click_count = match is_consecutive {
+                        true => self.global_input.click_count % 3 + 1,
+                        false => 1,
+                    };
+
                     // Keep track of pressed buttons in the current input::State.
                     let xy = self.global_input.current.mouse.xy;
                     let widget = self.global_input.current.widget_under_mouse;
@@ -466,10 +864,9 @@ impl Ui {
                     // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
                     // uncapture a widget.
                     if let Key::Escape = key {
-                        // TODO:
-                        // 1. Cancel `Drag` if currently under way.
-                        // 2. If mouse is captured due to pinning widget with left mouse button,
-                        //    cancel capturing.
+                        self.cancel_drag();
+                        // TODO: If mouse is captured due to pinning widget with left mouse
+                        // button, cancel capturing.
                     }
                 },
 
@@ -495,9 +892,9 @@ impl Ui {
                     let release_event = event::Ui::Release(widget, release).into();
                     self.global_input.push_event(release_event);
 
-                    // Check for `Click` and `DoubleClick` events.
+                    // Check for `Click`, `DoubleClick` and `TripleClick` events.
                     let down = self.global_input.current.mouse.buttons[mouse_button].if_down();
-                    if let Some((_, widget)) = down {
+                    if let Some((press_xy, widget)) = down {
 
                         // The widget that's being clicked.
                         let clicked_widget = self.global_input.current.widget_under_mouse
@@ -514,56 +911,46 @@ impl Ui {
                         let click_event = event::Ui::Click(clicked_widget, click).into();
                         self.global_input.push_event(click_event);
 
-                        let now = instant::Instant::now();
-                        let double_click = self.global_input.last_click
-                            .and_then(|(last_time, last_click)| {
-
-                                // If the button of this click is different to the button
-                                // of last click, don't create a `DoubleClick`.
-                                if click.button != last_click.button {
-                                    return None;
-                                }
-
-                                // If the mouse has moved since the last click, don't
-                                // create a `DoubleClick`.
-                                if click.xy != last_click.xy {
-                                    return None;
-                                }
-
-                                // If the duration since the last click is longer than the
-                                // double_click_threshold, don't create a `DoubleClick`.
-                                let duration = now.duration_since(last_time);
-                                // TODO: Work out how to get this threshold from the user's
-                                // system preferences.
-                                let threshold = self.theme.double_click_threshold;
-                                if duration >= threshold {
-                                    return None;
-                                }
+                        // The click only continues the sequence counted by its press if the mouse
+                        // has not moved since the press.
+                        let click_count = match click.xy == press_xy {
+                            true => self.global_input.click_count,
+                            false => 1,
+                        };
+                        self.global_input.click_count = click_count;
+                        self.global_input.last_click = Some((self.clock.now(), click));
 
-                                Some(event::DoubleClick {
+                        match click_count {
+                            2 => {
+                                let double_click = event::DoubleClick {
                                     button: click.button,
                                     xy: click.xy,
                                     modifiers: click.modifiers,
-                                })
-                            });
-
-                        if let Some(double_click) = double_click {
-                            // Reset the `last_click` to `None`, as to not register another
-                            // `DoubleClick` on the next consecutive `Click`.
-                            self.global_input.last_click = None;
-                            let double_click_event =
-                                event::Ui::DoubleClick(clicked_widget, double_click).into();
-                            self.global_input.push_event(double_click_event);
-
-                        } else {
-                            // Set the `Click` that we just stored as the `last_click`.
-                            self.global_input.last_click = Some((now, click));
+                                };
+                                let double_click_event =
+                                    event::Ui::DoubleClick(clicked_widget, double_click).into();
+                                self.global_input.push_event(double_click_event);
+                            },
+                            3 => {
+                                let triple_click = event::TripleClick {
+                                    button: click.button,
+                                    xy: click.xy,
+                                    modifiers: click.modifiers,
+                                };
+                                let triple_click_event =
+                                    event::Ui::TripleClick(clicked_widget, triple_click).into();
+                                self.global_input.push_event(triple_click_event);
+                            },
+                            _ => (),
                         }
                     }
 
                     // Uncapture widget capturing mouse if MouseButton::Left is down and
                     // widget_under_mouse != capturing widget.
                     if let MouseButton::Left = mouse_button {
+                        // Drop or cancel any payload that is being dragged.
+                        self.release_drag();
+
                         if let Some(idx) = self.global_input.current.widget_capturing_mouse {
                             if Some(idx) != self.global_input.current.widget_under_mouse {
                                 let source = input::Source::Mouse;
@@ -611,6 +998,16 @@ impl Ui {
                 self.track_widget_under_mouse_and_update_capturing();
             },
 
+            // The window moved to a display with a different scale factor.
+            Input::ScaleFactor(scale_factor) => {
+                if scale_factor != self.scale_factor {
+                    let event = event::Ui::ScaleFactorChanged(scale_factor).into();
+                    self.global_input.push_event(event);
+                    self.scale_factor = scale_factor;
+                    self.needs_redraw();
+                }
+            },
+
             // The mouse cursor was moved to a new position.
             //
             // Checks for events in the following order:
@@ -660,6 +1057,14 @@ impl Ui {
                         self.global_input.current.mouse.xy = mouse_xy;
 
                         self.track_widget_under_mouse_and_update_capturing();
+
+                        // Notify the drop target of the dragged payload's new location.
+                        let maybe_target = self.drag_drop.as_ref()
+                            .and_then(|d| if d.is_dragging() { d.target } else { None });
+                        if let Some(idx) = maybe_target {
+                            let event = event::Ui::DragOver(idx, mouse_xy).into();
+                            self.global_input.push_event(event);
+                        }
                     },
 
                     // Some scrolling occurred (e.g. mouse scroll wheel).
@@ -692,6 +1097,11 @@ impl Ui {
                                     None => continue,
                                 };
 
+                            // Widgets beneath an open modal layer may not be scrolled.
+                            if !self.is_within_modal(idx) {
+                                continue;
+                            }
+
                             fn offset_is_at_bound<A>(scroll: &widget::scroll::State<A>,
                                                      additional_offset: Scalar) -> bool
                             {
@@ -802,13 +1212,11 @@ impl Ui {
 
                 input::touch::Phase::Start => {
                     // Find the widget under the touch.
-                    let widget_under_touch =
-                        graph::algo::pick_widgets(&self.depth_order.indices, touch.xy)
-                            .next(&self.widget_graph, &self.depth_order.indices, &self.theme);
+                    let widget_under_touch = self.pick_widget(touch.xy);
 
                     // The start of the touch interaction state to be stored.
                     let start = input::state::touch::Start {
-                        time: instant::Instant::now(),
+                        time: self.clock.now(),
                         xy: touch.xy,
                         widget: widget_under_touch,
                     };
@@ -838,15 +1246,12 @@ impl Ui {
                 input::touch::Phase::Move => {
 
                     // Update the widget under the touch and return the widget capturing the touch.
+                    let widget_under_touch = self.pick_widget(touch.xy);
                     let widget = match self.global_input.current.touch.get_mut(&touch.id) {
-                        Some(touch) => {
-                            touch.widget =
-                                graph::algo::pick_widgets(&self.depth_order.indices, touch.xy)
-                                    .next(&self.widget_graph,
-                                          &self.depth_order.indices,
-                                          &self.theme);
-                            touch.xy = touch.xy;
-                            touch.start.widget
+                        Some(state) => {
+                            state.widget = widget_under_touch;
+                            state.xy = touch.xy;
+                            state.start.widget
                         },
                         None => None,
                     };
@@ -877,13 +1282,11 @@ impl Ui {
                     //
                     // If the widget at the end of the touch is the same as the widget at the start
                     // of the touch, that widget receives the `Tap`.
-                    let tapped_widget =
-                        graph::algo::pick_widgets(&self.depth_order.indices, touch.xy)
-                            .next(&self.widget_graph, &self.depth_order.indices, &self.theme)
-                            .and_then(|widget| match Some(widget) == widget_capturing {
-                                true => Some(widget),
-                                false => None,
-                            });
+                    let tapped_widget = self.pick_widget(touch.xy)
+                        .and_then(|widget| match Some(widget) == widget_capturing {
+                            true => Some(widget),
+                            false => None,
+                        });
                     let tap = event::Tap { id: touch.id, xy: touch.xy };
                     let event = event::Ui::Tap(tapped_widget, tap);
                     self.global_input.push_event(event.into());
@@ -902,6 +1305,25 @@ impl Ui {
             Input::Focus(_focused) => (),
 
             Input::Redraw => self.needs_redraw(),
+
+            // External files are delivered to whichever widget is under the mouse.
+            Input::DroppedFile(path) => {
+                let widget = self.global_input.current.widget_under_mouse;
+                let event = event::Ui::DroppedFile(widget, path);
+                self.global_input.push_event(event.into());
+            },
+
+            Input::HoveredFile(path) => {
+                let widget = self.global_input.current.widget_under_mouse;
+                let event = event::Ui::HoveredFile(widget, path);
+                self.global_input.push_event(event.into());
+            },
+
+            Input::HoveredFileCancelled => {
+                let widget = self.global_input.current.widget_under_mouse;
+                let event = event::Ui::HoveredFileCancelled(widget);
+                self.global_input.push_event(event.into());
+            },
         }
     }
 
@@ -913,6 +1335,106 @@ impl Ui {
         &self.global_input
     }
 
+    /// The top-most widget currently under the mouse cursor, if any.
+    ///
+    /// This is the widget that most recently received an `event::Widget::MouseEnter` event.
+    pub fn hovered_widget(&self) -> Option<widget::Id> {
+        self.global_input.current.widget_under_mouse
+    }
+
+    /// The duration for which the `hovered_widget` has been under the mouse cursor.
+    ///
+    /// Returns `None` if there is no widget under the mouse.
+    pub fn hover_duration(&self) -> Option<std::time::Duration> {
+        self.global_input.current.widget_under_mouse_since
+            .map(|since| self.clock.now().duration_since(since))
+    }
+
+    /// The duration for which the widget with the given `id` has been under the mouse cursor.
+    ///
+    /// Returns `None` if the widget is not the `hovered_widget`.
+    pub fn hover_duration_of(&self, id: widget::Id) -> Option<std::time::Duration> {
+        match self.hovered_widget() == Some(id) {
+            true => self.hover_duration(),
+            false => None,
+        }
+    }
+
+    /// The number of physical pixels per logical point on the window's current display.
+    ///
+    /// All positions and dimensions within the `Ui` are given in logical points. This is updated
+    /// whenever an `Input::ScaleFactor` is handled.
+    pub fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
+    /// Snap the given point to the nearest corner of a physical pixel on the window's display.
+    ///
+    /// See the `position::pixel` module for details.
+    pub fn snap_point(&self, xy: Point) -> Point {
+        position::pixel::snap_point(xy, [self.win_w, self.win_h], self.scale_factor)
+    }
+
+    /// Snap each edge of the given rectangle to the nearest physical pixel boundary on the
+    /// window's display.
+    ///
+    /// This is useful for keeping the edges of borders and lines crisp.
+    pub fn snap_rect(&self, rect: Rect) -> Rect {
+        position::pixel::snap_rect(rect, [self.win_w, self.win_h], self.scale_factor)
+    }
+
+    /// Snap the given length (e.g. the thickness of a border) to a whole number of physical
+    /// pixels, no less than one.
+    pub fn snap_length(&self, length: Scalar) -> Scalar {
+        position::pixel::snap_length(length, self.scale_factor)
+    }
+
+    /// The widget at the root of the top-most open modal layer, if any.
+    ///
+    /// While a modal layer is open, all input is routed to the widgets within its subtree.
+    pub fn modal(&self) -> Option<widget::Id> {
+        self.modals.last().map(|&(id, _)| id)
+    }
+
+    /// Whether or not the widget with the given `id` may receive input while the top-most modal
+    /// layer (if any) is open.
+    fn is_within_modal(&self, id: widget::Id) -> bool {
+        match self.modal() {
+            Some(modal) => id == modal || self.widget_graph.does_recursive_depth_edge_exist(modal, id),
+            None => true,
+        }
+    }
+
+    /// The top-most widget under the given `xy`.
+    ///
+    /// While a modal layer is open, any point that does not fall over one of the layer's widgets
+    /// is considered to be over the root of the layer.
+    fn pick_widget(&self, xy: Point) -> Option<widget::Id> {
+        let picked = graph::algo::pick_widgets(&self.depth_order.indices, xy)
+            .next(&self.widget_graph, &self.depth_order.indices, &self.theme);
+        match picked {
+            Some(id) if self.is_within_modal(id) => Some(id),
+            _ => self.modal().or(picked),
+        }
+    }
+
+    /// Move the keyboard capture to the given widget, emitting the necessary capture events once
+    /// the current update has finished.
+    fn recapture_keyboard(&mut self, maybe_id: Option<widget::Id>) {
+        let source = input::Source::Keyboard;
+        let prev = self.global_input.current.widget_capturing_keyboard;
+        if prev == maybe_id {
+            return;
+        }
+        if let Some(idx) = prev {
+            self.pending_events.push(event::Ui::WidgetUncapturesInputSource(idx, source));
+        }
+        if let Some(idx) = maybe_id {
+            self.pending_events.push(event::Ui::WidgetCapturesInputSource(idx, source));
+        }
+        self.global_input.current.widget_capturing_keyboard = maybe_id;
+    }
+
     /// Set keyboard capturing widget
     pub fn keyboard_capture(&mut self, idx: widget::Id) {
         let source = input::Source::Keyboard;
@@ -961,6 +1483,9 @@ impl Ui {
         {
             let (relative, maybe_id) = match position {
                 Position::Absolute(abs) => return abs,
+                Position::Constrained(id) => return ui.constraints.rect_of(id)
+                    .map(|rect| range_from_rect(rect).start + dim / 2.0)
+                    .unwrap_or(0.0),
                 Position::Relative(relative, maybe_id) => (relative, maybe_id),
             };
 
@@ -1051,6 +1576,30 @@ impl Ui {
         self.maybe_prev_widget_id = None;
         self.maybe_current_parent_id = None;
 
+        // Advance the frame time.
+        let now = self.clock.now();
+        self.frame_delta = now.duration_since(self.frame_time);
+        self.frame_time = now;
+
+        if let Some(ref mut recorder) = self.maybe_recorder {
+            recorder.set_widgets(now);
+        }
+
+        // No input is received while a touch is held in place, so check for long presses here.
+        self.poll_long_press();
+
+        // Redraw if a requested deadline has been reached. Widgets request new deadlines during
+        // each update, so any remaining deadline is cleared.
+        if let Some(deadline) = self.maybe_redraw_deadline.take() {
+            if deadline <= now {
+                self.needs_redraw();
+            }
+        }
+
+        // Ensure that we are updated once a touch held in place would become a long press.
+        let threshold = self.theme.long_press_threshold;
+        self.maybe_redraw_deadline = self.global_input.gestures.long_press_deadline(threshold);
+
         // Move the previous `updated_widgets` to `prev_updated_widgets` and clear
         // `updated_widgets` so that we're ready to store the newly updated widgets.
         {
@@ -1079,7 +1628,15 @@ impl Ui {
 
         ui_cell.ui.maybe_current_parent_id = Some(ui_cell.window.into());
 
-        ui_cell.set_mouse_cursor(cursor::MouseCursor::Arrow);
+        // While a payload is being dragged, indicate whether or not the target accepts it.
+        let mouse_cursor = match ui_cell.drag_drop {
+            Some(ref drag_drop) if drag_drop.is_dragging() => match drag_drop.is_accepted() {
+                true => cursor::MouseCursor::Grabbing,
+                false => cursor::MouseCursor::NotAllowed,
+            },
+            _ => cursor::MouseCursor::Arrow,
+        };
+        ui_cell.set_mouse_cursor(mouse_cursor);
 
         ui_cell
     }
@@ -1217,6 +1774,13 @@ impl<'a> UiCell<'a> {
         self.ui.widget_id_generator()
     }
 
+    /// A mutable reference to the `Ui`'s constraint layout, used to add and remove constraints.
+    ///
+    /// See the `position::constraint` module for details.
+    pub fn constraints_mut(&mut self) -> &mut position::constraint::Layout {
+        self.ui.constraints_mut()
+    }
+
     /// The **Rect** that bounds the kids of the widget with the given index.
     ///
     /// Returns `None` if the widget has no children or if there's is no widget for the given index.
@@ -1237,10 +1801,141 @@ impl<'a> UiCell<'a> {
                 y: y,
                 modifiers: self.ui.global_input.current.modifiers,
             });
-            self.ui.pending_scroll_events.push(event);
+            self.ui.pending_events.push(event);
         }
     }
 
+    /// Open a modal layer rooted at the widget with the given `id`.
+    ///
+    /// While the layer is open, input is only routed to the widget and its depth descendants. Any
+    /// mouse or touch input outside of these is delivered to the widget itself. The keyboard is
+    /// captured by the widget when the layer is first opened.
+    ///
+    /// The widget should call this during every update for which it remains open. The layer is
+    /// closed at the end of the first update in which the widget is not set, at which point the
+    /// keyboard is returned to the widget that was capturing it before the layer was opened.
+    pub fn open_modal(&mut self, id: widget::Id) {
+        if self.ui.modals.iter().any(|&(modal, _)| modal == id) {
+            return;
+        }
+        let prev = self.ui.global_input.current.widget_capturing_keyboard;
+        self.ui.modals.push((id, prev));
+        self.ui.recapture_keyboard(Some(id));
+    }
+
+    /// Move the keyboard capture to the widget with the given `id`.
+    ///
+    /// This is useful for focusing a widget that accepts text input, e.g. when a dialog opens.
+    pub fn focus_keyboard(&mut self, id: widget::Id) {
+        self.ui.recapture_keyboard(Some(id));
+    }
+
+    /// Begin dragging the given `payload` from the `source` widget.
+    ///
+    /// This is normally called by the source widget upon receiving an `event::Drag` while the
+    /// left mouse button is down. The widget under the cursor becomes the drop target and receives
+    /// drag-and-drop events, beginning during the next call to `Ui::set_widgets`.
+    ///
+    /// Returns `false` if the drag could not be started, either because another drag is already
+    /// in progress or because the left mouse button is not down.
+    pub fn start_drag(&mut self,
+                      source: widget::Id,
+                      payload: Box<std::any::Any + Send>,
+                      preview: drag_drop::Preview) -> bool
+    {
+        if self.ui.drag_drop.is_some() || self.ui.global_input.current.mouse.buttons.left().is_up() {
+            return false;
+        }
+        let xy = self.ui.global_input.current.mouse.xy;
+        let target = self.ui.global_input.current.widget_under_mouse;
+        self.ui.drag_drop = Some(drag_drop::DragDrop::new(source, payload, preview, xy, target));
+        self.ui.pending_events.push(event::Ui::DragStart(source));
+        if let Some(idx) = target {
+            self.ui.pending_events.push(event::Ui::DragEnter(idx));
+        }
+        self.set_mouse_cursor(cursor::MouseCursor::NotAllowed);
+        true
+    }
+
+    /// Indicate whether or not the `target` widget accepts the payload that is currently being
+    /// dragged over it.
+    ///
+    /// A target that does not accept the drag will not receive a `Drop` event if the payload is
+    /// released over it. Acceptance is reset whenever the drag moves onto a different target.
+    pub fn accept_drag(&mut self, target: widget::Id, accept: bool) {
+        let mouse_cursor = match self.ui.drag_drop {
+            Some(ref mut drag_drop) if drag_drop.is_dragging()
+                                    && drag_drop.target == Some(target) => {
+                drag_drop.accepted_by = if accept { Some(target) } else { None };
+                match accept {
+                    true => cursor::MouseCursor::Grabbing,
+                    false => cursor::MouseCursor::NotAllowed,
+                }
+            },
+            _ => return,
+        };
+        self.set_mouse_cursor(mouse_cursor);
+    }
+
+    /// Take the payload that was dropped onto the `target` widget.
+    ///
+    /// Returns `None` if no payload was dropped onto the `target` or if the payload has already
+    /// been taken.
+    pub fn take_drag_payload(&mut self, target: widget::Id) -> Option<Box<std::any::Any + Send>> {
+        match self.ui.drag_drop {
+            Some(ref mut drag_drop) if drag_drop.phase == drag_drop::Phase::Dropped(target) =>
+                drag_drop.payload.take(),
+            _ => None,
+        }
+    }
+
+    /// Produce the value of the given widget's `property` for the current frame as it animates
+    /// towards the `target`.
+    ///
+    /// Whenever the `target` changes, a new transition begins from the property's current value
+    /// towards the new `target` over the given `duration`, following the given `easing` curve.
+    /// The first time a property is animated, it settles immediately at the `target`.
+    ///
+    /// The `Ui` requests that it be redrawn for as long as any animation is in progress.
+    ///
+    /// See the `animation` module for details.
+    pub fn animate<T>(&mut self,
+                      id: widget::Id,
+                      property: &'static str,
+                      target: T,
+                      duration: std::time::Duration,
+                      easing: animation::Easing) -> T
+        where T: Animatable,
+    {
+        let now = self.ui.frame_time;
+        self.ui.animations.animate((id, property), target, now, duration, easing)
+    }
+
+    /// Request that the `Ui` be updated and redrawn at the given moment, even if no input is
+    /// received in the meantime.
+    ///
+    /// Requests only last until the next call to `Ui::set_widgets`, so widgets that depend upon
+    /// the passing of time (e.g. a blinking caret) should request their next deadline each time
+    /// they are updated. When multiple requests are made, the earliest is kept.
+    ///
+    /// See `Ui::next_redraw_deadline`.
+    pub fn request_redraw_at(&mut self, deadline: instant::Instant) {
+        let deadline = match self.ui.maybe_redraw_deadline {
+            Some(existing) => std::cmp::min(existing, deadline),
+            None => deadline,
+        };
+        self.ui.maybe_redraw_deadline = Some(deadline);
+    }
+
+    /// Request that the `Ui` be updated and redrawn once the given duration has elapsed.
+    ///
+    /// This is short-hand for `request_redraw_at` with the current time of the `Ui`'s clock
+    /// plus the given `duration`.
+    pub fn request_redraw_after(&mut self, duration: std::time::Duration) {
+        let deadline = self.ui.clock.now() + duration;
+        self.request_redraw_at(deadline);
+    }
+
     /// Sets the mouse cursor
     pub fn set_mouse_cursor(&mut self, cursor: cursor::MouseCursor) {
         self.ui.mouse_cursor = cursor;
@@ -1268,18 +1963,60 @@ impl<'a> Drop for UiCell<'a> {
             depth_order.update(widget_graph, window, updated_widgets);
         }
 
+        // Remove the constraint anchors of any widgets that were not set during this update.
+        {
+            let Ui { ref mut constraints, ref updated_widgets, .. } = *self.ui;
+            constraints.retain_widgets(updated_widgets);
+        }
+
+        // Close any modal layers whose widgets were not set during this update, returning the
+        // keyboard to the widget that was capturing it when the layer was opened.
+        let mut modal_closed = false;
+        while let Some(i) = self.ui.modals.iter()
+            .rposition(|&(id, _)| !self.ui.updated_widgets.contains(&id))
+        {
+            let (id, prev) = self.ui.modals.remove(i);
+            let capturing = self.ui.global_input.current.widget_capturing_keyboard;
+            let lost_capture = match capturing {
+                None => true,
+                Some(idx) => idx == id
+                    || !self.ui.updated_widgets.contains(&idx)
+                    || self.ui.widget_graph.does_recursive_depth_edge_exist(id, idx),
+            };
+            if lost_capture {
+                let prev = prev.and_then(|idx| match self.ui.updated_widgets.contains(&idx) {
+                    true => Some(idx),
+                    false => None,
+                });
+                self.ui.recapture_keyboard(prev);
+            }
+            modal_closed = true;
+        }
+
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
         // Update which widget is under the cursor.
-        if changed {
+        if changed || modal_closed {
             self.ui.track_widget_under_mouse_and_update_capturing();
         }
 
-        // Move all pending `Scroll` events that have been produced since the start of this method
-        // into the `global_input` event buffer.
-        for scroll_event in self.ui.pending_scroll_events.drain(0..) {
-            self.ui.global_input.push_event(scroll_event.into());
+        // Drop the animations of removed widgets and keep redrawing while any are in progress.
+        self.ui.animations.retain_widgets(&self.ui.updated_widgets);
+        if self.ui.animations.is_running(self.ui.frame_time) {
+            self.ui.needs_redraw();
+        }
+
+        // A dropped payload is only available until the end of the update following the drop.
+        let is_dropped = self.ui.drag_drop.as_ref().map_or(false, |d| !d.is_dragging());
+        if is_dropped {
+            self.ui.drag_drop = None;
+        }
+
+        // Move all pending events that have been produced since the start of this method into the
+        // `global_input` event buffer.
+        for event in self.ui.pending_events.drain(0..) {
+            self.ui.global_input.push_event(event.into());
         }
     }
 }
@@ -1310,6 +2047,66 @@ pub fn widget_graph_mut(ui: &mut Ui) -> &mut Graph {
     &mut ui.widget_graph
 }
 
+/// Suggest the given dimensions of the widget to the constraint layout along each axis on which
+/// the widget is not itself sized by the layout.
+///
+/// This allows constraints to refer to widgets that are sized in some other manner. Widgets that
+/// are not referred to by any constraint are ignored.
+pub fn suggest_constrained_dimensions(ui: &mut Ui,
+                                      id: widget::Id,
+                                      x: Dimension,
+                                      y: Dimension,
+                                      dim: Dimensions)
+{
+    let anchors = match ui.constraints.get_anchors(id) {
+        Some(anchors) => anchors,
+        None => return,
+    };
+    if let Dimension::Constrained(_) = x {} else {
+        ui.constraints.suggest_value(anchors.width, dim[0]);
+    }
+    if let Dimension::Constrained(_) = y {} else {
+        ui.constraints.suggest_value(anchors.height, dim[1]);
+    }
+}
+
+/// Suggest the position of the given widget's `Rect` to the constraint layout along each axis on
+/// which the widget is not itself positioned by the layout.
+///
+/// The scroll offset of the widget's parents is removed, as it is re-applied to the positions of
+/// constrained widgets.
+pub fn suggest_constrained_position(ui: &mut Ui,
+                                    id: widget::Id,
+                                    x: Position,
+                                    y: Position,
+                                    rect: Rect)
+{
+    let anchors = match ui.constraints.get_anchors(id) {
+        Some(anchors) => anchors,
+        None => return,
+    };
+    let offset = graph::algo::scroll_offset(&ui.widget_graph, id);
+    if let Position::Constrained(_) = x {} else {
+        ui.constraints.suggest_value(anchors.left, rect.left() - offset[0]);
+    }
+    if let Position::Constrained(_) = y {} else {
+        ui.constraints.suggest_value(anchors.bottom, rect.bottom() - offset[1]);
+    }
+}
+
+
+/// A function for filtering `ModifierKey`s.
+fn filter_modifier(key: input::Key) -> Option<input::ModifierKey> {
+    use input::{Key, ModifierKey};
+    match key {
+        Key::LCtrl | Key::RCtrl => Some(ModifierKey::CTRL),
+        Key::LShift | Key::RShift => Some(ModifierKey::SHIFT),
+        Key::LAlt | Key::RAlt => Some(ModifierKey::ALT),
+        Key::LGui | Key::RGui => Some(ModifierKey::GUI),
+        _ => None
+    }
+}
+
 
 /// Infer a widget's `Depth` parent by examining it's *x* and *y* `Position`s.
 ///
@@ -86,7 +86,9 @@ impl Widget for BorderedRectangle {
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
 
-        let border = style.border(&ui.theme);
+        // Align the edges of the border to the display's pixels so that they remain crisp.
+        let rect = ui.snap_rect(rect);
+        let border = ui.snap_length(style.border(&ui.theme));
         if let Some(triangles) = border_triangles(rect, border) {
             let border_color = style.border_color(&ui.theme);
             widget::Triangles::single_color(border_color, triangles.iter().cloned())
@@ -1,7 +1,9 @@
 //! The `Button` widget and related items.
 
-use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable, UiCell, Widget};
+use {Color, Colorable, Dimensions, FontSize, Borderable, Labelable, Positionable, Sizeable, Ui,
+     UiCell, Widget};
 use image;
+use label;
 use position::{self, Align, Rect, Scalar};
 use text;
 use widget;
@@ -294,6 +296,11 @@ impl<'a> Widget for Button<'a, Flat> {
         self.style.clone()
     }
 
+    /// The measured dimensions of the label along with the border.
+    fn content_dimensions(&self, _id: widget::Id, ui: &Ui) -> Option<Dimensions> {
+        label_dimensions(self.maybe_label, &self.style, ui)
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -333,6 +340,11 @@ impl<'a> Widget for Button<'a, Image> {
         self.style.clone()
     }
 
+    /// The measured dimensions of the label along with the border.
+    fn content_dimensions(&self, _id: widget::Id, ui: &Ui) -> Option<Dimensions> {
+        label_dimensions(self.maybe_label, &self.style, ui)
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -410,6 +422,18 @@ fn bordered_rectangle(button_id: widget::Id, rectangle_id: widget::Id,
         .set(rectangle_id, ui);
 }
 
+/// The dimensions of the given label when measured with the button's font, along with the border
+/// on either side.
+///
+/// Returns `None` if there is no label or if no font is available.
+fn label_dimensions(maybe_label: Option<&str>, style: &Style, ui: &Ui) -> Option<Dimensions> {
+    maybe_label.and_then(|label| {
+        let font_id = style.label_font_id(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        label::bordered_dimensions(label, font_id, font_size, style.border(&ui.theme), ui)
+    })
+}
+
 fn label(button_id: widget::Id, label_id: widget::Id,
          label: &str, style: &Style, ui: &mut UiCell)
 {
@@ -0,0 +1,28 @@
+//! Timing shared by the widgets that display a blinking text cursor.
+
+use std;
+
+
+/// Whether or not a cursor that began blinking at `start` is visible at the moment `now`, along
+/// with the moment at which it will next be shown or hidden.
+///
+/// Returns `None` if the `interval` is zero, in which case the cursor does not blink.
+pub fn blink_phase(start: instant::Instant,
+                   now: instant::Instant,
+                   interval: std::time::Duration) -> Option<(bool, instant::Instant)>
+{
+    let interval_nanos = duration_nanos(interval);
+    if interval_nanos == 0 {
+        return None;
+    }
+    let elapsed_nanos = if now > start { duration_nanos(now.duration_since(start)) } else { 0 };
+    let phase = elapsed_nanos / interval_nanos;
+    let until_next = interval_nanos - elapsed_nanos % interval_nanos;
+    let next_toggle = now + std::time::Duration::new(until_next / 1_000_000_000,
+                                                     (until_next % 1_000_000_000) as u32);
+    Some((phase % 2 == 0, next_toggle))
+}
+
+fn duration_nanos(duration: std::time::Duration) -> u64 {
+    duration.as_secs() * 1_000_000_000 + duration.subsec_nanos() as u64
+}
@@ -0,0 +1,1038 @@
+//! A widget for editing source code, given as a `String`.
+
+use {Color, Colorable, FontSize, Positionable, Sizeable, Widget};
+use event;
+use input::{self, Key, ModifierKey};
+use input::key_bindings::{self, Action, Movement};
+use input::shortcut::Chord;
+use position::{Point, Range, Rect, Scalar};
+use std;
+use std::borrow::Cow;
+use text;
+use utils;
+use widget;
+use widget::caret;
+use cursor;
+
+
+/// A widget for editing source code, given as a `String`.
+///
+/// Unlike the `TextEdit`, the **CodeEditor** never wraps lines and expects a monospace font, so
+/// that each line may be laid out independently of all others. Only the lines that are visible
+/// within the editor's `Rect` are highlighted and drawn, allowing for texts of many thousands of
+/// lines. The **CodeEditor** scrolls its own content in response to scrolling and to movement of
+/// the cursor.
+///
+/// Cursor movement and editing follow the `Theme`'s `key_bindings` in the same manner as the
+/// `TextEdit`. In addition, the **CodeEditor** provides:
+///
+/// - A gutter displaying the number of each line.
+/// - A highlight behind the line containing the cursor.
+/// - Indentation via `Tab` and `Shift+Tab`, inserting either tab characters or spaces.
+/// - Automatic indentation of new lines.
+/// - A highlight behind the bracket matching the bracket at the cursor.
+/// - Syntax highlighting via a user-provided `Highlighter`.
+#[derive(WidgetCommon_)]
+pub struct CodeEditor<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+    highlighter: Option<&'a Highlighter>,
+}
+
+/// Unique graphical styling for the **CodeEditor**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the background behind the text.
+    #[conrod(default = "theme.shape_color")]
+    pub color: Option<Color>,
+    /// The color of any text that is not styled by the `Highlighter`.
+    #[conrod(default = "theme.label_color")]
+    pub text_color: Option<Color>,
+    /// The font size for the text.
+    #[conrod(default = "theme.font_size_medium")]
+    pub font_size: Option<FontSize>,
+    /// The font used for the text. This should be a monospace font.
+    #[conrod(default = "theme.font_id")]
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The vertical space between each line of text.
+    #[conrod(default = "2.0")]
+    pub line_spacing: Option<Scalar>,
+    /// The number of columns between each tab stop.
+    #[conrod(default = "4")]
+    pub tab_width: Option<usize>,
+    /// Whether `Tab` inserts spaces rather than a tab character.
+    #[conrod(default = "true")]
+    pub soft_tabs: Option<bool>,
+    /// Whether or not the gutter displaying line numbers is shown.
+    #[conrod(default = "true")]
+    pub show_line_numbers: Option<bool>,
+    /// The color of the gutter behind the line numbers.
+    #[conrod(default = "theme.background_color")]
+    pub gutter_color: Option<Color>,
+    /// The color of the line numbers, other than the number of the line containing the cursor.
+    #[conrod(default = "theme.label_color.alpha(0.5)")]
+    pub line_number_color: Option<Color>,
+    /// The color of the highlight behind the line containing the cursor.
+    #[conrod(default = "theme.label_color.alpha(0.08)")]
+    pub current_line_color: Option<Color>,
+    /// The color of the highlight behind a pair of matching brackets.
+    #[conrod(default = "theme.label_color.alpha(0.25)")]
+    pub bracket_match_color: Option<Color>,
+    /// The duration for which the cursor is alternately shown and hidden while the
+    /// **CodeEditor** is capturing the keyboard. A duration of zero disables blinking.
+    #[conrod(default = "theme.caret_blink_interval")]
+    pub caret_blink_interval: Option<std::time::Duration>,
+}
+
+/// Produces the syntax highlighting for each line of a **CodeEditor**'s text.
+///
+/// Only visible lines are highlighted, so lines may be requested in any order. Highlighters that
+/// depend upon the preceding lines (e.g. for block comments) may inspect the whole `text`.
+///
+/// This is implemented for all functions with the same signature as `highlight_line`.
+pub trait Highlighter {
+    /// The styled spans of the line at index `line_idx` of the given `text`.
+    ///
+    /// The spans should be ordered and should not overlap. Any part of the `line` that is not
+    /// covered by a span is drawn with the editor's `text_color`.
+    fn highlight_line(&self, text: &str, line_idx: usize, line: &str) -> Vec<Span>;
+}
+
+/// A range of a line styled by a `Highlighter`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    /// The range of bytes within the line.
+    pub bytes: std::ops::Range<usize>,
+    /// The color of the text within the range.
+    pub color: Color,
+}
+
+widget_ids! {
+    struct Ids {
+        background,
+        current_line,
+        selected_rectangles[],
+        open_bracket,
+        close_bracket,
+        spans[],
+        gutter,
+        line_numbers[],
+        cursor,
+    }
+}
+
+/// The State of the **CodeEditor** that will be cached within the `Ui`.
+pub struct State {
+    ids: Ids,
+    cursor: Cursor,
+    /// The offset of the visible area from the top left of the text, along each axis.
+    scroll: Point,
+    /// Whether or not a selection is currently being dragged out via the mouse.
+    is_dragging: bool,
+    /// The selection begun by a double or triple click, if any.
+    select_by: Option<text::cursor::UnitSelection<usize>>,
+    /// The moment from which the cursor began blinking, if the keyboard is captured.
+    blink_start: Option<instant::Instant>,
+}
+
+/// The selected range of the text, described by byte indices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    /// The end of the selection that remains in place while selecting.
+    pub anchor: usize,
+    /// The end of the selection that moves while selecting, i.e. the position of the caret.
+    pub head: usize,
+}
+
+/// The lines of the text, determined once per update and again after each edit.
+struct Lines {
+    /// The byte index at which each line begins.
+    starts: Vec<usize>,
+    /// The number of columns spanned by the widest line, determined when first required.
+    max_columns: Option<usize>,
+}
+
+/// The pairs of brackets that are matched.
+const BRACKETS: &'static [(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+
+impl<'a> CodeEditor<'a> {
+
+    /// Construct a new **CodeEditor** for the given text.
+    pub fn new(text: &'a str) -> Self {
+        CodeEditor {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            text: text,
+            highlighter: None,
+        }.crop_kids()
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Use the given `Highlighter` to style the text.
+    pub fn highlighter(mut self, highlighter: &'a Highlighter) -> Self {
+        self.highlighter = Some(highlighter);
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub tab_width { style.tab_width = Some(usize) }
+        pub soft_tabs { style.soft_tabs = Some(bool) }
+        pub show_line_numbers { style.show_line_numbers = Some(bool) }
+        pub gutter_color { style.gutter_color = Some(Color) }
+        pub line_number_color { style.line_number_color = Some(Color) }
+        pub current_line_color { style.current_line_color = Some(Color) }
+        pub bracket_match_color { style.bracket_match_color = Some(Color) }
+        pub caret_blink_interval { style.caret_blink_interval = Some(std::time::Duration) }
+    }
+
+}
+
+impl<'a> Widget for CodeEditor<'a> {
+    type State = State;
+    type Style = Style;
+    /// The new text, if it was edited.
+    type Event = Option<String>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            cursor: Cursor { anchor: 0, head: 0 },
+            scroll: [0.0, 0.0],
+            is_dragging: false,
+            select_by: None,
+            blink_start: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let CodeEditor { text, highlighter, .. } = self;
+        let mut text = Cow::Borrowed(text);
+
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return None,
+        };
+
+        let font_size = style.font_size(&ui.theme);
+        let line_spacing = style.line_spacing(&ui.theme);
+        let tab_width = std::cmp::max(style.tab_width(&ui.theme), 1);
+        let soft_tabs = style.soft_tabs(&ui.theme);
+        let text_color = style.text_color(&ui.theme);
+
+        // As the font is expected to be monospace, every character shares the same width.
+        let char_w = {
+            let font = ui.fonts.get(font_id).unwrap();
+            match text::line::width("0", font, font_size) {
+                w if w > 0.0 => w,
+                _ => font_size as Scalar / 2.0,
+            }
+        };
+        let line_h = font_size as Scalar + line_spacing;
+
+        let mut lines = Lines::new(&text);
+
+        // The gutter is wide enough for the greatest line number plus a column either side.
+        let gutter_w = match style.show_line_numbers(&ui.theme) {
+            true => (num_digits(lines.len()) + 2) as Scalar * char_w,
+            false => 0.0,
+        };
+        let text_left = rect.left() + gutter_w + char_w / 2.0;
+        let text_area_w = utils::partial_max(rect.right() - text_left, 0.0);
+
+        // The indentation inserted by a single `Tab`.
+        let indent_unit = match soft_tabs {
+            true => std::iter::repeat(' ').take(tab_width).collect::<String>(),
+            false => "\t".to_string(),
+        };
+
+        // Ensure the cursor lies within the text, as the text may have changed since the last
+        // update.
+        let clamp = |text: &str, idx: usize| {
+            let mut idx = std::cmp::min(idx, text.len());
+            while !text.is_char_boundary(idx) {
+                idx -= 1;
+            }
+            idx
+        };
+        let mut cursor = Cursor {
+            anchor: clamp(&text, state.cursor.anchor),
+            head: clamp(&text, state.cursor.head),
+        };
+        let mut scroll = state.scroll;
+        let mut is_dragging = state.is_dragging;
+        let mut select_by = state.select_by;
+
+        // The line and byte index of the character boundary nearest to the given absolute
+        // position.
+        let index_at_xy = |text: &str, lines: &Lines, xy: Point, scroll: Point| -> (usize, usize) {
+            let line = ((rect.top() + scroll[1] - xy[1]) / line_h).floor();
+            let line = utils::clamp(line, 0.0, (lines.len() - 1) as Scalar) as usize;
+            let column = (xy[0] - text_left + scroll[0]) / char_w;
+            let (start, end) = lines.range(text, line);
+            (line, start + index_at_column(&text[start..end], column, tab_width))
+        };
+
+        // The range of the given unit of text surrounding the byte index on the given line.
+        let unit_range = |unit: text::cursor::Unit, text: &str, lines: &Lines, line, idx| {
+            let (start, end) = lines.range(text, line);
+            match unit {
+                text::cursor::Unit::Word => {
+                    let word = text::cursor::word_range_at(&text[start..end], idx - start);
+                    (start + word.start, start + word.end)
+                },
+                text::cursor::Unit::Paragraph => (start, end),
+            }
+        };
+
+        let mut interpreter = key_bindings::Interpreter::new();
+        'events: for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+
+                event::Widget::Press(press) => match press.button {
+
+                    // Place the cursor at the mouse, extending the selection if `Shift` is held. A
+                    // double or triple press selects the surrounding word or line.
+                    event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
+                        let xy = utils::vec2_add(rel_xy, rect.xy());
+                        let (line, head) = index_at_xy(&text, &lines, xy, scroll);
+                        let click_count = ui.global_input().click_count;
+                        select_by = text::cursor::Unit::from_click_count(click_count)
+                            .map(|unit| {
+                                let (start, end) = unit_range(unit, &text, &lines, line, head);
+                                text::cursor::UnitSelection { unit: unit, start: start, end: end }
+                            });
+                        cursor = match select_by {
+                            Some(select_by) => Cursor { anchor: select_by.start, head: select_by.end },
+                            None => match press.modifiers.contains(ModifierKey::SHIFT) {
+                                true => Cursor { anchor: cursor.anchor, head: head },
+                                false => Cursor { anchor: head, head: head },
+                            },
+                        };
+                        is_dragging = true;
+                    },
+
+                    event::Button::Keyboard(key) => {
+                        // `Tab` and `Shift+Tab` indent and outdent.
+                        let is_tab = key == Key::Tab
+                            && (press.modifiers - ModifierKey::SHIFT).is_empty();
+                        if is_tab {
+                            interpreter.suppress_text();
+                            let (new_text, new_cursor) = match press.modifiers.is_empty() {
+                                true => indent(&text, &lines, cursor, &indent_unit, tab_width,
+                                               soft_tabs),
+                                false => outdent(&text, &lines, cursor, tab_width),
+                            };
+                            *text.to_mut() = new_text;
+                            lines = Lines::new(&text);
+                            cursor = new_cursor;
+                            continue 'events;
+                        }
+
+                        let chord = Chord::new(press.modifiers, key);
+                        let action = match interpreter.press(&ui.theme.key_bindings, chord) {
+                            Some(action) => action,
+                            None => continue 'events,
+                        };
+
+                        match action {
+
+                            Action::Move(movement) => {
+                                let Cursor { anchor, head } = cursor;
+                                let head = text::cursor::move_selection(anchor, head, movement, |idx, m| {
+                                    move_index(&text, &lines, idx, m, tab_width)
+                                });
+                                cursor = Cursor { anchor: head, head: head };
+                            },
+
+                            Action::Select(movement) => {
+                                cursor.head = move_index(&text, &lines, cursor.head, movement,
+                                                         tab_width);
+                            },
+
+                            Action::Delete(movement) => {
+                                let Cursor { anchor, head } = cursor;
+                                let (start, end) = text::cursor::delete_range(anchor, head, movement, |idx, m| {
+                                    match m {
+                                        // With soft tabs, remove leading spaces by the tab.
+                                        Movement::Left if soft_tabs =>
+                                            soft_tab_start(&text, idx, tab_width)
+                                                .unwrap_or_else(|| {
+                                                    move_index(&text, &lines, idx, m, tab_width)
+                                                }),
+                                        _ => move_index(&text, &lines, idx, m, tab_width),
+                                    }
+                                });
+                                text.to_mut().replace_range(start..end, "");
+                                lines = Lines::new(&text);
+                                cursor = Cursor { anchor: start, head: start };
+                            },
+
+                            Action::SelectAll => {
+                                cursor = Cursor { anchor: 0, head: text.len() };
+                            },
+
+                            Action::InsertNewline => {
+                                let (start, end) = cursor.range();
+                                let (before, after) = newline_with_indent(&text, start, end,
+                                                                          &indent_unit);
+                                let inserted = format!("{}{}", before, after);
+                                text.to_mut().replace_range(start..end, &inserted);
+                                lines = Lines::new(&text);
+                                let head = start + before.len();
+                                cursor = Cursor { anchor: head, head: head };
+                            },
+
+                        }
+                    },
+
+                    _ => (),
+                },
+
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        is_dragging = false;
+                    }
+                },
+
+                // Extend the selection to the mouse, by whole units if begun by a double or triple
+                // press.
+                event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                    if is_dragging {
+                        let xy = utils::vec2_add(drag.to, rect.xy());
+                        let (line, idx) = index_at_xy(&text, &lines, xy, scroll);
+                        cursor = match select_by {
+                            None => Cursor { anchor: cursor.anchor, head: idx },
+                            Some(select_by) => {
+                                let unit_at_idx = unit_range(select_by.unit, &text, &lines, line,
+                                                             idx);
+                                let (anchor, head) = select_by.extend(idx, unit_at_idx);
+                                Cursor { anchor: anchor, head: head }
+                            },
+                        };
+                    }
+                },
+
+                event::Widget::Text(text_event) => {
+                    let string = match interpreter.text(&text_event) {
+                        Some(string) => string,
+                        None => continue 'events,
+                    };
+                    let (start, end) = cursor.range();
+                    text.to_mut().replace_range(start..end, string);
+                    lines = Lines::new(&text);
+                    let head = start + string.len();
+                    cursor = Cursor { anchor: head, head: head };
+                },
+
+                event::Widget::Scroll(scroll_event) => {
+                    let max_y = lines.len() as Scalar * line_h - rect.h();
+                    scroll[1] = utils::clamp(scroll[1] + scroll_event.y, 0.0,
+                                             utils::partial_max(max_y, 0.0));
+                    if scroll_event.x != 0.0 {
+                        let max_columns = lines.max_columns(&text, tab_width);
+                        let max_x = (max_columns + 1) as Scalar * char_w - text_area_w;
+                        scroll[0] = utils::clamp(scroll[0] + scroll_event.x, 0.0,
+                                                 utils::partial_max(max_x, 0.0));
+                    }
+                },
+
+                _ => (),
+            }
+        }
+
+        if let Some(_) = ui.widget_input(id).mouse() {
+            ui.set_mouse_cursor(cursor::MouseCursor::Text);
+        }
+
+        let head_line = lines.of(cursor.head);
+        let head_column = {
+            let (start, _) = lines.range(&text, head_line);
+            column_of(&text[start..], cursor.head - start, tab_width)
+        };
+        let text_has_changed = match text {
+            Cow::Owned(_) => true,
+            Cow::Borrowed(_) => false,
+        };
+        let cursor_has_changed = state.cursor != cursor;
+
+        // Scroll the cursor into view if it has moved.
+        if cursor_has_changed || text_has_changed {
+            let top = head_line as Scalar * line_h;
+            if top < scroll[1] {
+                scroll[1] = top;
+            } else if top + line_h > scroll[1] + rect.h() {
+                scroll[1] = top + line_h - rect.h();
+            }
+            let x = head_column as Scalar * char_w;
+            if x < scroll[0] {
+                scroll[0] = x;
+            } else if x + char_w > scroll[0] + text_area_w {
+                scroll[0] = x + char_w - text_area_w;
+            }
+            let max_y = utils::partial_max(lines.len() as Scalar * line_h - rect.h(), 0.0);
+            scroll[1] = utils::clamp(scroll[1], 0.0, max_y);
+            scroll[0] = utils::partial_max(scroll[0], 0.0);
+        }
+
+        if cursor_has_changed {
+            state.update(|state| state.cursor = cursor);
+        }
+        if state.scroll != scroll {
+            state.update(|state| state.scroll = scroll);
+        }
+        if state.is_dragging != is_dragging {
+            state.update(|state| state.is_dragging = is_dragging);
+        }
+        if state.select_by != select_by {
+            state.update(|state| state.select_by = select_by);
+        }
+
+        // Only the lines that intersect the visible area are laid out.
+        let first_line = std::cmp::min((scroll[1] / line_h).floor() as usize, lines.len() - 1);
+        let end_line = std::cmp::min(((scroll[1] + rect.h()) / line_h).ceil() as usize + 1,
+                                     lines.len());
+        let visible_lines = first_line..end_line;
+
+        // The vertical range occupied by the line at the given index.
+        let line_y = |line: usize| {
+            let top = rect.top() - line as Scalar * line_h + scroll[1];
+            Range::new(top - line_h, top)
+        };
+        // The horizontal position of the given column.
+        let column_x = |column: usize| text_left + column as Scalar * char_w - scroll[0];
+
+        widget::Rectangle::fill(rect.dim())
+            .xy(rect.xy())
+            .color(style.color(&ui.theme))
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.background, ui);
+
+        // Highlight the line containing the cursor.
+        let is_capturing_keyboard = ui.global_input().current.widget_capturing_keyboard == Some(id);
+        if is_capturing_keyboard && visible_lines.start <= head_line && head_line < visible_lines.end {
+            let y = line_y(head_line);
+            widget::Rectangle::fill([rect.w(), y.len()])
+                .x_y(rect.x(), y.middle())
+                .color(style.current_line_color(&ui.theme))
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.current_line, ui);
+        }
+
+        // Draw a rectangle behind the selected range of each visible line.
+        let (selection_start, selection_end) = cursor.range();
+        let selected_rects: Vec<Rect> = visible_lines.clone().filter_map(|line| {
+            let (start, end) = lines.range(&text, line);
+            if selection_start == selection_end || selection_end < start || selection_start > end {
+                return None;
+            }
+            let line_text = &text[start..end];
+            let from = std::cmp::max(selection_start, start) - start;
+            let to = std::cmp::min(selection_end, end) - start;
+            let left = column_x(column_of(line_text, from, tab_width));
+            let mut right = column_x(column_of(line_text, to, tab_width));
+            // Indicate that the selection includes the end of the line.
+            if selection_end > end {
+                right += char_w;
+            }
+            Some(Rect { x: Range::new(left, right), y: line_y(line) })
+        }).collect();
+        if state.ids.selected_rectangles.len() < selected_rects.len() {
+            let num_rects = selected_rects.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.selected_rectangles.resize(num_rects, id_gen));
+        }
+        let selected_color = text_color.highlighted().alpha(0.25);
+        let iter = state.ids.selected_rectangles.iter().zip(&selected_rects);
+        for (&rectangle_id, selected_rect) in iter {
+            widget::Rectangle::fill(selected_rect.dim())
+                .xy(selected_rect.xy())
+                .color(selected_color)
+                .graphics_for(id)
+                .parent(id)
+                .set(rectangle_id, ui);
+        }
+
+        // Highlight the bracket at the cursor along with its match.
+        if let Some((a, b)) = matching_brackets(&text, cursor.head) {
+            let bracket_ids = [state.ids.open_bracket, state.ids.close_bracket];
+            for (&bracket_idx, &bracket_id) in [a, b].iter().zip(bracket_ids.iter()) {
+                let line = lines.of(bracket_idx);
+                if line < visible_lines.start || line >= visible_lines.end {
+                    continue;
+                }
+                let start = lines.starts[line];
+                let x = column_x(column_of(&text[start..], bracket_idx - start, tab_width));
+                let y = line_y(line);
+                widget::Rectangle::fill([char_w, y.len()])
+                    .x_y(x + char_w / 2.0, y.middle())
+                    .color(style.bracket_match_color(&ui.theme))
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(bracket_id, ui);
+            }
+        }
+
+        // Split each visible line into spans of uniformly colored text.
+        let mut spans: Vec<(String, Color, Rect)> = Vec::new();
+        for line in visible_lines.clone() {
+            let (start, end) = lines.range(&text, line);
+            let line_text = &text[start..end];
+            let styled = match highlighter {
+                Some(highlighter) => highlighter.highlight_line(&text, line, line_text),
+                None => Vec::new(),
+            };
+            let y = line_y(line);
+            let text_y = Range::new(y.end - font_size as Scalar, y.end);
+            for (bytes, color) in colored_ranges(line_text, styled, text_color) {
+                let start_column = column_of(line_text, bytes.start, tab_width);
+                let display = expand_tabs(&line_text[bytes.clone()], start_column, tab_width);
+                if display.trim().is_empty() {
+                    continue;
+                }
+                let left = column_x(start_column);
+                let right = left + display.chars().count() as Scalar * char_w;
+                if right < text_left || left > rect.right() {
+                    continue;
+                }
+                spans.push((display, color, Rect { x: Range::new(left, right), y: text_y }));
+            }
+        }
+        if state.ids.spans.len() < spans.len() {
+            let num_spans = spans.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.spans.resize(num_spans, id_gen));
+        }
+        let iter = state.ids.spans.iter().zip(&spans);
+        for (&span_id, &(ref display, color, span_rect)) in iter {
+            widget::Text::new(display)
+                .font_id(font_id)
+                .font_size(font_size)
+                .no_line_wrap()
+                .left_justify()
+                .color(color)
+                .wh(span_rect.dim())
+                .xy(span_rect.xy())
+                .graphics_for(id)
+                .parent(id)
+                .set(span_id, ui);
+        }
+
+        // Draw the gutter over any horizontally scrolled text.
+        if gutter_w > 0.0 {
+            widget::Rectangle::fill([gutter_w, rect.h()])
+                .x_y(rect.left() + gutter_w / 2.0, rect.y())
+                .color(style.gutter_color(&ui.theme))
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.gutter, ui);
+
+            let num_lines = visible_lines.len();
+            if state.ids.line_numbers.len() < num_lines {
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.line_numbers.resize(num_lines, id_gen));
+            }
+            let line_number_color = style.line_number_color(&ui.theme);
+            let number_w = gutter_w - char_w;
+            let iter = state.ids.line_numbers.iter().zip(visible_lines.clone());
+            for (&line_number_id, line) in iter {
+                let y = line_y(line);
+                let color = if line == head_line { text_color } else { line_number_color };
+                widget::Text::new(&(line + 1).to_string())
+                    .font_id(font_id)
+                    .font_size(font_size)
+                    .no_line_wrap()
+                    .right_justify()
+                    .color(color)
+                    .w_h(number_w, font_size as Scalar)
+                    .x_y(rect.left() + number_w / 2.0, y.end - font_size as Scalar / 2.0)
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(line_number_id, ui);
+            }
+        }
+
+        // Takes the `String` from the `Cow` if the `Cow` is `Owned`.
+        fn take_if_owned(text: Cow<str>) -> Option<String> {
+            match text {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(s) => Some(s),
+            }
+        }
+
+        // If this widget is not capturing the keyboard, there is no need to draw the cursor.
+        if !is_capturing_keyboard {
+            if state.blink_start.is_some() {
+                state.update(|state| state.blink_start = None);
+            }
+            return take_if_owned(text);
+        }
+
+        // Restart the blink whenever the cursor moves or the text changes.
+        let now = ui.time();
+        let blink_start = match state.blink_start {
+            Some(start) if !cursor_has_changed && !text_has_changed => start,
+            _ => {
+                state.update(|state| state.blink_start = Some(now));
+                now
+            },
+        };
+        let blink_interval = style.caret_blink_interval(&ui.theme);
+        let cursor_is_visible = match caret::blink_phase(blink_start, now, blink_interval) {
+            Some((is_visible, next_toggle)) => {
+                ui.request_redraw_at(next_toggle);
+                is_visible
+            },
+            None => true,
+        };
+
+        let cursor_x = column_x(head_column);
+        let cursor_y = line_y(head_line);
+        let cursor_color = if cursor_is_visible { text_color } else { text_color.alpha(0.0) };
+        if cursor_x >= text_left - char_w / 2.0 {
+            widget::Line::centred([0.0, cursor_y.start], [0.0, cursor_y.end])
+                .x_y(cursor_x, cursor_y.middle())
+                .color(cursor_color)
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.cursor, ui);
+        }
+
+        take_if_owned(text)
+    }
+
+}
+
+
+impl Cursor {
+
+    /// The selected range as `(start, end)` where `start <= end`.
+    pub fn range(&self) -> (usize, usize) {
+        (std::cmp::min(self.anchor, self.head), std::cmp::max(self.anchor, self.head))
+    }
+
+}
+
+impl<F> Highlighter for F
+    where F: Fn(&str, usize, &str) -> Vec<Span>,
+{
+    fn highlight_line(&self, text: &str, line_idx: usize, line: &str) -> Vec<Span> {
+        (*self)(text, line_idx, line)
+    }
+}
+
+
+impl Lines {
+
+    /// Find the start of each line of the given text.
+    fn new(text: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        Lines { starts: starts, max_columns: None }
+    }
+
+    /// The number of lines.
+    fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// The index of the line containing the given byte index.
+    fn of(&self, idx: usize) -> usize {
+        match self.starts.binary_search(&idx) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    /// The range of bytes occupied by the given line, excluding its line break.
+    fn range(&self, text: &str, line: usize) -> (usize, usize) {
+        let start = self.starts[line];
+        let end = self.starts.get(line + 1).map(|&next| next - 1).unwrap_or(text.len());
+        (start, end)
+    }
+
+    /// The number of columns spanned by the widest line.
+    fn max_columns(&mut self, text: &str, tab_width: usize) -> usize {
+        if let Some(max_columns) = self.max_columns {
+            return max_columns;
+        }
+        let max_columns = text.lines()
+            .map(|line| column_of(line, line.len(), tab_width))
+            .max()
+            .unwrap_or(0);
+        self.max_columns = Some(max_columns);
+        max_columns
+    }
+
+}
+
+
+/// The number of decimal digits in the given number.
+fn num_digits(n: usize) -> usize {
+    n.to_string().len()
+}
+
+/// The display column of the given byte index within a line, expanding tabs to the next stop.
+fn column_of(line: &str, idx: usize, tab_width: usize) -> usize {
+    line[..idx].chars().fold(0, |column, c| match c {
+        '\t' => (column / tab_width + 1) * tab_width,
+        _ => column + 1,
+    })
+}
+
+/// The byte index of the character boundary within the line nearest to the given column.
+fn index_at_column(line: &str, column: Scalar, tab_width: usize) -> usize {
+    let mut start_column = 0;
+    for (i, c) in line.char_indices() {
+        let end_column = match c {
+            '\t' => (start_column / tab_width + 1) * tab_width,
+            _ => start_column + 1,
+        };
+        if column < (start_column + end_column) as Scalar / 2.0 {
+            return i;
+        }
+        start_column = end_column;
+    }
+    line.len()
+}
+
+/// The given text with each tab expanded to spaces, given that it begins at `start_column`.
+fn expand_tabs(text: &str, start_column: usize, tab_width: usize) -> String {
+    let mut column = start_column;
+    let mut expanded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                let next = (column / tab_width + 1) * tab_width;
+                expanded.extend(std::iter::repeat(' ').take(next - column));
+                column = next;
+            },
+            _ => {
+                expanded.push(c);
+                column += 1;
+            },
+        }
+    }
+    expanded
+}
+
+/// Divide the line into ranges of uniform color, filling the gaps between the given spans with
+/// the `default` color. Invalid spans are ignored.
+fn colored_ranges(line: &str, spans: Vec<Span>, default: Color)
+    -> Vec<(std::ops::Range<usize>, Color)>
+{
+    let mut ranges = Vec::new();
+    let mut idx = 0;
+    for span in spans {
+        let std::ops::Range { start, end } = span.bytes;
+        if start < idx || end <= start || end > line.len()
+        || !line.is_char_boundary(start) || !line.is_char_boundary(end) {
+            continue;
+        }
+        if idx < start {
+            ranges.push((idx..start, default));
+        }
+        ranges.push((start..end, span.color));
+        idx = end;
+    }
+    if idx < line.len() {
+        ranges.push((idx..line.len(), default));
+    }
+    ranges
+}
+
+/// The byte index reached by applying the given movement from `idx`.
+fn move_index(text: &str, lines: &Lines, idx: usize, movement: Movement, tab_width: usize)
+    -> usize
+{
+    let line = lines.of(idx);
+    let (line_start, line_end) = lines.range(text, line);
+    let vertical = |line: usize| {
+        let column = column_of(&text[line_start..], idx - line_start, tab_width);
+        let (start, end) = lines.range(text, line);
+        start + index_at_column(&text[start..end], column as Scalar, tab_width)
+    };
+    match movement {
+        Movement::Left => text[..idx].chars().next_back().map(|c| idx - c.len_utf8()).unwrap_or(idx),
+        Movement::Right => text[idx..].chars().next().map(|c| idx + c.len_utf8()).unwrap_or(idx),
+        Movement::Up => if line > 0 { vertical(line - 1) } else { 0 },
+        Movement::Down => if line + 1 < lines.len() { vertical(line + 1) } else { text.len() },
+        Movement::WordLeft => text::cursor::word_start_before(text, idx),
+        Movement::WordRight => text::cursor::word_end_after(text, idx),
+        // Move to the first non-whitespace character, or to the start of the line if already
+        // there.
+        Movement::LineStart => {
+            let line_text = &text[line_start..line_end];
+            let indent = line_text.len() - line_text.trim_start().len();
+            if idx == line_start + indent { line_start } else { line_start + indent }
+        },
+        Movement::LineEnd => line_end,
+        Movement::DocumentStart => 0,
+        Movement::DocumentEnd => text.len(),
+    }
+}
+
+/// If the cursor at `idx` is preceded only by spaces on its line, the index of the previous tab
+/// stop.
+fn soft_tab_start(text: &str, idx: usize, tab_width: usize) -> Option<usize> {
+    let line_start = text[..idx].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let leading = &text[line_start..idx];
+    if leading.is_empty() || leading.chars().any(|c| c != ' ') {
+        return None;
+    }
+    let columns = leading.len();
+    Some(idx - ((columns - 1) % tab_width + 1))
+}
+
+/// The indices of the lines spanned by the given cursor.
+fn selected_lines(lines: &Lines, cursor: Cursor) -> std::ops::Range<usize> {
+    let (start, end) = cursor.range();
+    let first = lines.of(start);
+    let mut last = lines.of(end);
+    // A selection ending at the very start of a line does not include that line.
+    if last > first && lines.starts[last] == end {
+        last -= 1;
+    }
+    first..last + 1
+}
+
+/// Insert an indent. A selection spanning multiple lines indents each of those lines, otherwise
+/// the selection is replaced by a tab or by spaces up to the next tab stop.
+fn indent(text: &str, lines: &Lines, cursor: Cursor, unit: &str, tab_width: usize, soft_tabs: bool)
+    -> (String, Cursor)
+{
+    let starts = &lines.starts;
+    let lines = selected_lines(lines, cursor);
+    let mut new_text = text.to_string();
+    let mut new_cursor = cursor;
+    let (start, end) = cursor.range();
+    if lines.len() > 1 {
+        for line in lines.rev() {
+            let line_start = starts[line];
+            new_text.insert_str(line_start, unit);
+            for idx in &mut [&mut new_cursor.anchor, &mut new_cursor.head] {
+                if **idx >= line_start {
+                    **idx += unit.len();
+                }
+            }
+        }
+    } else {
+        let inserted = match soft_tabs {
+            true => {
+                let line_start = starts[lines.start];
+                let column = column_of(&text[line_start..], start - line_start, tab_width);
+                std::iter::repeat(' ').take(tab_width - column % tab_width).collect()
+            },
+            false => "\t".to_string(),
+        };
+        new_text.replace_range(start..end, &inserted);
+        let head = start + inserted.len();
+        new_cursor = Cursor { anchor: head, head: head };
+    }
+    (new_text, new_cursor)
+}
+
+/// Remove up to one level of indentation from each line spanned by the cursor.
+fn outdent(text: &str, lines: &Lines, cursor: Cursor, tab_width: usize) -> (String, Cursor) {
+    let mut new_text = text.to_string();
+    let mut new_cursor = cursor;
+    for line in selected_lines(lines, cursor).rev() {
+        let line_start = lines.starts[line];
+        let removed = match text[line_start..].chars().next() {
+            Some('\t') => 1,
+            _ => text[line_start..].chars().take(tab_width).take_while(|&c| c == ' ').count(),
+        };
+        new_text.replace_range(line_start..line_start + removed, "");
+        for idx in &mut [&mut new_cursor.anchor, &mut new_cursor.head] {
+            if **idx >= line_start + removed {
+                **idx -= removed;
+            } else if **idx > line_start {
+                **idx = line_start;
+            }
+        }
+    }
+    (new_text, new_cursor)
+}
+
+/// The text inserted before and after the cursor when inserting a new line in place of the
+/// range `start..end`.
+///
+/// The new line copies the indentation of the current line, adding a level of indentation after
+/// an opening bracket. If the cursor is between a pair of brackets, the closing bracket is moved
+/// to its own line.
+fn newline_with_indent(text: &str, start: usize, end: usize, unit: &str) -> (String, String) {
+    let line_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let indent: String = text[line_start..start].chars().take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    let before_char = text[line_start..start].trim_end().chars().next_back();
+    let opening = BRACKETS.iter().find(|&&(open, _)| Some(open) == before_char);
+    match opening {
+        None => (format!("\n{}", indent), String::new()),
+        Some(&(_, close)) => {
+            let before = format!("\n{}{}", indent, unit);
+            let after = match text[end..].trim_start_matches(|c| c == ' ' || c == '\t')
+                .chars().next()
+            {
+                Some(c) if c == close => format!("\n{}", indent),
+                _ => String::new(),
+            };
+            (before, after)
+        },
+    }
+}
+
+/// The byte indices of the bracket at (or immediately before) `idx` and its matching bracket,
+/// ordered from first to last.
+fn matching_brackets(text: &str, idx: usize) -> Option<(usize, usize)> {
+    let after = text[idx..].chars().next().map(|c| (idx, c));
+    let before = text[..idx].chars().next_back().map(|c| (idx - c.len_utf8(), c));
+    let is_bracket = |&(_, c): &(usize, char)| {
+        BRACKETS.iter().any(|&(open, close)| c == open || c == close)
+    };
+    let (bracket_idx, bracket) = after.into_iter().chain(before).find(is_bracket)?;
+    for &(open, close) in BRACKETS {
+        let mut depth = 0;
+        if bracket == open {
+            for (i, c) in text[bracket_idx..].char_indices() {
+                if c == open { depth += 1; }
+                if c == close { depth -= 1; }
+                if depth == 0 { return Some((bracket_idx, bracket_idx + i)); }
+            }
+            return None;
+        } else if bracket == close {
+            for (i, c) in text[..bracket_idx + close.len_utf8()].char_indices().rev() {
+                if c == close { depth += 1; }
+                if c == open { depth -= 1; }
+                if depth == 0 { return Some((i, bracket_idx)); }
+            }
+            return None;
+        }
+    }
+    None
+}
+
+
+impl<'a> Colorable for CodeEditor<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
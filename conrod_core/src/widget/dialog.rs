@@ -0,0 +1,435 @@
+//! Stock alert, confirm and prompt dialogs built upon the `Modal` layer.
+
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Widget};
+use color;
+use input;
+use position::{Dimension, Position, Range, Rect, Scalar};
+use widget;
+
+
+/// Displays a message along with an "OK" button.
+///
+/// All dialogs open a `Modal` layer, blocking interaction with the rest of the `Ui` while they are
+/// set. Dismissing the layer via `Escape` or the backdrop produces a `Response::Cancel`.
+#[derive(Clone, WidgetCommon_)]
+pub struct Alert<'a> {
+    /// Common widget building params for the **Alert**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Alert**.
+    pub style: Style,
+    message: &'a str,
+    ok_label: &'a str,
+}
+
+/// Displays a message along with "OK" and "Cancel" buttons.
+#[derive(Clone, WidgetCommon_)]
+pub struct Confirm<'a> {
+    /// Common widget building params for the **Confirm**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Confirm**.
+    pub style: Style,
+    message: &'a str,
+    ok_label: &'a str,
+    cancel_label: &'a str,
+}
+
+/// Displays a message along with a text field, "OK" and "Cancel" buttons.
+///
+/// The text field captures the keyboard when the dialog opens. Pressing `Enter` within the field
+/// is equivalent to pressing "OK".
+#[derive(Clone, WidgetCommon_)]
+pub struct Prompt<'a> {
+    /// Common widget building params for the **Prompt**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Prompt**.
+    pub style: Style,
+    message: &'a str,
+    text: &'a str,
+    ok_label: &'a str,
+    cancel_label: &'a str,
+}
+
+/// Unique styling shared by all dialogs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the dialog's panel.
+    #[conrod(default = "theme.background_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the panel.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the border surrounding the panel.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the message, field and button text.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size of the message, field and button text.
+    #[conrod(default = "theme.font_size_medium")]
+    pub label_font_size: Option<FontSize>,
+    /// The color of the buttons and text field.
+    #[conrod(default = "theme.shape_color")]
+    pub button_color: Option<Color>,
+    /// The color of the backdrop drawn over the rest of the `Ui`.
+    #[conrod(default = "color::BLACK.alpha(0.5)")]
+    pub backdrop_color: Option<Color>,
+}
+
+widget_ids! {
+    struct Ids {
+        modal,
+        panel,
+        message,
+        field,
+        field_text,
+        ok,
+        cancel,
+    }
+}
+
+/// Represents the state of a dialog widget.
+pub struct State {
+    ids: Ids,
+}
+
+/// The user's response to a dialog.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Response {
+    /// The "OK" button was pressed, or `Enter` was pressed within a **Prompt**'s text field.
+    Ok,
+    /// The "Cancel" button was pressed or the dialog's modal layer was dismissed.
+    Cancel,
+}
+
+/// The padding between the edges of the panel and its contents.
+const PAD: Scalar = 10.0;
+/// The dimensions of each of the dialog's buttons.
+const BUTTON_DIM: [Scalar; 2] = [80.0, 30.0];
+/// The height of the **Prompt**'s text field.
+const FIELD_H: Scalar = 30.0;
+
+
+impl<'a> Alert<'a> {
+
+    /// Begin building an **Alert** displaying the given message.
+    pub fn new(message: &'a str) -> Self {
+        Alert {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            message: message,
+            ok_label: "OK",
+        }
+    }
+
+    builder_methods!{
+        pub ok_label { ok_label = &'a str }
+    }
+
+}
+
+impl<'a> Confirm<'a> {
+
+    /// Begin building a **Confirm** displaying the given message.
+    pub fn new(message: &'a str) -> Self {
+        Confirm {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            message: message,
+            ok_label: "OK",
+            cancel_label: "Cancel",
+        }
+    }
+
+    builder_methods!{
+        pub ok_label { ok_label = &'a str }
+        pub cancel_label { cancel_label = &'a str }
+    }
+
+}
+
+impl<'a> Prompt<'a> {
+
+    /// Begin building a **Prompt** displaying the given message and editing the given text.
+    pub fn new(message: &'a str, text: &'a str) -> Self {
+        Prompt {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            message: message,
+            text: text,
+            ok_label: "OK",
+            cancel_label: "Cancel",
+        }
+    }
+
+    builder_methods!{
+        pub ok_label { ok_label = &'a str }
+        pub cancel_label { cancel_label = &'a str }
+    }
+
+}
+
+/// Implements the builder methods and `Widget` boilerplate shared by every dialog.
+macro_rules! impl_dialog {
+    ($Dialog:ident, $default_h:expr) => {
+        impl<'a> $Dialog<'a> {
+            builder_methods!{
+                pub label_color { style.label_color = Some(Color) }
+                pub label_font_size { style.label_font_size = Some(FontSize) }
+                pub button_color { style.button_color = Some(Color) }
+                pub backdrop_color { style.backdrop_color = Some(Color) }
+            }
+        }
+
+        impl<'a> Colorable for $Dialog<'a> {
+            builder_method!(color { style.color = Some(Color) });
+        }
+
+        impl<'a> Borderable for $Dialog<'a> {
+            builder_methods!{
+                border { style.border = Some(Scalar) }
+                border_color { style.border_color = Some(Color) }
+            }
+        }
+
+        impl<'a> $Dialog<'a> {
+            fn default_dimensions(ui: &::Ui) -> (Dimension, Dimension) {
+                let w = if ui.win_w < 320.0 { ui.win_w } else { 320.0 };
+                (Dimension::Absolute(w), Dimension::Absolute($default_h))
+            }
+        }
+    };
+}
+
+impl_dialog!(Alert, 140.0);
+impl_dialog!(Confirm, 140.0);
+impl_dialog!(Prompt, 180.0);
+
+
+impl<'a> Widget for Alert<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Response>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State { ids: Ids::new(id_gen) }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_y_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_x_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).0
+    }
+
+    fn default_y_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).1
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, rect, style, ui, .. } = args;
+        let dismissed = set_panel(&state.ids, rect, style, self.message, ui);
+        let ok = set_button(state.ids.ok, state.ids.modal, self.ok_label, rect, 0, style, ui);
+        match (ok, dismissed) {
+            (true, _) => Some(Response::Ok),
+            (false, true) => Some(Response::Cancel),
+            _ => None,
+        }
+    }
+
+}
+
+impl<'a> Widget for Confirm<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Response>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State { ids: Ids::new(id_gen) }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_y_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_x_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).0
+    }
+
+    fn default_y_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).1
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, rect, style, ui, .. } = args;
+        let ids = &state.ids;
+        let dismissed = set_panel(ids, rect, style, self.message, ui);
+        let ok = set_button(ids.ok, ids.modal, self.ok_label, rect, 0, style, ui);
+        let cancel = set_button(ids.cancel, ids.modal, self.cancel_label, rect, 1, style, ui);
+        match (ok, cancel || dismissed) {
+            (true, _) => Some(Response::Ok),
+            (false, true) => Some(Response::Cancel),
+            _ => None,
+        }
+    }
+
+}
+
+impl<'a> Widget for Prompt<'a> {
+    type State = State;
+    type Style = Style;
+    /// The edited text, if it changed, along with the user's response, if any.
+    type Event = (Option<String>, Option<Response>);
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State { ids: Ids::new(id_gen) }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_y_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_x_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).0
+    }
+
+    fn default_y_dimension(&self, ui: &::Ui) -> Dimension {
+        Self::default_dimensions(ui).1
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        // The dialog is opening if it was not set during the previous update, whether for the
+        // first time or after having been closed.
+        let is_opening = !ui.prev_updated_widgets().contains(&id);
+        let dismissed = set_panel(&state.ids, rect, style, self.message, ui);
+
+        // The text field sits directly above the buttons.
+        let field_rect = Rect {
+            x: rect.x.pad(PAD),
+            y: Range::from_pos_and_len(rect.bottom() + PAD * 2.0 + BUTTON_DIM[1] + FIELD_H / 2.0,
+                                       FIELD_H),
+        };
+        let font_size = style.label_font_size(&ui.theme);
+        let border = style.border(&ui.theme);
+        widget::BorderedRectangle::new(field_rect.dim())
+            .xy(field_rect.xy())
+            .color(style.button_color(&ui.theme))
+            .border(border)
+            .border_color(style.border_color(&ui.theme))
+            .parent(state.ids.modal)
+            .set(state.ids.field, ui);
+        let text_rect = Rect {
+            x: field_rect.x.pad(border + PAD / 2.0),
+            y: Range::from_pos_and_len(field_rect.y.middle(), font_size as Scalar + 1.0),
+        };
+        let edited = widget::TextEdit::new(self.text)
+            .wh(text_rect.dim())
+            .xy(text_rect.xy())
+            .font_size(font_size)
+            .color(style.label_color(&ui.theme))
+            .parent(state.ids.field)
+            .set(state.ids.field_text, ui);
+        if is_opening {
+            ui.focus_keyboard(state.ids.field_text);
+        }
+        let entered = ui.widget_input(state.ids.field_text).presses().key().any(|press| {
+            press.key == input::Key::Return
+        });
+
+        let ids = &state.ids;
+        let ok = set_button(ids.ok, ids.modal, self.ok_label, rect, 0, style, ui);
+        let cancel = set_button(ids.cancel, ids.modal, self.cancel_label, rect, 1, style, ui);
+        let response = match (ok || entered, cancel || dismissed) {
+            (true, _) => Some(Response::Ok),
+            (false, true) => Some(Response::Cancel),
+            _ => None,
+        };
+        (edited, response)
+    }
+
+}
+
+
+/// Set the dialog's `Modal` layer, its panel and its message.
+///
+/// Returns whether or not the user requested that the layer be dismissed.
+fn set_panel(ids: &Ids, rect: Rect, style: &Style, message: &str, ui: &mut ::UiCell) -> bool {
+    let dismissed = widget::Modal::new()
+        .backdrop_color(style.backdrop_color(&ui.theme))
+        .set(ids.modal, ui)
+        .is_some();
+
+    widget::BorderedRectangle::new(rect.dim())
+        .xy(rect.xy())
+        .color(style.color(&ui.theme))
+        .border(style.border(&ui.theme))
+        .border_color(style.border_color(&ui.theme))
+        .parent(ids.modal)
+        .set(ids.panel, ui);
+
+    let message_w = rect.x.pad(PAD).len();
+    widget::Text::new(message)
+        .w(message_w)
+        .wrap_by_word()
+        .font_size(style.label_font_size(&ui.theme))
+        .color(style.label_color(&ui.theme))
+        .top_left_with_margin_on(ids.panel, PAD)
+        .parent(ids.panel)
+        .set(ids.message, ui);
+
+    dismissed
+}
+
+/// Set one of the buttons along the bottom of the panel, counting from the right.
+///
+/// Returns whether or not the button was clicked.
+fn set_button(id: widget::Id,
+              modal: widget::Id,
+              label: &str,
+              rect: Rect,
+              index_from_right: usize,
+              style: &Style,
+              ui: &mut ::UiCell) -> bool
+{
+    let x = rect.right() - (PAD + BUTTON_DIM[0]) * (index_from_right + 1) as Scalar
+        + BUTTON_DIM[0] / 2.0;
+    let y = rect.bottom() + PAD + BUTTON_DIM[1] / 2.0;
+    widget::Button::new()
+        .label(label)
+        .label_color(style.label_color(&ui.theme))
+        .label_font_size(style.label_font_size(&ui.theme))
+        .color(style.button_color(&ui.theme))
+        .wh(BUTTON_DIM)
+        .x_y(x, y)
+        .parent(modal)
+        .set(id, ui)
+        .was_clicked()
+}
@@ -0,0 +1,942 @@
+//! A docking manager that arranges panels into resizable splits, tab stacks and floating windows.
+
+use {Color, Colorable, FontSize, Labelable, Positionable, Sizeable, UiCell, Widget};
+use color;
+use drag_drop;
+use event;
+use fnv;
+use input;
+use position::{Axis, Dimensions, Point, Range, Rect, Scalar};
+use std;
+use widget;
+
+
+/// Uniquely identifies a panel within a docking `Layout`.
+///
+/// The ids are chosen by the application and are used to look up the title of each panel.
+pub type PanelId = u64;
+
+/// The arrangement of panels managed by a **Dock**.
+///
+/// The **Dock** never mutates the layout itself. Instead, a new layout is produced whenever the
+/// user rearranges the panels so that the application may store it. When the `serialize` feature
+/// is enabled the layout may be saved and restored using any `serde` format.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Layout {
+    /// The tree of panels docked within the **Dock**'s area, if any.
+    pub root: Option<Node>,
+    /// Panels torn off into floating windows, from bottom to top.
+    pub floating: Vec<Floating>,
+}
+
+/// A node within a docking `Layout` tree.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Node {
+    /// A stack of panels, only one of which is visible at a time, selected via a tab bar.
+    Tabs(Stack),
+    /// Child nodes laid out from left to right, each with a weight relative to the others.
+    Row(Vec<(Scalar, Node)>),
+    /// Child nodes laid out from top to bottom, each with a weight relative to the others.
+    Column(Vec<(Scalar, Node)>),
+}
+
+/// A stack of panels sharing the same area.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Stack {
+    /// The panels within the stack in the order in which their tabs appear.
+    pub panels: Vec<PanelId>,
+    /// The index of the visible panel.
+    pub selected: usize,
+}
+
+/// A window floating above the docked panels.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Floating {
+    /// The position of the centre of the window.
+    pub xy: Point,
+    /// The dimensions of the window, including its title bar.
+    pub dim: Dimensions,
+    /// The panels within the window.
+    pub node: Node,
+}
+
+/// The area of a panel onto which another panel may be docked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Zone {
+    /// Split the panel, placing the docked panel to its left.
+    Left,
+    /// Split the panel, placing the docked panel to its right.
+    Right,
+    /// Split the panel, placing the docked panel above it.
+    Top,
+    /// Split the panel, placing the docked panel below it.
+    Bottom,
+    /// Add the docked panel as a tab within the panel's stack.
+    Center,
+}
+
+
+/// A docking manager for a set of panels.
+///
+/// Each panel is identified by a `PanelId` and shown with a tab bearing its title. Panels may be
+/// dragged by their tab and dropped onto the left, right, top, bottom or centre of another panel,
+/// either splitting that panel or stacking alongside it as a tab. The zone under the cursor is
+/// highlighted while dragging. Dropping a panel outside of any panel tears it off into a floating
+/// window, which may itself be moved by its title bar.
+///
+/// The widgets within each visible panel are set by the application via the produced `Panels`.
+#[derive(Clone, WidgetCommon_)]
+pub struct Dock<'a> {
+    /// Common widget building params for the **Dock**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Dock**.
+    pub style: Style,
+    layout: &'a Layout,
+    titles: &'a [(PanelId, &'a str)],
+}
+
+/// Unique styling for the **Dock**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The height of the tab bar at the top of each stack.
+    #[conrod(default = "24.0")]
+    pub tab_bar_height: Option<Scalar>,
+    /// The maximum width of each tab.
+    #[conrod(default = "120.0")]
+    pub max_tab_width: Option<Scalar>,
+    /// The color of the tab bar behind the tabs.
+    #[conrod(default = "theme.border_color")]
+    pub tab_bar_color: Option<Color>,
+    /// The color of each unselected tab.
+    #[conrod(default = "theme.shape_color")]
+    pub tab_color: Option<Color>,
+    /// The color of the selected tab of each stack.
+    #[conrod(default = "theme.background_color")]
+    pub selected_tab_color: Option<Color>,
+    /// The color of the tab labels.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size of the tab labels.
+    #[conrod(default = "theme.font_size_small")]
+    pub label_font_size: Option<FontSize>,
+    /// The thickness of the dividers between split panels.
+    #[conrod(default = "4.0")]
+    pub divider_thickness: Option<Scalar>,
+    /// The color of the dividers between split panels.
+    #[conrod(default = "theme.border_color")]
+    pub divider_color: Option<Color>,
+    /// The height of the title bar of each floating window.
+    #[conrod(default = "16.0")]
+    pub title_bar_height: Option<Scalar>,
+    /// The color of the title bar of each floating window.
+    #[conrod(default = "theme.border_color")]
+    pub title_bar_color: Option<Color>,
+    /// The color of the drop zone overlay shown while dragging a panel.
+    #[conrod(default = "color::LIGHT_BLUE.alpha(0.4)")]
+    pub overlay_color: Option<Color>,
+}
+
+widget_ids! {
+    struct Ids {
+        splitters[],
+        tab_bars[],
+        windows[],
+        title_bars[],
+        overlay,
+    }
+}
+
+/// The widget ids generated for each panel.
+#[derive(Copy, Clone, Debug)]
+struct PanelIds {
+    content: widget::Id,
+    tab: widget::Id,
+}
+
+/// Represents the state of the **Dock** widget.
+pub struct State {
+    ids: Ids,
+    panels: fnv::FnvHashMap<PanelId, PanelIds>,
+}
+
+/// The payload dragged from a panel's tab.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DragPanel {
+    /// The panel being dragged.
+    pub panel: PanelId,
+}
+
+/// The event produced by the **Dock**.
+///
+/// The panels are used to set the widgets within each visible panel. The new layout is produced
+/// whenever the user resizes, selects, docks, floats or moves a panel.
+pub type Event = (Panels, Option<Layout>);
+
+/// An iterator yielding each visible `Panel` of a **Dock**.
+#[derive(Clone, Debug)]
+pub struct Panels {
+    panels: std::vec::IntoIter<Panel>,
+}
+
+/// A single visible panel of a **Dock**, used to set the widget within the panel.
+#[derive(Copy, Clone, Debug)]
+pub struct Panel {
+    /// The id of the panel within the `Layout`.
+    pub id: PanelId,
+    /// The id generated for the panel's widget.
+    pub widget_id: widget::Id,
+    /// The area occupied by the panel's content beneath its tab bar.
+    pub rect: Rect,
+    parent_id: widget::Id,
+}
+
+/// A change to the layout requested by the user while setting the **Dock**.
+#[derive(Clone, Debug)]
+enum Action {
+    Weights(usize, Vec<Scalar>),
+    Select(PanelId),
+    Dock(PanelId, PanelId, Zone),
+    Float(PanelId, Point, Dimensions),
+    Move(usize, Point),
+}
+
+/// The area occupied by a stack, onto which a dragged panel may be dropped.
+#[derive(Copy, Clone, Debug)]
+struct StackArea {
+    rect: Rect,
+    panel: PanelId,
+    parent_id: widget::Id,
+}
+
+/// The state threaded through the recursive setting of each node.
+struct Context<'a> {
+    style: &'a Style,
+    titles: &'a [(PanelId, &'a str)],
+    splitter_ids: Vec<widget::Id>,
+    tab_bar_ids: Vec<widget::Id>,
+    panel_ids: fnv::FnvHashMap<PanelId, PanelIds>,
+    next_splitter: usize,
+    next_stack: usize,
+    actions: Vec<Action>,
+    stacks: Vec<StackArea>,
+    panels: Vec<Panel>,
+}
+
+/// The distance a tab must be dragged before the drag of its panel begins.
+const DRAG_THRESHOLD: Scalar = 4.0;
+
+
+impl Layout {
+
+    /// A layout with the given node docked at its root.
+    pub fn new(root: Node) -> Self {
+        Layout {
+            root: Some(root),
+            floating: Vec::new(),
+        }
+    }
+
+    /// Whether or not the layout contains the given panel.
+    pub fn contains(&self, panel: PanelId) -> bool {
+        self.nodes().any(|node| node.contains(panel))
+    }
+
+    /// All panels within the layout, docked panels first.
+    pub fn panels(&self) -> Vec<PanelId> {
+        let mut panels = Vec::new();
+        for node in self.nodes() {
+            node.collect_panels(&mut panels);
+        }
+        panels
+    }
+
+    /// Remove the given panel from the layout.
+    ///
+    /// Stacks left without panels are removed, and splits left with a single child are replaced
+    /// by that child. Returns `false` if the layout did not contain the panel.
+    pub fn remove(&mut self, panel: PanelId) -> bool {
+        if !self.contains(panel) {
+            return false;
+        }
+        self.root = self.root.take().and_then(|node| node.without(panel));
+        let floating = std::mem::replace(&mut self.floating, Vec::new());
+        self.floating = floating.into_iter()
+            .filter_map(|Floating { xy, dim, node }| {
+                node.without(panel).map(|node| Floating { xy: xy, dim: dim, node: node })
+            })
+            .collect();
+        true
+    }
+
+    /// Make the given panel the visible panel of its stack.
+    ///
+    /// Returns `false` if the layout did not contain the panel.
+    pub fn select(&mut self, panel: PanelId) -> bool {
+        self.nodes_mut().any(|node| node.select(panel))
+    }
+
+    /// Move the given panel into the `zone` of the stack containing the `target` panel.
+    ///
+    /// Docking to the `Center` adds the panel as the selected tab of the target's stack. Docking
+    /// to a side splits the target's stack, giving the panel half of the stack's area. If the
+    /// target's stack is already split along the same axis, the panel is added to that split
+    /// instead of nesting a new one.
+    ///
+    /// Returns `false` and leaves the layout unchanged if either panel is missing, or if docking
+    /// would leave the layout as it was.
+    pub fn dock(&mut self, panel: PanelId, target: PanelId, zone: Zone) -> bool {
+        let target_stack = match self.nodes().filter_map(|node| node.stack_of(target)).next() {
+            Some(stack) => stack.clone(),
+            None => return false,
+        };
+        if !self.contains(panel) {
+            return false;
+        }
+
+        // When docking a panel beside its own stack, target one of the panel's neighbours.
+        let target = match target_stack.panels.contains(&panel) {
+            false => target,
+            true => match zone {
+                Zone::Center => return false,
+                _ => match target_stack.panels.iter().find(|&&p| p != panel) {
+                    Some(&other) => other,
+                    None => return false,
+                },
+            },
+        };
+
+        self.remove(panel);
+        self.nodes_mut().any(|node| node.insert(panel, target, zone))
+    }
+
+    /// Tear the given panel off into a new floating window with the given position and size.
+    ///
+    /// Returns `false` if the layout did not contain the panel.
+    pub fn float(&mut self, panel: PanelId, xy: Point, dim: Dimensions) -> bool {
+        if !self.remove(panel) {
+            return false;
+        }
+        self.floating.push(Floating {
+            xy: xy,
+            dim: dim,
+            node: Node::tabs(vec![panel]),
+        });
+        true
+    }
+
+    /// The docked root node followed by the node of each floating window.
+    fn nodes<'a>(&'a self) -> Box<Iterator<Item=&'a Node> + 'a> {
+        Box::new(self.root.iter().chain(self.floating.iter().map(|f| &f.node)))
+    }
+
+    /// The docked root node followed by the node of each floating window.
+    fn nodes_mut<'a>(&'a mut self) -> Box<Iterator<Item=&'a mut Node> + 'a> {
+        Box::new(self.root.iter_mut().chain(self.floating.iter_mut().map(|f| &mut f.node)))
+    }
+
+    /// The children of the `n`th split in the order in which the **Dock** visits them.
+    fn split_mut(&mut self, mut n: usize) -> Option<&mut Vec<(Scalar, Node)>> {
+        for node in self.nodes_mut() {
+            if let Some(children) = node.split_mut(&mut n) {
+                return Some(children);
+            }
+        }
+        None
+    }
+
+}
+
+impl Node {
+
+    /// A stack of the given panels with the first panel selected.
+    pub fn tabs(panels: Vec<PanelId>) -> Self {
+        Node::Tabs(Stack { panels: panels, selected: 0 })
+    }
+
+    /// Whether or not the node contains the given panel.
+    pub fn contains(&self, panel: PanelId) -> bool {
+        self.stack_of(panel).is_some()
+    }
+
+    /// The stack containing the given panel.
+    fn stack_of(&self, panel: PanelId) -> Option<&Stack> {
+        match *self {
+            Node::Tabs(ref stack) => match stack.panels.contains(&panel) {
+                true => Some(stack),
+                false => None,
+            },
+            Node::Row(ref children) | Node::Column(ref children) =>
+                children.iter().filter_map(|&(_, ref child)| child.stack_of(panel)).next(),
+        }
+    }
+
+    fn collect_panels(&self, panels: &mut Vec<PanelId>) {
+        match *self {
+            Node::Tabs(ref stack) => panels.extend(stack.panels.iter().cloned()),
+            Node::Row(ref children) | Node::Column(ref children) =>
+                for &(_, ref child) in children {
+                    child.collect_panels(panels);
+                },
+        }
+    }
+
+    fn select(&mut self, panel: PanelId) -> bool {
+        match *self {
+            Node::Tabs(ref mut stack) => match stack.panels.iter().position(|&p| p == panel) {
+                Some(i) => {
+                    stack.selected = i;
+                    true
+                },
+                None => false,
+            },
+            Node::Row(ref mut children) | Node::Column(ref mut children) =>
+                children.iter_mut().any(|&mut (_, ref mut child)| child.select(panel)),
+        }
+    }
+
+    /// The node without the given panel, or `None` if nothing would remain.
+    fn without(self, panel: PanelId) -> Option<Node> {
+        match self {
+            Node::Tabs(mut stack) => {
+                if let Some(i) = stack.panels.iter().position(|&p| p == panel) {
+                    stack.panels.remove(i);
+                    if stack.selected > i || stack.selected == stack.panels.len() {
+                        stack.selected = stack.selected.saturating_sub(1);
+                    }
+                }
+                match stack.panels.is_empty() {
+                    true => None,
+                    false => Some(Node::Tabs(stack)),
+                }
+            },
+            Node::Row(children) => Node::split(Axis::X, children, panel),
+            Node::Column(children) => Node::split(Axis::Y, children, panel),
+        }
+    }
+
+    /// Rebuild a split without the given panel.
+    ///
+    /// Children that split along the same axis are flattened into the split, scaling their
+    /// weights so that they occupy the same area.
+    fn split(axis: Axis, children: Vec<(Scalar, Node)>, panel: PanelId) -> Option<Node> {
+        let mut remaining = Vec::with_capacity(children.len());
+        for (weight, child) in children {
+            match (axis, child.without(panel)) {
+                (_, None) => (),
+                (Axis::X, Some(Node::Row(grandchildren))) |
+                (Axis::Y, Some(Node::Column(grandchildren))) => {
+                    let total = grandchildren.iter().fold(0.0, |total, &(w, _)| total + w);
+                    for (w, grandchild) in grandchildren {
+                        let w = match total > 0.0 {
+                            true => weight * w / total,
+                            false => weight,
+                        };
+                        remaining.push((w, grandchild));
+                    }
+                },
+                (_, Some(child)) => remaining.push((weight, child)),
+            }
+        }
+        match remaining.len() {
+            0 => None,
+            1 => remaining.pop().map(|(_, child)| child),
+            _ => Some(match axis {
+                Axis::X => Node::Row(remaining),
+                Axis::Y => Node::Column(remaining),
+            }),
+        }
+    }
+
+    /// Insert the panel into the zone of the stack containing the `target`.
+    fn insert(&mut self, panel: PanelId, target: PanelId, zone: Zone) -> bool {
+        let is_target_stack = match *self {
+            Node::Tabs(ref stack) => Some(stack.panels.contains(&target)),
+            _ => None,
+        };
+        match is_target_stack {
+            Some(false) => return false,
+            Some(true) => {
+                if let (Zone::Center, &mut Node::Tabs(ref mut stack)) = (zone, &mut *self) {
+                    stack.panels.push(panel);
+                    stack.selected = stack.panels.len() - 1;
+                    return true;
+                }
+                // Split the target's stack along the zone's axis.
+                let target_node = std::mem::replace(self, Node::Row(Vec::new()));
+                let new_node = Node::tabs(vec![panel]);
+                let children = match zone {
+                    Zone::Left | Zone::Top => vec![(1.0, new_node), (1.0, target_node)],
+                    _ => vec![(1.0, target_node), (1.0, new_node)],
+                };
+                *self = match zone.axis() {
+                    Some(Axis::X) => Node::Row(children),
+                    _ => Node::Column(children),
+                };
+                return true;
+            },
+            None => (),
+        }
+
+        let (axis, children) = match *self {
+            Node::Row(ref mut children) => (Axis::X, children),
+            Node::Column(ref mut children) => (Axis::Y, children),
+            Node::Tabs(_) => return false,
+        };
+        for i in 0..children.len() {
+            let is_target = match children[i].1 {
+                Node::Tabs(ref stack) => stack.panels.contains(&target),
+                _ => false,
+            };
+            // Add the panel to this split rather than nesting a new split along the same axis.
+            if is_target && zone.axis() == Some(axis) {
+                let weight = children[i].0 / 2.0;
+                children[i].0 = weight;
+                let index = match zone {
+                    Zone::Left | Zone::Top => i,
+                    _ => i + 1,
+                };
+                children.insert(index, (weight, Node::tabs(vec![panel])));
+                return true;
+            }
+            if children[i].1.insert(panel, target, zone) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The children of the `n`th split found by a depth-first traversal of the node.
+    fn split_mut(&mut self, n: &mut usize) -> Option<&mut Vec<(Scalar, Node)>> {
+        match *self {
+            Node::Tabs(_) => None,
+            Node::Row(ref mut children) | Node::Column(ref mut children) => {
+                if *n == 0 {
+                    return Some(children);
+                }
+                *n -= 1;
+                for &mut (_, ref mut child) in children.iter_mut() {
+                    if let Some(children) = child.split_mut(n) {
+                        return Some(children);
+                    }
+                }
+                None
+            },
+        }
+    }
+
+    /// The number of splits and stacks within the node.
+    fn count(&self) -> (usize, usize) {
+        match *self {
+            Node::Tabs(_) => (0, 1),
+            Node::Row(ref children) | Node::Column(ref children) =>
+                children.iter().fold((1, 0), |(splits, stacks), &(_, ref child)| {
+                    let (s, t) = child.count();
+                    (splits + s, stacks + t)
+                }),
+        }
+    }
+
+}
+
+impl Zone {
+
+    /// The axis along which docking to the zone splits a stack.
+    fn axis(&self) -> Option<Axis> {
+        match *self {
+            Zone::Left | Zone::Right => Some(Axis::X),
+            Zone::Top | Zone::Bottom => Some(Axis::Y),
+            Zone::Center => None,
+        }
+    }
+
+}
+
+
+impl<'a> Dock<'a> {
+
+    /// Begin building a **Dock** arranging its panels according to the given `layout`.
+    ///
+    /// The `titles` are displayed within the tab of each panel.
+    pub fn new(layout: &'a Layout, titles: &'a [(PanelId, &'a str)]) -> Self {
+        Dock {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            layout: layout,
+            titles: titles,
+        }
+    }
+
+    builder_methods!{
+        pub tab_bar_height { style.tab_bar_height = Some(Scalar) }
+        pub max_tab_width { style.max_tab_width = Some(Scalar) }
+        pub tab_bar_color { style.tab_bar_color = Some(Color) }
+        pub tab_color { style.tab_color = Some(Color) }
+        pub selected_tab_color { style.selected_tab_color = Some(Color) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub divider_thickness { style.divider_thickness = Some(Scalar) }
+        pub divider_color { style.divider_color = Some(Color) }
+        pub title_bar_height { style.title_bar_height = Some(Scalar) }
+        pub title_bar_color { style.title_bar_color = Some(Color) }
+        pub overlay_color { style.overlay_color = Some(Color) }
+    }
+
+}
+
+impl<'a> Widget for Dock<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            panels: fnv::FnvHashMap::default(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Dock { layout, titles, .. } = self;
+
+        // Ensure there are enough widget ids for every split, stack, window and panel.
+        let (num_splitters, num_stacks) = layout.nodes()
+            .fold((0, 0), |(splits, stacks), node| {
+                let (s, t) = node.count();
+                (splits + s, stacks + t)
+            });
+        let num_windows = layout.floating.len();
+        let panels = layout.panels();
+        if state.ids.splitters.len() < num_splitters
+        || state.ids.tab_bars.len() < num_stacks
+        || state.ids.windows.len() < num_windows
+        || panels.iter().any(|panel| !state.panels.contains_key(panel))
+        {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.splitters.resize(num_splitters, id_gen);
+                state.ids.tab_bars.resize(num_stacks, id_gen);
+                state.ids.windows.resize(num_windows, id_gen);
+                state.ids.title_bars.resize(num_windows, id_gen);
+                for &panel in &panels {
+                    state.panels.entry(panel).or_insert_with(|| PanelIds {
+                        content: id_gen.next(),
+                        tab: id_gen.next(),
+                    });
+                }
+            });
+        }
+
+        let mut cx = Context {
+            style: &style,
+            titles: titles,
+            splitter_ids: state.ids.splitters.iter().cloned().collect(),
+            tab_bar_ids: state.ids.tab_bars.iter().cloned().collect(),
+            panel_ids: state.panels.clone(),
+            next_splitter: 0,
+            next_stack: 0,
+            actions: Vec::new(),
+            stacks: Vec::new(),
+            panels: Vec::new(),
+        };
+
+        if let Some(ref root) = layout.root {
+            set_node(root, rect, id, &mut cx, ui);
+        }
+
+        // Draw each floating window above the docked panels.
+        for (i, floating) in layout.floating.iter().enumerate() {
+            let window_id = state.ids.windows[i];
+            let title_bar_id = state.ids.title_bars[i];
+            let window_rect = Rect::from_xy_dim(floating.xy, floating.dim);
+            widget::Rectangle::fill(floating.dim)
+                .xy(floating.xy)
+                .color(style.tab_bar_color(&ui.theme))
+                .floating(true)
+                .parent(id)
+                .set(window_id, ui);
+
+            let title_bar_height = style.title_bar_height(&ui.theme);
+            let title_bar_bottom = window_rect.top() - title_bar_height;
+            let title_bar_rect = Rect {
+                x: window_rect.x,
+                y: Range::new(title_bar_bottom, window_rect.top()),
+            };
+            widget::Rectangle::fill(title_bar_rect.dim())
+                .xy(title_bar_rect.xy())
+                .color(style.title_bar_color(&ui.theme))
+                .parent(window_id)
+                .set(title_bar_id, ui);
+            for drag in ui.widget_input(title_bar_id).drags().left() {
+                cx.actions.push(Action::Move(i, drag.delta_xy));
+            }
+
+            let node_rect = Rect {
+                x: window_rect.x,
+                y: Range::new(window_rect.bottom(), title_bar_bottom),
+            };
+            set_node(&floating.node, node_rect, window_id, &mut cx, ui);
+        }
+
+        // Highlight the zone under a panel that is being dragged, docking the panel when dropped.
+        let maybe_drag = ui.drag_drop().and_then(|drag_drop| {
+            drag_drop.payload::<DragPanel>()
+                .map(|payload| (payload.panel, drag_drop.xy, drag_drop.phase, drag_drop.target))
+        });
+        if let Some((panel, xy, phase, maybe_target)) = maybe_drag {
+            // Floating windows are searched first as they are drawn above the docked panels.
+            let maybe_area = cx.stacks.iter().rev().find(|area| area.rect.is_over(xy)).cloned();
+            if let (true, Some(area)) = (layout.contains(panel), maybe_area) {
+                let zone = zone(area.rect, xy);
+                match phase {
+                    drag_drop::Phase::Dragging => {
+                        // The overlay is a graphic of the target so that it does not become
+                        // the drop target itself.
+                        let overlay_rect = zone_rect(area.rect, zone);
+                        let overlay = widget::Rectangle::fill(overlay_rect.dim())
+                            .xy(overlay_rect.xy())
+                            .color(style.overlay_color(&ui.theme))
+                            .depth(-1.0)
+                            .parent(area.parent_id);
+                        match maybe_target {
+                            Some(target) => {
+                                overlay.graphics_for(target).set(state.ids.overlay, ui);
+                                ui.accept_drag(target, true);
+                            },
+                            None => {
+                                overlay.set(state.ids.overlay, ui);
+                            },
+                        }
+                    },
+                    drag_drop::Phase::Dropped(target) => {
+                        if ui.take_drag_payload(target).is_some() {
+                            cx.actions.push(Action::Dock(panel, area.panel, zone));
+                        }
+                    },
+                }
+            }
+        }
+
+        let Context { actions, panels, .. } = cx;
+        let maybe_layout = match actions.is_empty() {
+            true => None,
+            false => {
+                let mut new_layout = layout.clone();
+                for action in actions {
+                    apply(&mut new_layout, action);
+                }
+                match new_layout != *layout {
+                    true => Some(new_layout),
+                    false => None,
+                }
+            },
+        };
+        (Panels { panels: panels.into_iter() }, maybe_layout)
+    }
+
+}
+
+
+impl Iterator for Panels {
+    type Item = Panel;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.panels.next()
+    }
+}
+
+impl Panel {
+
+    /// Sets the given widget as the widget to use for the panel.
+    ///
+    /// Sets the position, dimensions and parent of the widget before setting it within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        widget
+            .wh(self.rect.dim())
+            .xy(self.rect.xy())
+            .parent(self.parent_id)
+            .set(self.widget_id, ui)
+    }
+
+}
+
+
+/// Set the widgets for the given node within the given rectangle.
+fn set_node(node: &Node, rect: Rect, parent_id: widget::Id, cx: &mut Context, ui: &mut UiCell) {
+    let (axis, children) = match *node {
+        Node::Tabs(ref stack) => return set_stack(stack, rect, parent_id, cx, ui),
+        Node::Row(ref children) => (Axis::X, children),
+        Node::Column(ref children) => (Axis::Y, children),
+    };
+    let index = cx.next_splitter;
+    cx.next_splitter += 1;
+    let splitter_id = cx.splitter_ids[index];
+    let weights: Vec<Scalar> = children.iter().map(|&(weight, _)| weight).collect();
+    let (panes, maybe_weights) = widget::Splitter::new(axis, &weights)
+        .divider_thickness(cx.style.divider_thickness(&ui.theme))
+        .divider_color(cx.style.divider_color(&ui.theme))
+        .wh(rect.dim())
+        .xy(rect.xy())
+        .parent(parent_id)
+        .set(splitter_id, ui);
+    if let Some(weights) = maybe_weights {
+        cx.actions.push(Action::Weights(index, weights));
+    }
+    for (pane, &(_, ref child)) in panes.zip(children) {
+        set_node(child, pane.rect, splitter_id, cx, ui);
+    }
+}
+
+/// Set the tab bar for the given stack along with the visible panel beneath it.
+fn set_stack(stack: &Stack, rect: Rect, parent_id: widget::Id, cx: &mut Context, ui: &mut UiCell) {
+    let tab_bar_id = cx.tab_bar_ids[cx.next_stack];
+    cx.next_stack += 1;
+
+    let tab_bar_height = cx.style.tab_bar_height(&ui.theme);
+    let tab_bar_bottom = rect.top() - tab_bar_height;
+    let tab_bar_rect = Rect { x: rect.x, y: Range::new(tab_bar_bottom, rect.top()) };
+    widget::Rectangle::fill(tab_bar_rect.dim())
+        .xy(tab_bar_rect.xy())
+        .color(cx.style.tab_bar_color(&ui.theme))
+        .parent(parent_id)
+        .set(tab_bar_id, ui);
+
+    // A stack without any panels, e.g. from a hand-built or deserialized layout, is left empty.
+    let last = match stack.panels.len() {
+        0 => return,
+        len => len - 1,
+    };
+    let selected = std::cmp::min(stack.selected, last);
+
+    let num_tabs = stack.panels.len() as Scalar;
+    let max_tab_width = cx.style.max_tab_width(&ui.theme);
+    let tab_width = if tab_bar_rect.w() / num_tabs < max_tab_width {
+        tab_bar_rect.w() / num_tabs
+    } else {
+        max_tab_width
+    };
+
+    for (i, &panel) in stack.panels.iter().enumerate() {
+        let ids = cx.panel_ids[&panel];
+        let title = cx.titles.iter()
+            .find(|&&(p, _)| p == panel)
+            .map(|&(_, title)| title)
+            .unwrap_or("");
+        let color = match i == selected {
+            true => cx.style.selected_tab_color(&ui.theme),
+            false => cx.style.tab_color(&ui.theme),
+        };
+        let left = tab_bar_rect.left() + tab_width * i as Scalar;
+        let tab_rect = Rect { x: Range::new(left, left + tab_width), y: tab_bar_rect.y };
+        let clicks = widget::Button::new()
+            .label(title)
+            .label_color(cx.style.label_color(&ui.theme))
+            .label_font_size(cx.style.label_font_size(&ui.theme))
+            .color(color)
+            .wh(tab_rect.dim())
+            .xy(tab_rect.xy())
+            .parent(tab_bar_id)
+            .set(ids.tab, ui);
+        if clicks.was_clicked() && i != selected {
+            cx.actions.push(Action::Select(panel));
+        }
+
+        // Drag the panel by its tab, tearing it off if it is dropped where it cannot be docked.
+        let events: Vec<_> = ui.widget_input(ids.tab).events().collect();
+        for event in events {
+            match event {
+                event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                    let [dx, dy] = drag.total_delta_xy;
+                    if (dx * dx + dy * dy).sqrt() > DRAG_THRESHOLD {
+                        let payload = Box::new(DragPanel { panel: panel });
+                        let preview = drag_drop::Preview::Label(title.to_string());
+                        ui.start_drag(ids.tab, payload, preview);
+                    }
+                },
+                // The drag was not accepted by any panel.
+                event::Widget::DragEnd(None) => {
+                    let mouse = &ui.global_input().current.mouse;
+                    if mouse.buttons.left().is_up() {
+                        cx.actions.push(Action::Float(panel, mouse.xy, rect.dim()));
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+
+    let content_rect = Rect { x: rect.x, y: Range::new(rect.bottom(), tab_bar_bottom) };
+    cx.panels.push(Panel {
+        id: stack.panels[selected],
+        widget_id: cx.panel_ids[&stack.panels[selected]].content,
+        rect: content_rect,
+        parent_id: parent_id,
+    });
+    cx.stacks.push(StackArea {
+        rect: rect,
+        panel: stack.panels[selected],
+        parent_id: parent_id,
+    });
+}
+
+/// Apply the given user action to the layout.
+fn apply(layout: &mut Layout, action: Action) {
+    match action {
+        Action::Weights(index, weights) => if let Some(children) = layout.split_mut(index) {
+            for (&mut (ref mut weight, _), new_weight) in children.iter_mut().zip(weights) {
+                *weight = new_weight;
+            }
+        },
+        Action::Select(panel) => {
+            layout.select(panel);
+        },
+        Action::Dock(panel, target, zone) => {
+            layout.dock(panel, target, zone);
+        },
+        Action::Float(panel, xy, dim) => {
+            layout.float(panel, xy, dim);
+        },
+        Action::Move(i, delta) => if let Some(floating) = layout.floating.get_mut(i) {
+            floating.xy = [floating.xy[0] + delta[0], floating.xy[1] + delta[1]];
+        },
+    }
+}
+
+/// The zone of the given panel rectangle over which the point lies.
+///
+/// A point within the outer quarter of the rectangle along either axis lies within the zone of
+/// the nearest edge. Otherwise the point lies within the `Center` zone.
+pub fn zone(rect: Rect, xy: Point) -> Zone {
+    let u = (xy[0] - rect.left()) / rect.w();
+    let v = (xy[1] - rect.bottom()) / rect.h();
+    let edges = [(u, Zone::Left), (1.0 - u, Zone::Right), (v, Zone::Bottom), (1.0 - v, Zone::Top)];
+    let mut nearest = (0.25, Zone::Center);
+    for &(distance, zone) in edges.iter() {
+        if distance < nearest.0 {
+            nearest = (distance, zone);
+        }
+    }
+    nearest.1
+}
+
+/// The area that a panel docked to the given zone of the panel rectangle would occupy.
+pub fn zone_rect(rect: Rect, zone: Zone) -> Rect {
+    let (x, y) = (rect.x.middle(), rect.y.middle());
+    match zone {
+        Zone::Left => Rect { x: Range::new(rect.left(), x), y: rect.y },
+        Zone::Right => Rect { x: Range::new(x, rect.right()), y: rect.y },
+        Zone::Bottom => Rect { x: rect.x, y: Range::new(rect.bottom(), y) },
+        Zone::Top => Rect { x: rect.x, y: Range::new(y, rect.top()) },
+        Zone::Center => rect,
+    }
+}
@@ -0,0 +1,149 @@
+//! A widget that displays the preview of a payload that is being dragged.
+
+use {Color, Colorable, FontSize, Borderable, Point, Sizeable, Theme};
+use drag_drop;
+use graph;
+use position::Scalar;
+use text;
+use widget::{self, Widget};
+
+
+/// Displays the `drag_drop::Preview` of the payload that is currently being dragged (if any)
+/// beneath the mouse cursor.
+///
+/// This widget should be instantiated once per `Ui::set_widgets`, usually after all other
+/// widgets. The preview is drawn within a floating rectangle on top of all other widgets so that
+/// it does not obscure the widget under the cursor.
+#[derive(Clone, WidgetCommon_)]
+pub struct DragPreview {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **DragPreview**.
+    pub style: Style,
+}
+
+/// Unique styling for the **DragPreview** widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the preview's rectangle surface.
+    #[conrod(default = "theme.shape_color.alpha(0.8)")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the preview's rectangle.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the preview's border.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the preview's label.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size for the preview's label.
+    #[conrod(default = "theme.font_size_small")]
+    pub label_font_size: Option<FontSize>,
+    /// The font used for the preview's label.
+    #[conrod(default = "theme.font_id")]
+    pub label_font_id: Option<Option<text::font::Id>>,
+    /// The padding between the edge of the preview's rectangle and its label.
+    #[conrod(default = "4.0")]
+    pub padding: Option<Scalar>,
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        label,
+    }
+}
+
+/// Unique state for the **DragPreview** widget.
+pub struct State {
+    ids: Ids,
+}
+
+
+impl DragPreview {
+
+    /// Construct a new **DragPreview** widget.
+    pub fn new() -> Self {
+        DragPreview {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+        }.w_h(0.0, 0.0)
+    }
+
+    /// Specify the font used for displaying the label.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for DragPreview {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        fn is_over_widget(_: &graph::Container, _: Point, _: &Theme) -> widget::IsOver {
+            false.into()
+        }
+        is_over_widget
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, ui, .. } = args;
+
+        let (label, xy) = match ui.drag_drop() {
+            Some(drag_drop) if drag_drop.is_dragging() => match drag_drop.preview {
+                drag_drop::Preview::Label(ref label) => (label.clone(), drag_drop.xy),
+                drag_drop::Preview::None => return,
+            },
+            _ => return,
+        };
+
+        let label_style = widget::floating_label::Style {
+            color: style.color(&ui.theme),
+            border: style.border(&ui.theme),
+            border_color: style.border_color(&ui.theme),
+            text_color: style.label_color(&ui.theme),
+            font_size: style.label_font_size(&ui.theme),
+            maybe_font_id: style.label_font_id(&ui.theme),
+            line_spacing: widget::text::Style::default().line_spacing(&ui.theme),
+            padding: style.padding(&ui.theme),
+        };
+        widget::floating_label::set(&label, xy, label_style, id,
+                                    state.ids.rectangle, state.ids.label, ui);
+    }
+
+}
+
+
+impl Colorable for DragPreview {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl Borderable for DragPreview {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
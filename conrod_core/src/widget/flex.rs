@@ -0,0 +1,498 @@
+//! A container that lays out a sequence of widgets along a row or column, in the manner of a CSS
+//! flexbox.
+
+use {Positionable, Sizeable, UiCell, Widget};
+use position::{Axis, Dimensions, Point, Range, Rect, Scalar};
+use std;
+use utils;
+use widget;
+use widget::record::{self, Records};
+
+
+/// A container that lays out a sequence of widgets along a row or column.
+///
+/// Like the `List`, the **Flex** generates a `widget::Id` for each of its items. Each item is
+/// positioned and sized automatically as it is set via `Item::set`:
+///
+/// - Items begin at their *basis* length along the main axis, which is measured from the widget's
+///   own size unless given via `Item::basis`, and is clamped via `Item::min` and `Item::max`.
+/// - Any remaining space is distributed between items according to their `Item::grow` factors.
+/// - If the items overflow the container, they are shrunk according to their `Item::shrink`
+///   factors, unless the **Flex** is wrapping in which case the items flow onto a new line.
+/// - Items are aligned along the cross axis via `Flex::cross_align` or `Item::align`.
+///
+/// As items are only measured when they are set, the size of each item that follows the item
+/// being set is remembered from the previous update. Whenever a measurement changes, the **Flex**
+/// requests another update via `UiCell::request_redraw_at` so that the layout settles without
+/// waiting for further input.
+#[derive(Clone, WidgetCommon_)]
+#[allow(missing_copy_implementations)]
+pub struct Flex {
+    /// Common widget building params for the **Flex**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Flex**.
+    pub style: Style,
+    num_items: usize,
+    axis: Axis,
+}
+
+/// Unique styling for the **Flex**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The space between each item along the main axis.
+    #[conrod(default = "0.0")]
+    pub spacing: Option<Scalar>,
+    /// The space between each line of items when wrapping.
+    #[conrod(default = "0.0")]
+    pub line_spacing: Option<Scalar>,
+    /// The padding between the edge of the **Flex** and its items.
+    #[conrod(default = "0.0")]
+    pub pad: Option<Scalar>,
+    /// The alignment of each line of items along the main axis when they do not fill it.
+    #[conrod(default = "Justify::Start")]
+    pub justify: Option<Justify>,
+    /// The default alignment of items along the cross axis.
+    #[conrod(default = "CrossAlign::Start")]
+    pub cross_align: Option<CrossAlign>,
+    /// Whether items that overflow the main axis flow onto a new line.
+    #[conrod(default = "false")]
+    pub wrap: Option<bool>,
+}
+
+/// The alignment of a line of items along the main axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Justify {
+    /// Items are packed towards the start of the line, i.e. the left of a row or the top of a
+    /// column.
+    Start,
+    /// Items are packed around the middle of the line.
+    Middle,
+    /// Items are packed towards the end of the line.
+    End,
+}
+
+/// The alignment of an item within its line along the cross axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CrossAlign {
+    /// Align the item with the start of the line, i.e. the top of a row or the left of a column.
+    Start,
+    /// Align the item with the middle of the line.
+    Middle,
+    /// Align the item with the end of the line.
+    End,
+    /// Stretch the item to fill the line.
+    Stretch,
+}
+
+widget_ids! {
+    struct Ids {
+        items[],
+        records[],
+    }
+}
+
+/// Represents the state of the **Flex** widget.
+pub struct State {
+    ids: Ids,
+}
+
+/// Describes how a single item is laid out, as measured when the item was last set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Spec {
+    /// The length of the item along the main axis before growing or shrinking.
+    pub basis: Scalar,
+    /// The breadth of the item along the cross axis.
+    pub breadth: Scalar,
+    /// The share of remaining space that the item receives.
+    pub grow: Scalar,
+    /// The share of overflow that the item absorbs, relative to its basis.
+    pub shrink: Scalar,
+    /// The minimum length of the item along the main axis.
+    pub min: Scalar,
+    /// The maximum length of the item along the main axis.
+    pub max: Scalar,
+    /// Overrides the `Flex`'s `cross_align` for this item.
+    pub align: Option<CrossAlign>,
+}
+
+/// The area occupied by an item, relative to the start of the **Flex**'s main and cross axes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Slot {
+    /// The distance from the start of the main axis and the length along it.
+    pub main: Range,
+    /// The distance from the start of the cross axis and the breadth along it.
+    pub cross: Range,
+}
+
+/// The parameters that apply to the layout of all items.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Params {
+    /// The length of the main axis.
+    pub length: Scalar,
+    /// The breadth of the cross axis.
+    pub breadth: Scalar,
+    /// The space between items along the main axis.
+    pub spacing: Scalar,
+    /// The space between lines when wrapping.
+    pub line_spacing: Scalar,
+    /// The alignment of each line along the main axis.
+    pub justify: Justify,
+    /// The default alignment of items along the cross axis.
+    pub cross_align: CrossAlign,
+    /// Whether items that overflow flow onto a new line.
+    pub wrap: bool,
+}
+
+/// Yields each `Item` of a **Flex**.
+pub struct Items {
+    axis: Axis,
+    kid_area: Rect,
+    params: Params,
+    /// The spec of each item as of this and the previous update.
+    specs: Records<Spec>,
+}
+
+/// A single item of a **Flex**, used to set the item's widget.
+pub struct Item<'a> {
+    /// The index of the item within the **Flex**.
+    pub i: usize,
+    /// The id generated for the item's widget.
+    pub widget_id: widget::Id,
+    items: &'a mut Items,
+    basis: Option<Scalar>,
+    grow: Scalar,
+    shrink: Scalar,
+    min: Scalar,
+    max: Scalar,
+    align: Option<CrossAlign>,
+}
+
+impl Flex {
+
+    /// Begin building a **Flex** with the given number of items.
+    pub fn new(axis: Axis, num_items: usize) -> Self {
+        Flex {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            num_items: num_items,
+            axis: axis,
+        }
+    }
+
+    /// Begin building a **Flex** whose items flow from left to right.
+    pub fn row(num_items: usize) -> Self {
+        Flex::new(Axis::X, num_items)
+    }
+
+    /// Begin building a **Flex** whose items flow from top to bottom.
+    pub fn column(num_items: usize) -> Self {
+        Flex::new(Axis::Y, num_items)
+    }
+
+    /// Stretch the items to fill each line along the cross axis.
+    pub fn stretch(self) -> Self {
+        self.cross_align(CrossAlign::Stretch)
+    }
+
+    builder_methods!{
+        pub spacing { style.spacing = Some(Scalar) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub pad { style.pad = Some(Scalar) }
+        pub justify { style.justify = Some(Justify) }
+        pub cross_align { style.cross_align = Some(CrossAlign) }
+        pub wrap { style.wrap = Some(bool) }
+    }
+
+}
+
+impl Widget for Flex {
+    type State = State;
+    type Style = Style;
+    type Event = Items;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Flex { num_items, axis, .. } = self;
+
+        if state.ids.items.len() < num_items {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.items.resize(num_items, id_gen);
+                state.ids.records.resize(num_items, id_gen);
+            });
+        }
+
+        // Retrieve the specs that were recorded for each item during the previous update.
+        let ids: Vec<_> = state.ids.items.iter().cloned()
+            .zip(state.ids.records.iter().cloned())
+            .take(num_items)
+            .collect();
+        let specs = Records::new(id, ids, ui);
+
+        let kid_area = rect.pad(style.pad(&ui.theme));
+        let (length, breadth) = match axis {
+            Axis::X => (kid_area.w(), kid_area.h()),
+            Axis::Y => (kid_area.h(), kid_area.w()),
+        };
+        let params = Params {
+            length: length,
+            breadth: breadth,
+            spacing: style.spacing(&ui.theme),
+            line_spacing: style.line_spacing(&ui.theme),
+            justify: style.justify(&ui.theme),
+            cross_align: style.cross_align(&ui.theme),
+            wrap: style.wrap(&ui.theme),
+        };
+
+        Items {
+            axis: axis,
+            kid_area: kid_area,
+            params: params,
+            specs: specs,
+        }
+    }
+
+}
+
+
+impl Items {
+
+    /// Yield the next `Item` of the **Flex**.
+    pub fn next(&mut self) -> Option<Item> {
+        let i = self.specs.len();
+        let widget_id = match self.specs.next_id() {
+            Some(widget_id) => widget_id,
+            None => return None,
+        };
+        Some(Item {
+            i: i,
+            widget_id: widget_id,
+            items: self,
+            basis: None,
+            grow: 0.0,
+            shrink: 1.0,
+            min: 0.0,
+            max: std::f64::MAX,
+            align: None,
+        })
+    }
+
+}
+
+impl<'a> Item<'a> {
+
+    /// The length of the item along the main axis before growing or shrinking.
+    ///
+    /// By default, this is measured from the size of the item's widget.
+    pub fn basis(mut self, basis: Scalar) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// The share of the remaining space along the main axis that the item receives, relative to
+    /// the `grow` factors of the other items on the line. By default this is `0.0`.
+    pub fn grow(mut self, grow: Scalar) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// The share of any overflow along the main axis that the item absorbs, relative to the
+    /// `shrink` factors of the other items on the line. By default this is `1.0`.
+    pub fn shrink(mut self, shrink: Scalar) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// The minimum length of the item along the main axis.
+    pub fn min(mut self, min: Scalar) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// The maximum length of the item along the main axis.
+    pub fn max(mut self, max: Scalar) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Align the item along the cross axis, overriding the `Flex`'s `cross_align`.
+    pub fn align(mut self, align: CrossAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Sets the given widget as the widget to use for the item.
+    ///
+    /// Sets the position, dimensions and parent of the widget before setting it within the `Ui`.
+    pub fn set<W>(self, mut widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let Item { i, widget_id, items, basis, grow, shrink, min, max, align } = self;
+
+        // Measure the widget.
+        let (content_length, content_breadth) = {
+            let dim = record::measure(&mut widget, widget_id, ui);
+            match items.axis {
+                Axis::X => (dim[0], dim[1]),
+                Axis::Y => (dim[1], dim[0]),
+            }
+        };
+        let spec = Spec {
+            basis: basis.unwrap_or(content_length),
+            breadth: content_breadth,
+            grow: grow,
+            shrink: shrink,
+            min: min,
+            max: max,
+            align: align,
+        };
+        // Lay out all items known so far, using the previous spec for those not yet set.
+        let specs = items.specs.push(spec, ui);
+        let slot = layout(&specs, &items.params)[i];
+        let (xy, dim) = items.slot_rect(slot);
+        items.specs.set(widget, xy, dim, ui)
+    }
+
+}
+
+impl Items {
+
+    /// The absolute position and dimensions of the given slot.
+    fn slot_rect(&self, slot: Slot) -> (Point, Dimensions) {
+        let area = self.kid_area;
+        match self.axis {
+            Axis::X => {
+                let x = Range::new(area.left() + slot.main.start, area.left() + slot.main.end);
+                let y = Range::new(area.top() - slot.cross.end, area.top() - slot.cross.start);
+                ([x.middle(), y.middle()], [x.len(), y.len()])
+            },
+            Axis::Y => {
+                let x = Range::new(area.left() + slot.cross.start, area.left() + slot.cross.end);
+                let y = Range::new(area.top() - slot.main.end, area.top() - slot.main.start);
+                ([x.middle(), y.middle()], [x.len(), y.len()])
+            },
+        }
+    }
+
+}
+
+/// Lay out the given items, producing the slot occupied by each.
+pub fn layout(specs: &[Spec], params: &Params) -> Vec<Slot> {
+    let hypothetical = |spec: &Spec| utils::clamp(spec.basis, spec.min, spec.max);
+
+    // Divide the items into lines.
+    let mut lines: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut start = 0;
+    let mut line_length = 0.0;
+    for (i, spec) in specs.iter().enumerate() {
+        let length = hypothetical(spec);
+        if params.wrap && i > start && line_length + params.spacing + length > params.length {
+            lines.push(start..i);
+            start = i;
+            line_length = length;
+        } else if i == start {
+            line_length = length;
+        } else {
+            line_length += params.spacing + length;
+        }
+    }
+    if start < specs.len() {
+        lines.push(start..specs.len());
+    }
+
+    let mut slots = Vec::with_capacity(specs.len());
+    let mut line_start = 0.0;
+    for line in lines {
+        let line_specs = &specs[line];
+        let lengths = resolve_lengths(line_specs, params);
+
+        // Position the items along the main axis.
+        let gaps = (line_specs.len() - 1) as Scalar * params.spacing;
+        let free = params.length - lengths.iter().fold(0.0, |sum, l| sum + l) - gaps;
+        let mut main_start = match params.justify {
+            Justify::Start => 0.0,
+            Justify::Middle => free.max(0.0) / 2.0,
+            Justify::End => free.max(0.0),
+        };
+
+        // A single line fills the cross axis, otherwise each line fits its broadest item.
+        let line_breadth = match params.wrap {
+            false => params.breadth,
+            true => line_specs.iter().fold(0.0, |max, spec| utils::partial_max(max, spec.breadth)),
+        };
+
+        for (spec, &length) in line_specs.iter().zip(&lengths) {
+            let main = Range::new(main_start, main_start + length);
+            main_start += length + params.spacing;
+            let breadth = spec.breadth;
+            let cross_start = match spec.align.unwrap_or(params.cross_align) {
+                CrossAlign::Start | CrossAlign::Stretch => line_start,
+                CrossAlign::Middle => line_start + (line_breadth - breadth) / 2.0,
+                CrossAlign::End => line_start + line_breadth - breadth,
+            };
+            let cross = match spec.align.unwrap_or(params.cross_align) {
+                CrossAlign::Stretch => Range::new(line_start, line_start + line_breadth),
+                _ => Range::new(cross_start, cross_start + breadth),
+            };
+            slots.push(Slot { main: main, cross: cross });
+        }
+
+        line_start += line_breadth + params.line_spacing;
+    }
+    slots
+}
+
+/// Grow or shrink the items of a single line so that they fill its length where possible.
+fn resolve_lengths(specs: &[Spec], params: &Params) -> Vec<Scalar> {
+    let mut lengths: Vec<Scalar> = specs.iter()
+        .map(|spec| utils::clamp(spec.basis, spec.min, spec.max))
+        .collect();
+    let mut frozen = vec![false; specs.len()];
+    let gaps = specs.len().saturating_sub(1) as Scalar * params.spacing;
+
+    // Each pass distributes the free space between the unfrozen items, freezing any that reach
+    // their min or max. Once no item is frozen during a pass, the lengths are resolved.
+    for _ in 0..specs.len() {
+        let free = params.length - lengths.iter().fold(0.0, |sum, l| sum + l) - gaps;
+        let is_growing = free > 0.0;
+        let weight = |i: usize| match is_growing {
+            true => specs[i].grow,
+            false => specs[i].shrink * specs[i].basis,
+        };
+        let total_weight = (0..specs.len())
+            .filter(|&i| !frozen[i])
+            .fold(0.0, |sum, i| sum + weight(i));
+        if free == 0.0 || total_weight <= 0.0 {
+            break;
+        }
+
+        let mut is_clamped = false;
+        for i in 0..specs.len() {
+            if frozen[i] {
+                continue;
+            }
+            let target = lengths[i] + free * weight(i) / total_weight;
+            let clamped = utils::clamp(target, specs[i].min, specs[i].max);
+            if clamped != target {
+                frozen[i] = true;
+                is_clamped = true;
+            }
+            lengths[i] = clamped;
+        }
+        if !is_clamped {
+            break;
+        }
+    }
+    lengths
+}
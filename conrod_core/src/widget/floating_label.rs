@@ -0,0 +1,71 @@
+//! A bordered label floating near the mouse cursor, shared by the widgets that follow the cursor.
+
+use {Color, Colorable, FontSize, Borderable, Point, Positionable, Scalar, UiCell, Widget};
+use label;
+use text;
+use widget;
+
+
+/// The resolved styling of a floating label.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Style {
+    /// The color of the label's rectangle surface.
+    pub color: Color,
+    /// The width of the border surrounding the label's rectangle.
+    pub border: Scalar,
+    /// The color of the label's border.
+    pub border_color: Color,
+    /// The color of the label's text.
+    pub text_color: Color,
+    /// The font size for the label's text.
+    pub font_size: FontSize,
+    /// The font used for the label's text. The first font within the `Ui` is used if `None`.
+    pub maybe_font_id: Option<text::font::Id>,
+    /// The distance between lines for multi-line text.
+    pub line_spacing: Scalar,
+    /// The padding between the edge of the label's rectangle and its text.
+    pub padding: Scalar,
+}
+
+
+/// Set the `text` within a bordered rectangle floating on top of all other widgets near the
+/// cursor at `anchor`.
+///
+/// The rectangle and text are instantiated with the given ids as graphics for the widget `id`.
+pub fn set(text: &str,
+           anchor: Point,
+           style: Style,
+           id: widget::Id,
+           rectangle_id: widget::Id,
+           text_id: widget::Id,
+           ui: &mut UiCell)
+{
+    let Style {
+        color, border, border_color, text_color, font_size, maybe_font_id, line_spacing, padding,
+    } = style;
+    let font_id = maybe_font_id.or(ui.fonts.ids().next());
+    let inset = padding + border;
+    let dim = label::padded_dimensions(text, font_id, font_size, line_spacing, inset, ui)
+        .unwrap_or([inset * 2.0, inset * 2.0]);
+    let xy = widget::tooltip::position_near_cursor(anchor, dim, ui.window_dim());
+
+    widget::BorderedRectangle::new(dim)
+        .xy(xy)
+        .color(color)
+        .border(border)
+        .border_color(border_color)
+        .parent(ui.window)
+        .graphics_for(id)
+        .floating(true)
+        .set(rectangle_id, ui);
+
+    widget::Text::new(text)
+        .and_then(font_id, widget::Text::font_id)
+        .middle_of(rectangle_id)
+        .color(text_color)
+        .font_size(font_size)
+        .line_spacing(line_spacing)
+        .parent(rectangle_id)
+        .graphics_for(id)
+        .set(text_id, ui);
+}
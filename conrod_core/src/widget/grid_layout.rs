@@ -0,0 +1,431 @@
+//! A container that lays out widgets within the cells of a grid of rows and columns.
+
+use {Positionable, Sizeable, UiCell, Widget};
+use position::{Dimensions, Point, Range, Rect, Scalar};
+use std;
+use utils;
+use widget;
+use widget::record::{self, Records};
+
+
+/// A container that lays out widgets within the cells of a grid of rows and columns.
+///
+/// Unlike the `Matrix`, whose cells are all the same size, each column and row of a
+/// **GridLayout** is a `Track` which may have a fixed size, a fraction of the remaining space or
+/// the size of its content. Items may span multiple rows and columns and are aligned within their
+/// cells.
+///
+/// Like the `Flex`, the **GridLayout** generates a `widget::Id` for each of its items and positions
+/// each item automatically as it is set via `Item::set`. Unless placed via `Item::at`, items fill
+/// the grid one row at a time. Items are measured when they are set, so the size of each item that
+/// follows the item being set is remembered from the previous update, and another update is
+/// requested whenever a measurement changes.
+///
+/// For example, a form whose labels are as wide as the widest label and whose fields fill the
+/// remaining width may be laid out via the columns `[Track::Auto, Track::Fraction(1.0)]`.
+#[derive(Clone, WidgetCommon_)]
+pub struct GridLayout<'a> {
+    /// Common widget building params for the **GridLayout**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **GridLayout**.
+    pub style: Style,
+    columns: &'a [Track],
+    rows: &'a [Track],
+    num_items: usize,
+}
+
+/// Unique styling for the **GridLayout**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The space between each column.
+    #[conrod(default = "0.0")]
+    pub column_gap: Option<Scalar>,
+    /// The space between each row.
+    #[conrod(default = "0.0")]
+    pub row_gap: Option<Scalar>,
+    /// The padding between the edge of the **GridLayout** and its cells.
+    #[conrod(default = "0.0")]
+    pub pad: Option<Scalar>,
+    /// The default alignment of items within their cells along the *x* axis.
+    #[conrod(default = "CellAlign::Stretch")]
+    pub x_align: Option<CellAlign>,
+    /// The default alignment of items within their cells along the *y* axis.
+    #[conrod(default = "CellAlign::Stretch")]
+    pub y_align: Option<CellAlign>,
+}
+
+/// Describes the size of a single column or row.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Track {
+    /// A fixed size.
+    Fixed(Scalar),
+    /// A share of the space remaining once all `Fixed` and `Auto` tracks are sized, relative to
+    /// the fractions of the other `Fraction` tracks.
+    Fraction(Scalar),
+    /// The size of the largest item within the track.
+    Auto,
+}
+
+/// The alignment of an item within its cell along a single axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CellAlign {
+    /// Align the item with the left or top of the cell.
+    Start,
+    /// Align the item with the middle of the cell.
+    Middle,
+    /// Align the item with the right or bottom of the cell.
+    End,
+    /// Stretch the item to fill the cell.
+    Stretch,
+}
+
+widget_ids! {
+    struct Ids {
+        items[],
+        records[],
+    }
+}
+
+/// Represents the state of the **GridLayout** widget.
+pub struct State {
+    ids: Ids,
+}
+
+/// Describes the placement of a single item, as measured when the item was last set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Placement {
+    /// The first row occupied by the item.
+    pub row: usize,
+    /// The first column occupied by the item.
+    pub col: usize,
+    /// The number of rows occupied by the item.
+    pub row_span: usize,
+    /// The number of columns occupied by the item.
+    pub col_span: usize,
+    /// The size of the item's widget.
+    pub dim: Dimensions,
+}
+
+/// The resolved position of each column and row, relative to the left and top of the grid
+/// respectively.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tracks {
+    /// The horizontal range occupied by each column, measured from the left of the grid.
+    pub columns: Vec<Range>,
+    /// The vertical range occupied by each row, measured downwards from the top of the grid.
+    pub rows: Vec<Range>,
+}
+
+/// Yields each `Item` of a **GridLayout**.
+pub struct Items {
+    kid_area: Rect,
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    gaps: [Scalar; 2],
+    aligns: [CellAlign; 2],
+    /// The placement of each item as of this and the previous update.
+    placements: Records<Placement>,
+}
+
+/// A single item of a **GridLayout**, used to set the item's widget.
+pub struct Item<'a> {
+    /// The index of the item within the **GridLayout**.
+    pub i: usize,
+    /// The id generated for the item's widget.
+    pub widget_id: widget::Id,
+    items: &'a mut Items,
+    cell: [usize; 2],
+    span: [usize; 2],
+    aligns: [CellAlign; 2],
+}
+
+
+impl<'a> GridLayout<'a> {
+
+    /// Begin building a **GridLayout** with the given columns, rows and number of items.
+    ///
+    /// Any rows occupied by items beyond the given `rows` are sized via `Track::Auto`.
+    pub fn new(columns: &'a [Track], rows: &'a [Track], num_items: usize) -> Self {
+        GridLayout {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            columns: columns,
+            rows: rows,
+            num_items: num_items,
+        }
+    }
+
+    /// Specify the space between both columns and rows.
+    pub fn gap(self, gap: Scalar) -> Self {
+        self.column_gap(gap).row_gap(gap)
+    }
+
+    builder_methods!{
+        pub column_gap { style.column_gap = Some(Scalar) }
+        pub row_gap { style.row_gap = Some(Scalar) }
+        pub pad { style.pad = Some(Scalar) }
+        pub x_align { style.x_align = Some(CellAlign) }
+        pub y_align { style.y_align = Some(CellAlign) }
+    }
+
+}
+
+impl<'a> Widget for GridLayout<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Items;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let GridLayout { columns, rows, num_items, .. } = self;
+
+        if state.ids.items.len() < num_items {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.items.resize(num_items, id_gen);
+                state.ids.records.resize(num_items, id_gen);
+            });
+        }
+
+        let ids: Vec<_> = state.ids.items.iter().cloned()
+            .zip(state.ids.records.iter().cloned())
+            .take(num_items)
+            .collect();
+        let placements = Records::new(id, ids, ui);
+
+        Items {
+            kid_area: rect.pad(style.pad(&ui.theme)),
+            columns: columns.to_vec(),
+            rows: rows.to_vec(),
+            gaps: [style.column_gap(&ui.theme), style.row_gap(&ui.theme)],
+            aligns: [style.x_align(&ui.theme), style.y_align(&ui.theme)],
+            placements: placements,
+        }
+    }
+
+}
+
+
+impl Items {
+
+    /// Yield the next `Item` of the **GridLayout**.
+    ///
+    /// By default the item occupies the cell following the previous item, moving to the start of
+    /// the next row after the last column.
+    pub fn next(&mut self) -> Option<Item> {
+        let i = self.placements.len();
+        let widget_id = match self.placements.next_id() {
+            Some(widget_id) => widget_id,
+            None => return None,
+        };
+        let num_columns = std::cmp::max(self.columns.len(), 1);
+        let cell = match self.placements.last() {
+            None => [0, 0],
+            Some(prev) if prev.col + prev.col_span >= num_columns => [prev.row + 1, 0],
+            Some(prev) => [prev.row, prev.col + prev.col_span],
+        };
+        let aligns = self.aligns;
+        Some(Item {
+            i: i,
+            widget_id: widget_id,
+            items: self,
+            cell: cell,
+            span: [1, 1],
+            aligns: aligns,
+        })
+    }
+
+}
+
+impl<'a> Item<'a> {
+
+    /// Place the item within the cell at the given row and column.
+    pub fn at(mut self, row: usize, col: usize) -> Self {
+        self.cell = [row, col];
+        self
+    }
+
+    /// Span the given number of rows and columns, beginning at the item's cell.
+    pub fn span(mut self, rows: usize, cols: usize) -> Self {
+        self.span = [std::cmp::max(rows, 1), std::cmp::max(cols, 1)];
+        self
+    }
+
+    /// Align the item within its cell along the *x* axis.
+    pub fn x_align(mut self, align: CellAlign) -> Self {
+        self.aligns[0] = align;
+        self
+    }
+
+    /// Align the item within its cell along the *y* axis.
+    pub fn y_align(mut self, align: CellAlign) -> Self {
+        self.aligns[1] = align;
+        self
+    }
+
+    /// Sets the given widget as the widget to use for the item.
+    ///
+    /// Sets the position, dimensions and parent of the widget before setting it within the `Ui`.
+    pub fn set<W>(self, mut widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let Item { widget_id, items, cell, span, aligns, .. } = self;
+
+        let dim = record::measure(&mut widget, widget_id, ui);
+        let placement = Placement {
+            row: cell[0],
+            col: cell[1],
+            row_span: span[0],
+            col_span: span[1],
+            dim: dim,
+        };
+
+        // Resolve the tracks using the previous placement of the items not yet set.
+        let placements = items.placements.push(placement, ui);
+        let tracks = resolve_tracks(&items.columns, &items.rows, &placements,
+                                    items.kid_area.dim(), items.gaps);
+        let (xy, dim) = items.item_rect(&tracks, &placement, aligns);
+        items.placements.set(widget, xy, dim, ui)
+    }
+
+}
+
+impl Items {
+
+    /// The absolute position and dimensions of the given item.
+    fn item_rect(&self, tracks: &Tracks, placement: &Placement, aligns: [CellAlign; 2])
+        -> (Point, Dimensions)
+    {
+        let area = self.kid_area;
+        let (x, y) = tracks.cell(placement);
+        let x = Range::new(area.left() + x.start, area.left() + x.end);
+        let y = Range::new(area.top() - y.end, area.top() - y.start);
+        let align = |cell: Range, len: Scalar, align: CellAlign| match align {
+            CellAlign::Stretch => cell,
+            CellAlign::Start => Range::new(cell.start, cell.start + len),
+            CellAlign::Middle => Range::from_pos_and_len(cell.middle(), len),
+            CellAlign::End => Range::new(cell.end - len, cell.end),
+        };
+        // The *y* axis is inverted, so the start of the cell is its top.
+        let y_align = match aligns[1] {
+            CellAlign::Start => CellAlign::End,
+            CellAlign::End => CellAlign::Start,
+            align => align,
+        };
+        let x = align(x, placement.dim[0].min(x.len()), aligns[0]);
+        let y = align(y, placement.dim[1].min(y.len()), y_align);
+        ([x.middle(), y.middle()], [x.len(), y.len()])
+    }
+
+}
+
+impl Tracks {
+
+    /// The horizontal and vertical ranges occupied by the given placement, relative to the left
+    /// and top of the grid respectively.
+    pub fn cell(&self, placement: &Placement) -> (Range, Range) {
+        fn span(tracks: &[Range], start: usize, span: usize) -> Range {
+            let first = tracks.get(start).or(tracks.last()).cloned()
+                .unwrap_or_else(|| Range::new(0.0, 0.0));
+            let last = tracks.get(start + span - 1).or(tracks.last()).cloned().unwrap_or(first);
+            Range::new(first.start, last.end)
+        }
+        (span(&self.columns, placement.col, placement.col_span),
+         span(&self.rows, placement.row, placement.row_span))
+    }
+
+}
+
+
+/// Resolve the size and position of each column and row for the given placements within a grid
+/// of the given dimensions.
+pub fn resolve_tracks(columns: &[Track],
+                      rows: &[Track],
+                      placements: &[Placement],
+                      dim: Dimensions,
+                      gaps: [Scalar; 2]) -> Tracks
+{
+    // Rows beyond those given are sized to their content.
+    let num_rows = placements.iter().fold(rows.len(), |max, p| {
+        std::cmp::max(max, p.row + p.row_span)
+    });
+    let rows: Vec<Track> = rows.iter().cloned()
+        .chain(std::iter::repeat(Track::Auto))
+        .take(num_rows)
+        .collect();
+    let column_spans = placements.iter().map(|p| (p.col, p.col_span, p.dim[0]));
+    let row_spans = placements.iter().map(|p| (p.row, p.row_span, p.dim[1]));
+    Tracks {
+        columns: resolve_axis(columns, column_spans, dim[0], gaps[0]),
+        rows: resolve_axis(&rows, row_spans, dim[1], gaps[1]),
+    }
+}
+
+/// Resolve the tracks along a single axis given the `(start, span, len)` of each item.
+fn resolve_axis<I>(tracks: &[Track], items: I, len: Scalar, gap: Scalar) -> Vec<Range>
+    where I: Iterator<Item=(usize, usize, Scalar)> + Clone,
+{
+    let mut sizes: Vec<Scalar> = tracks.iter().map(|track| match *track {
+        Track::Fixed(size) => size,
+        Track::Fraction(_) | Track::Auto => 0.0,
+    }).collect();
+    let is_auto = |i: usize| tracks.get(i) == Some(&Track::Auto);
+
+    // Size each `Auto` track to fit the items that occupy only that track.
+    for (start, span, item_len) in items.clone() {
+        if span == 1 && is_auto(start) {
+            sizes[start] = utils::partial_max(sizes[start], item_len);
+        }
+    }
+
+    // Grow the `Auto` tracks beneath spanning items that do not yet fit.
+    for (start, span, item_len) in items.filter(|&(_, span, _)| span > 1) {
+        let end = std::cmp::min(start + span, tracks.len());
+        if start >= end {
+            continue;
+        }
+        let gaps = (end - start - 1) as Scalar * gap;
+        let spanned = sizes[start..end].iter().fold(gaps, |sum, s| sum + s);
+        let autos: Vec<usize> = (start..end).filter(|&i| is_auto(i)).collect();
+        if spanned < item_len && !autos.is_empty() {
+            let extra = (item_len - spanned) / autos.len() as Scalar;
+            for i in autos {
+                sizes[i] += extra;
+            }
+        }
+    }
+
+    // Divide the remaining space between the `Fraction` tracks.
+    let gaps = tracks.len().saturating_sub(1) as Scalar * gap;
+    let used = sizes.iter().fold(gaps, |sum, s| sum + s);
+    let remaining = utils::partial_max(len - used, 0.0);
+    let total_fraction = tracks.iter().fold(0.0, |sum, track| match *track {
+        Track::Fraction(fraction) => sum + fraction,
+        _ => sum,
+    });
+    if total_fraction > 0.0 {
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if let Track::Fraction(fraction) = *track {
+                *size = remaining * fraction / total_fraction;
+            }
+        }
+    }
+
+    let mut start = 0.0;
+    sizes.iter().map(|&size| {
+        let range = Range::new(start, start + size);
+        start += size + gap;
+        range
+    }).collect()
+}
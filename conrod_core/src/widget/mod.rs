@@ -5,7 +5,7 @@
 
 use graph::{Container, UniqueWidgetState};
 use position::{Align, Depth, Dimension, Dimensions, Padding, Position, Point,
-               Positionable, Rect, Relative, Sizeable};
+               Positionable, Rect, Relative, Scalar, SizeLimits, Sizeable};
 use std;
 use text::font;
 use theme::{self, Theme};
@@ -27,14 +27,20 @@ pub use self::primitive::text::{self, Text};
 pub use self::bordered_rectangle::BorderedRectangle;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
+pub use self::code_editor::CodeEditor;
+pub use self::dock::Dock;
+pub use self::drag_preview::DragPreview;
 pub use self::collapsible_area::CollapsibleArea;
 pub use self::drop_down_list::DropDownList;
 pub use self::envelope_editor::EnvelopeEditor;
 pub use self::file_navigator::FileNavigator;
+pub use self::flex::Flex;
 pub use self::grid::Grid;
+pub use self::grid_layout::GridLayout;
 pub use self::list::List;
 pub use self::list_select::ListSelect;
 pub use self::matrix::Matrix;
+pub use self::modal::Modal;
 pub use self::graph::Graph;
 pub use self::number_dialer::NumberDialer;
 pub use self::plot_path::PlotPath;
@@ -42,11 +48,14 @@ pub use self::range_slider::RangeSlider;
 pub use self::rounded_rectangle::RoundedRectangle;
 pub use self::scrollbar::Scrollbar;
 pub use self::slider::Slider;
+pub use self::splitter::Splitter;
 pub use self::tabs::Tabs;
 pub use self::text_box::TextBox;
 pub use self::text_edit::TextEdit;
 pub use self::title_bar::TitleBar;
 pub use self::toggle::Toggle;
+pub use self::tooltip::Tooltip;
+pub use self::window::Window;
 pub use self::xy_pad::XYPad;
 
 
@@ -65,26 +74,39 @@ pub mod primitive;
 pub mod bordered_rectangle;
 pub mod button;
 pub mod canvas;
+mod caret;
+pub mod code_editor;
 pub mod collapsible_area;
+pub mod dialog;
+pub mod dock;
+pub mod drag_preview;
 pub mod drop_down_list;
 pub mod envelope_editor;
 pub mod file_navigator;
+pub mod flex;
+mod floating_label;
 pub mod grid;
+pub mod grid_layout;
 pub mod list;
 pub mod list_select;
 pub mod matrix;
+pub mod modal;
 pub mod graph;
 pub mod number_dialer;
 pub mod plot_path;
 pub mod range_slider;
+mod record;
 pub mod rounded_rectangle;
 pub mod scrollbar;
 pub mod slider;
+pub mod splitter;
 pub mod tabs;
 pub mod text_box;
 pub mod text_edit;
 pub mod title_bar;
 pub mod toggle;
+pub mod tooltip;
+pub mod window;
 pub mod xy_pad;
 
 
@@ -251,6 +273,8 @@ pub struct CommonStyle {
     pub maybe_y_position: Option<Position>,
     /// The rendering Depth of the Widget.
     pub maybe_depth: Option<Depth>,
+    /// The limits applied to the dimensions of the Widget.
+    pub size_limits: SizeLimits,
 }
 
 /// A wrapper around a **Widget**'s unique **Widget::State**.
@@ -448,6 +472,23 @@ pub fn default_y_dimension<W>(widget: &W, ui: &Ui) -> Dimension
     default_dimension(widget, ui, |default| default.common.maybe_y_dimension)
 }
 
+/// The dimensions of the bounding box of the given widget's kids as of the previous update, along
+/// with the space between the widget's `Rect` and its `kid_area`.
+///
+/// Returns `None` if the widget has not yet been set or has no kids.
+///
+/// This is called by the default implementation of **Widget::content_dimensions**.
+///
+/// If you wish to override **Widget::content_dimensions**, feel free to call this function
+/// internally if you partly require the behaviour of the default implementation.
+pub fn kids_content_dimensions(id: Id, ui: &Ui) -> Option<Dimensions> {
+    let rect = match ui.rect_of(id) { Some(rect) => rect, None => return None };
+    let kid_area = ui.kid_area_of(id).unwrap_or(rect);
+    ui.kids_bounding_box(id).map(|kids| {
+        [kids.w() + rect.w() - kid_area.w(), kids.h() + rect.h() - kid_area.h()]
+    })
+}
+
 
 /// A trait implemented by all **Widget** types.
 ///
@@ -664,6 +705,17 @@ pub trait Widget: Common + Sized {
         default_y_dimension(self, ui)
     }
 
+    /// The dimensions of the widget's content, used to resolve `Dimension::OfContent`.
+    ///
+    /// Widgets that display a label should override this with the measured size of the label.
+    ///
+    /// By default, this is the bounding box of the widget's kids as of the previous update, along
+    /// with the space between the widget's `Rect` and its `kid_area`. Returns `None` if the widget
+    /// has not yet been set or has no kids, in which case the widget's default dimension is used.
+    fn content_dimensions(&self, id: Id, ui: &Ui) -> Option<Dimensions> {
+        kids_content_dimensions(id, ui)
+    }
+
     /// If the widget is draggable, implement this method and return the position and dimensions of
     /// the draggable space. The position should be relative to the center of the widget.
     fn drag_area(&self,
@@ -764,7 +816,9 @@ pub trait Widget: Common + Sized {
     /// A "floating" widget will always be rendered *after* its parent tree and all widgets
     /// connected to its parent tree. If two sibling widgets are both floating, then the one that
     /// was last clicked will be rendered last. If neither are clicked, they will be rendered in
-    /// the order in which they were cached into the `Ui`.
+    /// the order in which they were cached into the `Ui`. A floating widget that was not set
+    /// during the previous update, e.g. a pop-up that is being re-opened, is brought to the front
+    /// as though it were cached anew.
     fn floating(mut self, is_floating: bool) -> Self {
         self.common_mut().is_floating = is_floating;
         self
@@ -884,7 +938,7 @@ pub trait Widget: Common + Sized {
 /// users have a clear, concise, purely functional `Widget` API. As a result, we try to keep this
 /// as verbosely annotated as possible. If anything is unclear, feel free to post an issue or PR
 /// with concerns/improvements to the github repo.
-fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
+fn set_widget<'a, 'b, W>(mut widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
     where W: Widget,
 {
     let type_id = std::any::TypeId::of::<W::State>();
@@ -950,9 +1004,19 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
 
     let new_style = widget.style();
     let depth = widget.get_depth();
+    resolve_content_dimensions(&mut widget, id, ui);
     let dim = widget.get_wh(&ui).unwrap_or([0.0, 0.0]);
     let x_pos = widget.get_x_position(ui);
     let y_pos = widget.get_y_position(ui);
+
+    // Constrained positions may depend upon the dimensions of the widget, so these are given to
+    // the constraint layout before the position is calculated.
+    {
+        let x_dim = widget.get_x_dimension(ui);
+        let y_dim = widget.get_y_dimension(ui);
+        let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
+        ui::suggest_constrained_dimensions(ui, id, x_dim, y_dim, dim);
+    }
     let place_on_kid_area = widget.common().place_on_kid_area;
 
     // Determine the id of the canvas that the widget is attached to. If not given explicitly,
@@ -1012,28 +1076,33 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
 
     // Construct the rectangle describing our Widget's area.
     let rect = Rect::from_xy_dim(xy, dim);
+    ui::suggest_constrained_position(ui::ref_mut_from_ui_cell(ui), id, x_pos, y_pos, rect);
 
     // Check whether or not the widget is a "floating" (hovering / pop-up style) widget.
     let maybe_floating = if widget.common().is_floating {
 
-        fn new_floating() -> Floating {
-            Floating { time_last_clicked: instant::Instant::now() }
-        }
+        let new_floating = || Floating { time_last_clicked: ui.clock().now() };
 
         // If it is floating, check to see if we need to update the last time it was clicked.
         match maybe_prev_common.as_ref() {
             Some(prev) => {
-                let maybe_mouse = ui.widget_input(id).mouse();
-                match (prev.maybe_floating, maybe_mouse) {
-                    (Some(prev_floating), Some(mouse)) => {
-                        if mouse.buttons.left().is_down() {
-                            Some(new_floating())
-                        } else {
-                            Some(prev_floating)
-                        }
-                    },
-                    (Some(prev_floating), None) => Some(prev_floating),
-                    _ => Some(new_floating()),
+                // A press upon the floating widget or any widget within its tree brings the
+                // floating widget to the front.
+                let is_pressed = ui.global_input().events().ui().any(|event| match *event {
+                    ::event::Ui::Press(Some(pressed), ::event::Press {
+                        button: ::event::Button::Mouse(input::MouseButton::Left, _),
+                        ..
+                    }) => pressed == id
+                        || ui.widget_graph().does_recursive_depth_edge_exist(id, pressed),
+                    _ => false,
+                });
+                match prev.maybe_floating {
+                    Some(_) if is_pressed => Some(new_floating()),
+                    // A floating widget that was not set during the previous update (e.g. a
+                    // pop-up that is being re-opened) is brought to the front.
+                    Some(_) if !ui.prev_updated_widgets().contains(&id) => Some(new_floating()),
+                    Some(prev_floating) => Some(prev_floating),
+                    None => Some(new_floating()),
                 }
             },
             None => Some(new_floating()),
@@ -1098,7 +1167,7 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         // Some widget to which this widget is relatively positioned (if there is one).
         let maybe_positioned_relatively_id = |pos: Position| match pos {
             Position::Relative(_, maybe_id) => maybe_id.or(maybe_prev_widget_id),
-            Position::Absolute(_) => None,
+            Position::Absolute(_) | Position::Constrained(_) => None,
         };
 
         let maybe_x_positioned_relatively_id = maybe_positioned_relatively_id(x_pos);
@@ -1285,6 +1354,45 @@ impl<W> Positionable for W
 }
 
 
+/// Replace any `Dimension::OfContent` given for the widget with the `Absolute` dimension of its
+/// content, where `id` is the `Id` with which the widget is set.
+///
+/// If the content cannot yet be measured, the widget's default dimension is used instead.
+///
+/// This is called when the widget is set, but may also be called beforehand in order to measure
+/// the widget, e.g. by layout containers that size their items according to their dimensions.
+pub fn resolve_content_dimensions<W>(widget: &mut W, id: Id, ui: &Ui)
+    where W: Widget,
+{
+    let content_pad = |dimension| match dimension {
+        Dimension::OfContent(maybe_pad) => Some(maybe_pad),
+        _ => None,
+    };
+    let x_pad = content_pad(widget.get_x_dimension(ui));
+    let y_pad = content_pad(widget.get_y_dimension(ui));
+    if x_pad.is_none() && y_pad.is_none() {
+        return;
+    }
+    let content = widget.content_dimensions(id, ui);
+    let resolve = |maybe_pad: Option<Scalar>, i: usize, default: Dimension| {
+        match content {
+            Some(dim) => Dimension::Absolute(dim[i] + maybe_pad.unwrap_or(0.0) * 2.0),
+            None => match default {
+                Dimension::OfContent(_) => Dimension::Absolute(0.0),
+                default => default,
+            },
+        }
+    };
+    if let Some(maybe_pad) = x_pad {
+        let default = widget.default_x_dimension(ui);
+        widget.common_mut().style.maybe_x_dimension = Some(resolve(maybe_pad, 0, default));
+    }
+    if let Some(maybe_pad) = y_pad {
+        let default = widget.default_y_dimension(ui);
+        widget.common_mut().style.maybe_y_dimension = Some(resolve(maybe_pad, 1, default));
+    }
+}
+
 /// In the case that a position hasn't been given for one of the axes, we must first check to see
 /// if we can infer the missing axis position from the other axis.
 ///
@@ -1298,7 +1406,8 @@ fn infer_position_from_other_position(other_pos: Position, dir_align: Align) ->
         Position::Relative(Relative::Scalar(_), maybe_id) =>
             Some(Position::Relative(Relative::Scalar(0.0), maybe_id)),
         Position::Relative(Relative::Align(_), _) |
-        Position::Absolute(_) => None,
+        Position::Absolute(_) |
+        Position::Constrained(_) => None,
     }
 }
 
@@ -1326,4 +1435,17 @@ impl<W> Sizeable for W
     fn get_y_dimension(&self, ui: &Ui) -> Dimension {
         self.common().style.maybe_y_dimension.unwrap_or_else(|| self.default_y_dimension(ui))
     }
+    fn get_maybe_x_dimension(&self) -> Option<Dimension> {
+        self.common().style.maybe_x_dimension
+    }
+    fn get_maybe_y_dimension(&self) -> Option<Dimension> {
+        self.common().style.maybe_y_dimension
+    }
+    fn size_limits(mut self, limits: SizeLimits) -> Self {
+        self.common_mut().style.size_limits = limits;
+        self
+    }
+    fn get_size_limits(&self) -> SizeLimits {
+        self.common().style.size_limits
+    }
 }
@@ -0,0 +1,140 @@
+//! A layer that blocks interaction with the rest of the `Ui` while it is open.
+
+use {Color, Colorable, Positionable, Widget};
+use color;
+use event;
+use input;
+use position::{Dimension, Position};
+use widget;
+
+
+/// A modal layer that covers the entire window.
+///
+/// While a **Modal** is set, all input is routed only to the **Modal** and the widgets placed
+/// upon it. Clicks that land outside of these widgets are delivered to the **Modal** itself. A
+/// dimming backdrop is drawn over the rest of the `Ui` and the keyboard is captured by the
+/// **Modal** when it first opens.
+///
+/// The layer remains open for as long as the **Modal** is set. Once it is no longer set, the
+/// keyboard is returned to whichever widget was capturing it before the layer opened.
+///
+/// Pressing `Escape` or clicking the backdrop produces a `Dismiss` event. It is up to the user to
+/// stop setting the **Modal** in response.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct Modal {
+    /// Common widget building params for the **Modal**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Modal**.
+    pub style: Style,
+}
+
+/// Unique styling for the **Modal**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the backdrop drawn over the rest of the `Ui`.
+    #[conrod(default = "color::BLACK.alpha(0.5)")]
+    pub backdrop_color: Option<Color>,
+}
+
+widget_ids! {
+    struct Ids {
+        backdrop,
+    }
+}
+
+/// Represents the state of the **Modal** widget.
+pub struct State {
+    ids: Ids,
+}
+
+/// The reasons for which the user may request that a **Modal** be dismissed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dismiss {
+    /// The `Escape` key was pressed.
+    Escape,
+    /// The backdrop surrounding the layer's widgets was clicked.
+    Backdrop,
+}
+
+
+impl Modal {
+
+    /// Begin building a new **Modal** layer.
+    pub fn new() -> Self {
+        Modal {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+        }.floating(true)
+    }
+
+    builder_methods!{
+        pub backdrop_color { style.backdrop_color = Some(Color) }
+    }
+
+}
+
+impl Widget for Modal {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Dismiss>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_y_position(&self, _ui: &::Ui) -> Position {
+        Position::Absolute(0.0)
+    }
+
+    fn default_x_dimension(&self, ui: &::Ui) -> Dimension {
+        Dimension::Absolute(ui.win_w)
+    }
+
+    fn default_y_dimension(&self, ui: &::Ui) -> Dimension {
+        Dimension::Absolute(ui.win_h)
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, ui, .. } = args;
+
+        ui.open_modal(id);
+
+        widget::Rectangle::fill([ui.win_w, ui.win_h])
+            .x_y(0.0, 0.0)
+            .color(style.backdrop_color(&ui.theme))
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.backdrop, ui);
+
+        // Only the top-most layer responds to `Escape`, even if one of its widgets has captured
+        // the keyboard.
+        let escaped = ui.modal() == Some(id) && ui.global_input().events().ui().any(|event| {
+            match *event {
+                event::Ui::Press(_, event::Press {
+                    button: event::Button::Keyboard(input::Key::Escape), ..
+                }) => true,
+                _ => false,
+            }
+        });
+        if escaped {
+            return Some(Dismiss::Escape);
+        }
+
+        match ui.widget_input(id).clicks().left().next() {
+            Some(_) => Some(Dismiss::Backdrop),
+            None => None,
+        }
+    }
+
+}
@@ -0,0 +1,187 @@
+//! An invisible widget used by layout containers to remember the measurements of each item
+//! between updates.
+
+use {Positionable, Sizeable, Theme, Ui, UiCell};
+use graph;
+use position::{Dimensions, Point};
+use std;
+use widget::{self, Widget};
+
+
+/// An invisible widget that remembers the given `value` until the next update.
+///
+/// Layout containers measure each item as it is set, so the measurements of any items that
+/// follow are only known from the previous update. Setting a **Record** alongside each item stores
+/// its measurements within the widget graph. The **Record** should occupy the same area as its
+/// item so that widgets positioned relative to the previously set widget are unaffected.
+#[derive(Clone, WidgetCommon_)]
+pub struct Record<T> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    value: T,
+}
+
+
+impl<T> Record<T> {
+
+    /// Construct a **Record** of the given value.
+    pub fn new(value: T) -> Self {
+        Record {
+            common: widget::CommonBuilder::default(),
+            value: value,
+        }
+    }
+
+}
+
+impl<T> Widget for Record<T>
+    where T: Copy + PartialEq + Send + std::fmt::Debug + 'static,
+{
+    type State = T;
+    type Style = ();
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        self.value
+    }
+
+    fn style(&self) -> Self::Style {
+        ()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        fn is_over_widget(_: &graph::Container, _: Point, _: &Theme) -> widget::IsOver {
+            false.into()
+        }
+        is_over_widget
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, .. } = args;
+        if **state != self.value {
+            state.update(|state| *state = self.value);
+        }
+    }
+}
+
+
+/// The value recorded by the **Record** with the given `id` during the previous update, if it was
+/// set.
+pub fn prev_value<T>(ui: &Ui, id: widget::Id) -> Option<T>
+    where T: Copy + PartialEq + Send + std::fmt::Debug + 'static,
+{
+    if !ui.prev_updated_widgets().contains(&id) {
+        return None;
+    }
+    ui.widget_graph().widget(id)
+        .and_then(|container| container.unique_widget_state::<Record<T>>())
+        .map(|&graph::UniqueWidgetState { ref state, .. }| *state)
+}
+
+
+/// The values recorded for each item of a layout container, during both the current and the
+/// previous update.
+///
+/// Each item is measured, recorded via `Records::push` and then set via `Records::set`. Whenever
+/// an item's value differs from the previous update, another update is requested so that the
+/// layout settles without waiting for further input.
+pub struct Records<T> {
+    parent: widget::Id,
+    /// The id of each item's widget along with the id of its **Record**.
+    ids: Vec<(widget::Id, widget::Id)>,
+    /// The value of each item as of the previous update.
+    prev_values: Vec<Option<T>>,
+    /// The value of each item that has been set during this update.
+    values: Vec<T>,
+}
+
+impl<T> Records<T>
+    where T: Copy + PartialEq + Send + std::fmt::Debug + 'static,
+{
+
+    /// Retrieve the values recorded for the given `(item, record)` ids during the previous update
+    /// of the container `parent`.
+    pub fn new(parent: widget::Id, ids: Vec<(widget::Id, widget::Id)>, ui: &Ui) -> Self {
+        let prev_values = ids.iter()
+            .map(|&(_, record_id)| prev_value(ui, record_id))
+            .collect();
+        let num_items = ids.len();
+        Records {
+            parent: parent,
+            ids: ids,
+            prev_values: prev_values,
+            values: Vec::with_capacity(num_items),
+        }
+    }
+
+    /// The number of items that have been pushed during this update.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The id of the next item's widget, or `None` if every item has been set.
+    pub fn next_id(&self) -> Option<widget::Id> {
+        self.ids.get(self.values.len()).map(|&(widget_id, _)| widget_id)
+    }
+
+    /// The value of the item most recently pushed during this update.
+    pub fn last(&self) -> Option<&T> {
+        self.values.last()
+    }
+
+    /// Record the value of the next item.
+    ///
+    /// Returns the value of every item known so far, using the values from the previous update
+    /// for the items that follow.
+    pub fn push(&mut self, value: T, ui: &mut UiCell) -> Vec<T> {
+        let i = self.values.len();
+        self.values.push(value);
+
+        // Request another update so that the items that follow may account for this item.
+        if self.prev_values[i] != Some(value) {
+            let now = ui.time();
+            ui.request_redraw_at(now);
+        }
+
+        self.values.iter().cloned()
+            .chain(self.prev_values[i + 1..].iter().filter_map(|&value| value))
+            .collect()
+    }
+
+    /// Set the widget of the item most recently pushed, along with its **Record**, at the given
+    /// position and dimensions.
+    pub fn set<W>(&self, widget: W, xy: Point, dim: Dimensions, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let i = self.values.len() - 1;
+        let (widget_id, record_id) = self.ids[i];
+
+        let event = widget
+            .wh(dim)
+            .xy(xy)
+            .parent(self.parent)
+            .set(widget_id, ui);
+
+        Record::new(self.values[i])
+            .wh(dim)
+            .xy(xy)
+            .parent(self.parent)
+            .graphics_for(widget_id)
+            .set(record_id, ui);
+
+        event
+    }
+
+}
+
+
+/// Measure the dimensions of the given widget before it is set with the given `id`, falling back
+/// to `0.0` for any dimension that cannot yet be determined.
+///
+/// Any dimension given as `Dimension::OfContent` is resolved to the size of the widget's content.
+pub fn measure<W>(widget: &mut W, id: widget::Id, ui: &Ui) -> Dimensions
+    where W: Widget,
+{
+    widget::resolve_content_dimensions(widget, id, ui);
+    [widget.get_w(ui).unwrap_or(0.0), widget.get_h(ui).unwrap_or(0.0)]
+}
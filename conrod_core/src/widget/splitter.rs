@@ -0,0 +1,415 @@
+//! A container that divides its area into resizable panes separated by draggable dividers.
+
+use {Color, Colorable, Positionable, Sizeable, UiCell, Widget};
+use cursor;
+use event;
+use input;
+use position::{Axis, Range, Rect, Scalar};
+use std;
+use utils;
+use widget;
+
+
+/// A container that divides its area into a row or column of panes separated by dividers.
+///
+/// Like the `Canvas` `flow_right` and `flow_down` splits, the length of each pane is given as a
+/// weight relative to the weights of the other panes. Unlike the `Canvas`, the user may drag the
+/// dividers between panes to resize them, and may double-click a divider to collapse the smaller
+/// of its neighbouring panes, or to restore a collapsed pane.
+///
+/// The **Splitter** produces the new weights whenever they change, so that the application may
+/// update and persist them. A collapsed pane has a weight of `0.0`.
+#[derive(Clone, WidgetCommon_)]
+pub struct Splitter<'a> {
+    /// Common widget building params for the **Splitter**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Splitter**.
+    pub style: Style,
+    weights: &'a [Scalar],
+    axis: Axis,
+}
+
+/// Unique styling for the **Splitter**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The thickness of each divider.
+    #[conrod(default = "6.0")]
+    pub divider_thickness: Option<Scalar>,
+    /// The color of each divider.
+    #[conrod(default = "theme.border_color")]
+    pub divider_color: Option<Color>,
+    /// The minimum length of each pane that is not collapsed.
+    #[conrod(default = "20.0")]
+    pub min_pane_length: Option<Scalar>,
+}
+
+widget_ids! {
+    struct Ids {
+        panes[],
+        dividers[],
+    }
+}
+
+/// Represents the state of the **Splitter** widget.
+pub struct State {
+    ids: Ids,
+    /// The divider being dragged along with the weights at the beginning of the drag.
+    maybe_drag: Option<(usize, Vec<Scalar>)>,
+    /// The weight of each collapsed pane prior to collapsing.
+    collapsed: Vec<Option<Scalar>>,
+}
+
+/// The event produced by the **Splitter**.
+///
+/// The panes are used to set the widget within each pane. The new weights are produced whenever
+/// the user resizes, collapses or restores a pane.
+pub type Event = (Panes, Option<Vec<Scalar>>);
+
+/// An iterator yielding each `Pane` of a **Splitter**.
+#[derive(Clone, Debug)]
+pub struct Panes {
+    splitter_id: widget::Id,
+    panes: std::vec::IntoIter<(widget::Id, Rect)>,
+    i: usize,
+}
+
+/// A single pane of a **Splitter**, used to set the widget within the pane.
+#[derive(Copy, Clone, Debug)]
+pub struct Pane {
+    /// The index of the pane within the **Splitter**.
+    pub i: usize,
+    /// The id generated for the pane's widget.
+    pub widget_id: widget::Id,
+    /// The area occupied by the pane.
+    pub rect: Rect,
+    splitter_id: widget::Id,
+}
+
+
+impl<'a> Splitter<'a> {
+
+    /// Begin building a **Splitter** with panes of the given weights along the given axis.
+    pub fn new(axis: Axis, weights: &'a [Scalar]) -> Self {
+        Splitter {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            weights: weights,
+            axis: axis,
+        }
+    }
+
+    /// A **Splitter** whose panes are laid out from left to right.
+    pub fn columns(weights: &'a [Scalar]) -> Self {
+        Self::new(Axis::X, weights)
+    }
+
+    /// A **Splitter** whose panes are laid out from top to bottom.
+    pub fn rows(weights: &'a [Scalar]) -> Self {
+        Self::new(Axis::Y, weights)
+    }
+
+    builder_methods!{
+        pub divider_thickness { style.divider_thickness = Some(Scalar) }
+        pub divider_color { style.divider_color = Some(Color) }
+        pub min_pane_length { style.min_pane_length = Some(Scalar) }
+    }
+
+}
+
+impl<'a> Widget for Splitter<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            maybe_drag: None,
+            collapsed: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Splitter { weights, axis, .. } = self;
+        let num_panes = weights.len();
+        let num_dividers = num_panes.saturating_sub(1);
+
+        if state.ids.panes.len() < num_panes || state.collapsed.len() != num_panes {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.panes.resize(num_panes, id_gen);
+                state.ids.dividers.resize(num_dividers, id_gen);
+                state.collapsed.resize(num_panes, None);
+            });
+        }
+
+        let thickness = style.divider_thickness(&ui.theme);
+        let min_pane_length = style.min_pane_length(&ui.theme);
+        let main_range = match axis {
+            Axis::X => rect.x,
+            Axis::Y => rect.y,
+        };
+        let length = utils::partial_max(main_range.len() - thickness * num_dividers as Scalar, 0.0);
+
+        // Resize, collapse or restore panes in response to interaction with the dividers.
+        let mut new_weights = weights.to_vec();
+        let mut weights_changed = false;
+        for d in 0..num_dividers {
+            let divider_id = state.ids.dividers[d];
+            for event in ui.widget_input(divider_id).events() {
+                match event {
+                    event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                        let start = match state.maybe_drag {
+                            Some((i, ref start)) if i == d => start.clone(),
+                            _ => new_weights.clone(),
+                        };
+                        if state.maybe_drag.is_none() {
+                            let start = start.clone();
+                            state.update(|state| state.maybe_drag = Some((d, start)));
+                        }
+                        let delta = match axis {
+                            Axis::X => drag.total_delta_xy[0],
+                            Axis::Y => -drag.total_delta_xy[1],
+                        };
+                        new_weights = drag_divider(&start, d, delta, length, min_pane_length);
+                        weights_changed = true;
+                    },
+                    event::Widget::Release(event::Release {
+                        button: event::Button::Mouse(input::MouseButton::Left, _),
+                        ..
+                    }) => {
+                        state.update(|state| state.maybe_drag = None);
+                    },
+                    event::Widget::DoubleClick(click)
+                        if click.button == input::MouseButton::Left =>
+                    {
+                        state.update(|state| {
+                            new_weights = toggle_collapse(&new_weights, d, &mut state.collapsed);
+                        });
+                        weights_changed = true;
+                    },
+                    _ => (),
+                }
+            }
+        }
+
+        // Draw the dividers between each pane.
+        let lengths = pane_lengths(&new_weights, length, min_pane_length);
+        let mut ranges = Vec::with_capacity(num_panes);
+        let mut start = 0.0;
+        for (i, &pane_length) in lengths.iter().enumerate() {
+            let pane = Range::new(start, start + pane_length);
+            ranges.push(pane);
+            start = pane.end;
+
+            if i + 1 < num_panes {
+                let divider = Range::new(start, start + thickness);
+                start = divider.end;
+                let divider_id = state.ids.dividers[i];
+                let is_dragging = match state.maybe_drag {
+                    Some((d, _)) => d == i,
+                    None => false,
+                };
+                let is_over = ui.widget_input(divider_id).mouse().is_some();
+                let color = style.divider_color(&ui.theme);
+                let color = match is_dragging || is_over {
+                    true => color.highlighted(),
+                    false => color,
+                };
+                if is_dragging || is_over {
+                    let resize_cursor = match axis {
+                        Axis::X => cursor::MouseCursor::ResizeHorizontal,
+                        Axis::Y => cursor::MouseCursor::ResizeVertical,
+                    };
+                    ui.set_mouse_cursor(resize_cursor);
+                }
+                let divider_rect = rect_along(rect, axis, divider);
+                widget::Rectangle::fill(divider_rect.dim())
+                    .xy(divider_rect.xy())
+                    .color(color)
+                    .parent(id)
+                    .set(divider_id, ui);
+            }
+        }
+
+        let panes: Vec<_> = state.ids.panes.iter()
+            .zip(ranges)
+            .map(|(&pane_id, range)| (pane_id, rect_along(rect, axis, range)))
+            .collect();
+        let panes = Panes {
+            splitter_id: id,
+            panes: panes.into_iter(),
+            i: 0,
+        };
+        let maybe_weights = match weights_changed && &new_weights[..] != weights {
+            true => Some(new_weights),
+            false => None,
+        };
+        (panes, maybe_weights)
+    }
+
+}
+
+
+impl Iterator for Panes {
+    type Item = Pane;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.panes.next().map(|(widget_id, rect)| {
+            let i = self.i;
+            self.i += 1;
+            Pane {
+                i: i,
+                widget_id: widget_id,
+                rect: rect,
+                splitter_id: self.splitter_id,
+            }
+        })
+    }
+}
+
+impl Pane {
+
+    /// Whether or not the pane has been collapsed.
+    pub fn is_collapsed(&self) -> bool {
+        self.rect.w() == 0.0 || self.rect.h() == 0.0
+    }
+
+    /// Sets the given widget as the widget to use for the pane.
+    ///
+    /// Sets the position, dimensions and parent of the widget before setting it within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        widget
+            .wh(self.rect.dim())
+            .xy(self.rect.xy())
+            .parent(self.splitter_id)
+            .set(self.widget_id, ui)
+    }
+
+}
+
+
+/// The rectangle occupying the given range along the main axis of the splitter's rectangle.
+///
+/// The range is measured from the left of the rectangle along the *x* axis, or downwards from the
+/// top of the rectangle along the *y* axis.
+fn rect_along(rect: Rect, axis: Axis, range: Range) -> Rect {
+    match axis {
+        Axis::X => Rect {
+            x: Range::new(rect.left() + range.start, rect.left() + range.end),
+            y: rect.y,
+        },
+        Axis::Y => Rect {
+            x: rect.x,
+            y: Range::new(rect.top() - range.end, rect.top() - range.start),
+        },
+    }
+}
+
+/// The length of each pane given their weights and the length available to all panes.
+///
+/// If the weights sum to zero, the length is divided equally. No pane that is not collapsed is
+/// made shorter than `min_pane_length` unless the panes together are shorter than the minimum.
+pub fn pane_lengths(weights: &[Scalar], length: Scalar, min_pane_length: Scalar) -> Vec<Scalar> {
+    let total = weights.iter().fold(0.0, |total, &w| total + w);
+    let weights = match total <= 0.0 {
+        true => vec![1.0; weights.len()],
+        false => weights.to_vec(),
+    };
+    let num_open = weights.iter().filter(|&&w| w > 0.0).count();
+    let num_open = utils::partial_max(num_open as Scalar, 1.0);
+    let min = utils::partial_min(min_pane_length, length / num_open);
+
+    // Fix each pane whose share of the length falls below the minimum at the minimum, sharing the
+    // length that remains between the other panes.
+    let mut lengths = vec![0.0; weights.len()];
+    let mut is_fixed: Vec<bool> = weights.iter().map(|&w| w <= 0.0).collect();
+    loop {
+        let (free_weight, fixed_length) = (0..weights.len())
+            .fold((0.0, 0.0), |(weight, len), i| match is_fixed[i] {
+                true => (weight, len + lengths[i]),
+                false => (weight + weights[i], len),
+            });
+        let remaining = length - fixed_length;
+        let mut is_any_newly_fixed = false;
+        for i in 0..weights.len() {
+            if is_fixed[i] {
+                continue;
+            }
+            lengths[i] = remaining * weights[i] / free_weight;
+            if lengths[i] < min {
+                lengths[i] = min;
+                is_fixed[i] = true;
+                is_any_newly_fixed = true;
+            }
+        }
+        if !is_any_newly_fixed {
+            return lengths;
+        }
+    }
+}
+
+/// The weights resulting from dragging the given divider by `delta` along the main axis.
+///
+/// Only the weights of the panes either side of the divider change. Neither pane is made shorter
+/// than `min_pane_length` unless both panes together are shorter than twice the minimum.
+pub fn drag_divider(weights: &[Scalar],
+                    divider: usize,
+                    delta: Scalar,
+                    length: Scalar,
+                    min_pane_length: Scalar) -> Vec<Scalar>
+{
+    let mut weights = weights.to_vec();
+    if divider + 1 >= weights.len() || length <= 0.0 {
+        return weights;
+    }
+    let total = weights.iter().fold(0.0, |total, &w| total + w);
+    if total <= 0.0 {
+        for w in &mut weights {
+            *w = 1.0;
+        }
+    }
+    let lengths = pane_lengths(&weights, length, min_pane_length);
+    let pair = lengths[divider] + lengths[divider + 1];
+    let min = utils::partial_min(min_pane_length, pair / 2.0);
+    let first = utils::clamp(lengths[divider] + delta, min, pair - min);
+    let weight_per_length = weights.iter().fold(0.0, |total, &w| total + w) / length;
+    weights[divider] = first * weight_per_length;
+    weights[divider + 1] = (pair - first) * weight_per_length;
+    weights
+}
+
+/// The weights resulting from double-clicking the given divider.
+///
+/// If either pane beside the divider is collapsed, it is restored to its weight prior to
+/// collapsing. Otherwise the smaller of the two panes is collapsed. The weight gained or lost by a
+/// pane is taken from or given to its neighbour on the other side of the divider.
+fn toggle_collapse(weights: &[Scalar], divider: usize, collapsed: &mut [Option<Scalar>])
+    -> Vec<Scalar>
+{
+    let mut weights = weights.to_vec();
+    let (a, b) = (divider, divider + 1);
+    let restore = |weights: &mut [Scalar], pane: usize, other: usize, prev: Option<Scalar>| {
+        let weight = utils::partial_min(prev.unwrap_or(weights[other] / 2.0), weights[other]);
+        weights[pane] = weight;
+        weights[other] -= weight;
+    };
+    if weights[a] == 0.0 {
+        restore(&mut weights, a, b, collapsed[a].take());
+    } else if weights[b] == 0.0 {
+        restore(&mut weights, b, a, collapsed[b].take());
+    } else {
+        let (pane, other) = if weights[a] <= weights[b] { (a, b) } else { (b, a) };
+        collapsed[pane] = Some(weights[pane]);
+        weights[other] += weights[pane];
+        weights[pane] = 0.0;
+    }
+    weights
+}
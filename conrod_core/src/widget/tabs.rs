@@ -184,6 +184,40 @@ impl<'a> Widget for Tabs<'a> {
         self.style.clone()
     }
 
+    /// The tab bar, sized to fit each tab's label, along with the content of the selected tab's
+    /// **Canvas** as of the previous update.
+    fn content_dimensions(&self, _id: widget::Id, ui: &::Ui) -> Option<Dimensions> {
+        let style = &self.style;
+        let font_size = style.label_font_size(&ui.theme);
+        let font = match style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+            Some(id) => match ui.fonts.get(id) { Some(font) => font, None => return None },
+            None => return None,
+        };
+        let max_text_width = max_text_width(self.tabs.iter(), font_size, font);
+        let num_tabs = self.tabs.len() as Scalar;
+        let bar_thickness = style.bar_thickness(&ui.theme);
+
+        // Only the selected tab's **Canvas** has kids that were set during the previous update.
+        let canvas = self.tabs.iter()
+            .filter_map(|&(canvas_id, _)| widget::kids_content_dimensions(canvas_id, ui))
+            .next()
+            .unwrap_or([0.0, 0.0]);
+
+        let dim = match style.layout(&ui.theme) {
+            Layout::Horizontal => {
+                let tab_w = max_text_width + TAB_BAR_LABEL_PADDING * 2.0;
+                let bar_h = horizontal_tab_bar_h(bar_thickness, font_size as Scalar);
+                [utils::partial_max(tab_w * num_tabs, canvas[0]), bar_h + canvas[1]]
+            },
+            Layout::Vertical => {
+                let bar_w = vertical_tab_bar_w(bar_thickness, max_text_width);
+                let tab_h = font_size as Scalar + TAB_BAR_LABEL_PADDING * 2.0;
+                [bar_w + canvas[0], utils::partial_max(tab_h * num_tabs, canvas[1])]
+            },
+        };
+        Some(dim)
+    }
+
     /// The area on which child widgets will be placed when using the `Place` Positionable methods.
     fn kid_area(&self, args: widget::KidAreaArgs<Self>) -> widget::KidArea {
         let widget::KidAreaArgs { rect, style, theme, fonts } = args;
@@ -1,13 +1,18 @@
 //! A widget for displaying and mutating multi-line text, given as a `String`.
 
 use {Color, Colorable, FontSize, Positionable, Sizeable, Widget, Ui};
+use color;
 use event;
+use graph;
 use input;
+use input::key_bindings::{self, Action, Movement};
+use input::shortcut::Chord;
 use position::{Align, Dimension, Point, Range, Rect, Scalar};
 use std;
 use text;
 use utils;
 use widget;
+use widget::caret;
 use cursor;
 use widget::primitive::text::Wrap;
 
@@ -16,12 +21,17 @@ use widget::primitive::text::Wrap;
 ///
 /// By default the text is wrapped via the first whitespace before the line exceeds the
 /// `TextEdit`'s width, however a user may change this using the `.wrap_by_character` method.
+///
+/// All matches of a search `Query` may be highlighted via the `.search` method, while the
+/// `.search_step` method selects the next or previous match.
 #[derive(WidgetCommon_)]
 pub struct TextEdit<'a> {
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    search: Option<&'a text::search::Query>,
+    search_step: Option<SearchStep>,
 }
 
 /// Unique graphical styling for the TextEdit.
@@ -51,10 +61,18 @@ pub struct Style {
     /// The font used for the `Text`.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The duration for which the cursor is alternately shown and hidden while the `TextEdit` is
+    /// capturing the keyboard. A duration of zero disables blinking.
+    #[conrod(default = "theme.caret_blink_interval")]
+    pub caret_blink_interval: Option<std::time::Duration>,
+    /// The color used to highlight the matches of the search query.
+    #[conrod(default = "color::YELLOW.alpha(0.35)")]
+    pub match_color: Option<Color>,
 }
 
 widget_ids! {
     struct Ids {
+        match_rectangles[],
         selected_rectangles[],
         text,
         cursor,
@@ -68,6 +86,13 @@ pub struct State {
     drag: Option<Drag>,
     /// Information about each line of text.
     line_infos: Vec<text::line::Info>,
+    /// The moment from which the cursor began blinking, if the keyboard is captured.
+    ///
+    /// This is reset each time the cursor moves or the text changes so that the cursor remains
+    /// visible while editing.
+    blink_start: Option<instant::Instant>,
+    /// The selection begun by a double or triple click, if any.
+    select_by: Option<text::cursor::UnitSelection<text::cursor::Index>>,
     ids: Ids,
 }
 
@@ -81,6 +106,15 @@ pub enum Drag {
     MoveSelection,
 }
 
+/// Moves the selection between the matches of the search query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SearchStep {
+    /// Select the first match following the cursor, wrapping around to the first match.
+    Next,
+    /// Select the last match preceding the cursor, wrapping around to the last match.
+    Previous,
+}
+
 /// The position of the `Cursor` over the text.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Cursor {
@@ -107,9 +141,26 @@ impl<'a> TextEdit<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            search: None,
+            search_step: None,
         }
     }
 
+    /// Highlight all matches of the given search query.
+    pub fn search(mut self, query: &'a text::search::Query) -> Self {
+        self.search = Some(query);
+        self
+    }
+
+    /// Select the next or previous match of the search query, scrolling it into view.
+    ///
+    /// This should only be specified for the update in which the step is requested, e.g. when a
+    /// "Find next" button is pressed.
+    pub fn search_step(mut self, step: SearchStep) -> Self {
+        self.search_step = Some(step);
+        self
+    }
+
     /// The `TextEdit` will wrap text via the whitespace that precedes the first width-exceeding
     /// character.
     ///
@@ -174,6 +225,8 @@ impl<'a> TextEdit<'a> {
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub caret_blink_interval { style.caret_blink_interval = Some(std::time::Duration) }
+        pub match_color { style.match_color = Some(Color) }
     }
 
 }
@@ -192,6 +245,8 @@ impl<'a> Widget for TextEdit<'a> {
             cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
             drag: None,
             line_infos: Vec::new(),
+            blink_start: None,
+            select_by: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -240,7 +295,7 @@ impl<'a> Widget for TextEdit<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let TextEdit { text, .. } = self;
+        let TextEdit { text, search, search_step, .. } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
@@ -357,8 +412,53 @@ impl<'a> Widget for TextEdit<'a> {
             })
         };
 
+        // Find the index reached by applying the given movement to the given cursor index.
+        let move_index = |cursor_idx: text::cursor::Index,
+                          movement: Movement,
+                          text: &str,
+                          line_infos: &[text::line::Info],
+                          font: &text::Font| -> text::cursor::Index
+        {
+            let infos = line_infos.iter().cloned();
+            let line_end = |line: usize| line_infos.get(line).map(|info| {
+                text::cursor::Index { line: line, char: info.end_char() - info.start_char }
+            });
+            match movement {
+                Movement::Left => cursor_idx.previous(infos),
+                Movement::Right => cursor_idx.next(infos),
+                Movement::WordLeft => cursor_idx.previous_word_start(text, infos),
+                Movement::WordRight => cursor_idx.next_word_end(text, infos),
+                Movement::Up | Movement::Down => cursor_xy_at(cursor_idx, text, line_infos, font)
+                    .and_then(|(x_pos, _)| {
+                        let next_line = match movement {
+                            Movement::Up => cursor_idx.line.saturating_sub(1),
+                            _ => cursor_idx.line + 1,
+                        };
+                        closest_cursor_index_on_line(x_pos, next_line, text, line_infos, font)
+                    }),
+                Movement::LineStart => Some(text::cursor::Index { line: cursor_idx.line, char: 0 }),
+                Movement::LineEnd => line_end(cursor_idx.line),
+                Movement::DocumentStart => Some(text::cursor::Index { line: 0, char: 0 }),
+                Movement::DocumentEnd => line_end(line_infos.len().saturating_sub(1)),
+            }.unwrap_or(cursor_idx)
+        };
+
+        // The range of the given unit of text surrounding the given cursor index.
+        fn unit_range(unit: text::cursor::Unit,
+                      cursor_idx: text::cursor::Index,
+                      text: &str,
+                      line_infos: &[text::line::Info]) -> (text::cursor::Index, text::cursor::Index)
+        {
+            let infos = line_infos.iter().cloned();
+            match unit {
+                text::cursor::Unit::Word => cursor_idx.word_range(text, infos),
+                text::cursor::Unit::Paragraph => cursor_idx.paragraph_range(infos),
+            }.unwrap_or((cursor_idx, cursor_idx))
+        }
+
         let mut cursor = state.cursor;
         let mut drag = state.drag;
+        let mut select_by = state.select_by;
 
         // Insert the given `string` at the given `cursor` position within the given `text`.
         //
@@ -432,6 +532,7 @@ impl<'a> Widget for TextEdit<'a> {
         //     - begin dragging selected text.
         // - Left mouse `Drag` for extending the end of the selection, or for dragging selected text.
         // - Key presses for cursor movement.
+        let mut interpreter = key_bindings::Interpreter::new();
         'events: for widget_event in ui.widget_input(id).events() {
             match widget_event {
 
@@ -445,162 +546,102 @@ impl<'a> Widget for TextEdit<'a> {
                         let font = ui.fonts.get(font_id).unwrap();
                         let closest = closest_cursor_index_and_xy(abs_xy, &text, infos, font);
                         if let Some((closest_cursor, _)) = closest {
-                            cursor = Cursor::Idx(closest_cursor);
+                            // A double or triple press selects the surrounding word or paragraph.
+                            let click_count = ui.global_input().click_count;
+                            select_by = text::cursor::Unit::from_click_count(click_count)
+                                .map(|unit| {
+                                    let (start, end) =
+                                        unit_range(unit, closest_cursor, &text, infos);
+                                    text::cursor::UnitSelection { unit: unit, start: start, end: end }
+                                });
+                            cursor = match select_by {
+                                Some(text::cursor::UnitSelection { start, end, .. }) =>
+                                    Cursor::Selection { start: start, end: end },
+                                None => Cursor::Idx(closest_cursor),
+                            };
                         }
 
                         // TODO: Differentiate between Selecting and MoveSelection.
                         drag = Some(Drag::Selecting);
                     }
 
-                    // Check for control keys.
-                    event::Button::Keyboard(key) => match key {
-
-                        // If `Cursor::Idx`, remove the `char` behind the cursor.
-                        // If `Cursor::Selection`, remove the selected text.
-                        input::Key::Backspace | input::Key::Delete => {
-                            let delete_word = press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
-
-                            // Calculate start/end indices of text to remove
-                            let (start, end) = match cursor {
-                                Cursor::Idx(cursor_idx) => {
+                    // Perform the editing action bound to the pressed keys, if any.
+                    event::Button::Keyboard(key) => {
+                        let chord = Chord::new(press.modifiers, key);
+                        let action = match interpreter.press(&ui.theme.key_bindings, chord) {
+                            Some(action) => action,
+                            None => continue 'events,
+                        };
+
+                        match action {
+
+                            // If `Cursor::Idx`, remove the text between the cursor and the end of
+                            // the movement. If `Cursor::Selection`, remove the selected text.
+                            Action::Delete(movement) => {
+                                let font = ui.fonts.get(font_id).unwrap();
+
+                                // Calculate start/end indices of text to remove
+                                let (anchor, head) = cursor.anchor_and_head();
+                                let (start, end) =
+                                    text::cursor::delete_range(anchor, head, movement, |idx, m| {
+                                        move_index(idx, m, &text, &state.line_infos, font)
+                                    });
+
+                                let (start_idx, end_idx) = {
                                     let line_infos = state.line_infos.iter().cloned();
+                                    (text::glyph::index_after_cursor(line_infos.clone(), start),
+                                     text::glyph::index_after_cursor(line_infos, end))
+                                };
 
-                                    let end = match (key, delete_word) {
-                                        (input::Key::Backspace, false) => {
-                                            cursor_idx.previous(line_infos)
-                                        }
-                                        (input::Key::Backspace, true) => {
-                                            cursor_idx.previous_word_start(&text, line_infos)
-                                        }
-                                        (input::Key::Delete, false) => {
-                                            cursor_idx.next(line_infos)
-                                        }
-                                        (input::Key::Delete, true) => {
-                                            cursor_idx.next_word_end(&text, line_infos)
-                                        }
-                                        _ => unreachable!(),
-                                    }.unwrap_or(cursor_idx);
-
-                                    (cursor_idx, end)
+                                if let (Some(start_idx), Some(end_idx)) = (start_idx, end_idx) {
+                                    let (start_idx, end_idx) = (std::cmp::min(start_idx, end_idx),
+                                                                std::cmp::max(start_idx, end_idx));
+
+                                    let new_cursor_char_idx =
+                                        if start_idx > 0 { start_idx } else { 0 };
+                                    let new_cursor_idx = {
+                                        let line_infos = state.line_infos.iter().cloned();
+                                        text::cursor::index_before_char(line_infos,
+                                                                        new_cursor_char_idx)
+                                            .expect("char index was out of range")
+                                    };
+                                    cursor = Cursor::Idx(new_cursor_idx);
+                                    *text.to_mut() = text.chars().take(start_idx)
+                                        .chain(text.chars().skip(end_idx))
+                                        .collect();
+                                    state.update(|state| {
+                                        let w = rect.w();
+                                        state.line_infos =
+                                            line_infos(&text, font, font_size, line_wrap, w)
+                                                .collect();
+                                    });
                                 }
-                                Cursor::Selection { start, end } => (start, end),
-                            };
-
-                            let (start_idx, end_idx) = {
-                                let line_infos = state.line_infos.iter().cloned();
-                                (text::glyph::index_after_cursor(line_infos.clone(), start),
-                                 text::glyph::index_after_cursor(line_infos, end))
-                            };
-
-                            if let (Some(start_idx), Some(end_idx)) = (start_idx, end_idx) {
-                                let (start_idx, end_idx) = (std::cmp::min(start_idx, end_idx),
-                                                            std::cmp::max(start_idx, end_idx));
-
-                                let new_cursor_char_idx =
-                                    if start_idx > 0 { start_idx } else { 0 };
-                                let new_cursor_idx = {
-                                    let line_infos = state.line_infos.iter().cloned();
-                                    text::cursor::index_before_char(line_infos,
-                                                                    new_cursor_char_idx)
-                                        .expect("char index was out of range")
+                            },
+
+                            // Expand the selection (or create a new one).
+                            Action::Select(movement) => {
+                                let font = ui.fonts.get(font_id).unwrap();
+                                let (start, end) = match cursor {
+                                    Cursor::Idx(idx) => (idx, idx),
+                                    Cursor::Selection { start, end } => (start, end),
                                 };
+                                let end = move_index(end, movement, &text, &state.line_infos, font);
+                                cursor = Cursor::Selection { start: start, end: end };
+                            },
+
+                            Action::Move(movement) => {
+                                let font = ui.fonts.get(font_id).unwrap();
+                                // Move from the start/end of the current selection, or simply
+                                // collapse it when moving by a single character or line.
+                                let (anchor, head) = cursor.anchor_and_head();
+                                let new_cursor_idx =
+                                    text::cursor::move_selection(anchor, head, movement, |idx, m| {
+                                        move_index(idx, m, &text, &state.line_infos, font)
+                                    });
                                 cursor = Cursor::Idx(new_cursor_idx);
-                                *text.to_mut() = text.chars().take(start_idx)
-                                    .chain(text.chars().skip(end_idx))
-                                    .collect();
-                                state.update(|state| {
-                                    let font = ui.fonts.get(font_id).unwrap();
-                                    let w = rect.w();
-                                    state.line_infos =
-                                        line_infos(&text, font, font_size, line_wrap, w)
-                                            .collect();
-                                });
-                            }
-                        },
-
-                        input::Key::Left | input::Key::Right | input::Key::Up | input::Key::Down => {
-                            let font = ui.fonts.get(font_id).unwrap();
-                            let move_word = press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
-                            let select = press.modifiers.contains(input::keyboard::ModifierKey::SHIFT);
-
-                            let (old_selection_start, cursor_idx) = match cursor {
-                                Cursor::Idx(idx) => (idx, idx),
-                                Cursor::Selection { start, end } => (start, end),
-                            };
-
-                            let new_cursor_idx = {
-                                let line_infos = state.line_infos.iter().cloned();
-                                match (key, move_word) {
-                                    (input::Key::Left, true) => cursor_idx
-                                        .previous_word_start(&text, line_infos),
-                                    (input::Key::Right, true) => cursor_idx
-                                        .next_word_end(&text, line_infos),
-                                    (input::Key::Left, false) => cursor_idx
-                                        .previous(line_infos),
-                                    (input::Key::Right, false) => cursor_idx
-                                        .next(line_infos),
-
-                                    // Up/Down movement
-                                    _ => cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
-                                        .and_then(|(x_pos, _)| {
-                                            let text::cursor::Index { line, .. } = cursor_idx;
-                                            let next_line = match key {
-                                                input::Key::Up => line.saturating_sub(1),
-                                                input::Key::Down => line + 1,
-                                                _ => unreachable!(),
-                                            };
-                                            closest_cursor_index_on_line(x_pos, next_line, &text, &state.line_infos, font)
-                                        })
-                                }.unwrap_or(cursor_idx)
-                            };
+                            },
 
-                            if select {
-                                // Expand the selection (or create a new one)
-                                cursor = Cursor::Selection {
-                                    start: old_selection_start,
-                                    end: new_cursor_idx,
-                                };
-                            } else {
-                                match cursor {
-                                    Cursor::Idx(_) => {
-                                        cursor = Cursor::Idx(new_cursor_idx);
-                                    },
-                                    Cursor::Selection { start, end } => {
-                                        // Move the cursor to the start/end of the current selection.
-                                        let new_cursor_idx = {
-                                            let cursor_idx = match key {
-                                                input::Key::Left | input::Key::Up =>
-                                                    std::cmp::min(start, end),
-                                                input::Key::Right | input::Key::Down =>
-                                                    std::cmp::max(start, end),
-                                                _ => unreachable!(),
-                                            };
-
-                                            if !move_word {
-                                                cursor_idx
-                                            } else {
-                                                // Move by word from the beginning or end of selection
-                                                let line_infos = state.line_infos.iter().cloned();
-                                                match key {
-                                                    input::Key::Left | input::Key::Up => {
-                                                        cursor_idx.previous_word_start(&text, line_infos)
-                                                    },
-                                                    input::Key::Right | input::Key::Down => {
-                                                        cursor_idx.next_word_end(&text, line_infos)
-                                                    }
-                                                    _ => unreachable!(),
-                                                }.unwrap_or(cursor_idx)
-                                            }
-                                        };
-                                        cursor = Cursor::Idx(new_cursor_idx);
-                                    },
-                                }
-                            }
-                        },
-
-                        input::Key::A => {
-                            // Select all text on Ctrl+a.
-                            if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                            Action::SelectAll => {
                                 let start = text::cursor::Index { line: 0, char: 0 };
                                 let end = {
                                     let line_infos = state.line_infos.iter().cloned();
@@ -608,63 +649,20 @@ impl<'a> Widget for TextEdit<'a> {
                                         .expect("char index was out of range")
                                 };
                                 cursor = Cursor::Selection { start: start, end: end };
-                            }
-                        },
-
-                        input::Key::E => {
-                            // move cursor to end.
-                            if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
-                                let mut line_infos = state.line_infos.iter().cloned();
-                                let line = line_infos.len() - 1;
-                                match line_infos.nth(line) {
-                                    Some(line_info) => {
-                                        let char = line_info.end_char() - line_info.start_char;
-                                        let new_cursor_idx = text::cursor::Index { line: line, char: char };
-                                        cursor = Cursor::Idx(new_cursor_idx);
-                                    },
-                                    _ => (),
+                            },
+
+                            Action::InsertNewline => {
+                                let font = ui.fonts.get(font_id).unwrap();
+                                match insert_text("\n", cursor, &text, &state.line_infos, font) {
+                                    Some((new_text, new_cursor, new_line_infos)) => {
+                                        *text.to_mut() = new_text;
+                                        cursor = new_cursor;
+                                        state.update(|state| state.line_infos = new_line_infos);
+                                    }, _ => ()
                                 }
-                            }
-                        },
+                            },
 
-                        input::Key::End => { // move cursor to end.
-                            let mut line_infos = state.line_infos.iter().cloned();
-                            let line = match cursor {
-                                Cursor::Idx(idx) => idx.line,
-                                Cursor::Selection {end, ..} => end.line, // use last line of selection
-                            };
-                            if let Some(line_info) = line_infos.nth(line) {
-                                let char = line_info.end_char() - line_info.start_char;
-                                let new_cursor_idx = text::cursor::Index { line: line, char: char };
-                                cursor = Cursor::Idx(new_cursor_idx);
-                            }
-                        },
-
-                        input::Key::Home => { // move cursor to beginning.
-                            let mut line_infos = state.line_infos.iter().cloned();
-                            let line = match cursor {
-                                Cursor::Idx(idx) => idx.line,
-                                Cursor::Selection {start, ..} => start.line, // use first line of selection
-                            };
-                            if line_infos.nth(line).is_some() {
-                                let char = 0;
-                                let new_cursor_idx = text::cursor::Index { line: line, char: char };
-                                cursor = Cursor::Idx(new_cursor_idx);
-                            }
-                        },
-
-                        input::Key::Return => {
-                            let font = ui.fonts.get(font_id).unwrap();
-                            match insert_text("\n", cursor, &text, &state.line_infos, font) {
-                                Some((new_text, new_cursor, new_line_infos)) => {
-                                    *text.to_mut() = new_text;
-                                    cursor = new_cursor;
-                                    state.update(|state| state.line_infos = new_line_infos);
-                                }, _ => ()
-                            }
-                        },
-
-                        _ => (),
+                        }
                     },
 
                     _ => (),
@@ -678,26 +676,14 @@ impl<'a> Widget for TextEdit<'a> {
                     }
                 },
 
-                event::Widget::Text(event::Text { string, modifiers }) => {
-                    if modifiers.contains(input::keyboard::ModifierKey::CTRL)
-                    || string.chars().count() == 0
-                    || string.chars().next().is_none() {
-                        continue 'events;
-                    }
-
-                    // Ignore text produced by arrow keys.
-                    //
-                    // TODO: These just happened to be the modifiers for the arrows on OS X, I've
-                    // no idea if they also apply to other platforms. We should definitely see if
-                    // there's a better way to handle this, or whether this should be fixed
-                    // upstream.
-                    match &string[..] {
-                        "\u{f700}" | "\u{f701}" | "\u{f702}" | "\u{f703}" => continue 'events,
-                        _ => ()
-                    }
+                event::Widget::Text(text_event) => {
+                    let string = match interpreter.text(&text_event) {
+                        Some(string) => string,
+                        None => continue 'events,
+                    };
 
                     let font = ui.fonts.get(font_id).unwrap();
-                    match insert_text(&string, cursor, &text, &state.line_infos, font) {
+                    match insert_text(string, cursor, &text, &state.line_infos, font) {
                         Some((new_text, new_cursor, new_line_infos)) => {
                             *text.to_mut() = new_text;
                             cursor = new_cursor;
@@ -719,11 +705,21 @@ impl<'a> Widget for TextEdit<'a> {
                             let infos = &state.line_infos;
                             let font = ui.fonts.get(font_id).unwrap();
                             match closest_cursor_index_and_xy(abs_xy, &text, infos, font) {
-                                Some((end_cursor_idx, _)) =>
-                                    cursor = Cursor::Selection {
+                                Some((end_cursor_idx, _)) => cursor = match select_by {
+                                    None => Cursor::Selection {
                                         start: start_cursor_idx,
                                         end: end_cursor_idx,
                                     },
+
+                                    // Extend the initially selected unit by whole units.
+                                    Some(select_by) => {
+                                        let unit_at_idx =
+                                            unit_range(select_by.unit, end_cursor_idx, &text, infos);
+                                        let (start, end) = select_by.extend(end_cursor_idx,
+                                                                            unit_at_idx);
+                                        Cursor::Selection { start: start, end: end }
+                                    },
+                                },
                                 _ => (),
                             }
                         },
@@ -745,6 +741,43 @@ impl<'a> Widget for TextEdit<'a> {
             ui.set_mouse_cursor(cursor::MouseCursor::Text);
         }
 
+        // Find all matches of the search query within the text.
+        let matches = match search {
+            Some(query) => query.find_all(&text),
+            None => Vec::new(),
+        };
+
+        // Select the next or previous match relative to the cursor, if requested.
+        if let Some(step) = search_step {
+            let infos = &state.line_infos;
+            let (start, end) = match cursor {
+                Cursor::Idx(idx) => (idx, idx),
+                Cursor::Selection { start, end } =>
+                    (std::cmp::min(start, end), std::cmp::max(start, end)),
+            };
+            let char_at = |idx| text::glyph::index_after_cursor(infos.iter().cloned(), idx)
+                .unwrap_or(0);
+            let (start_char, end_char) = (char_at(start), char_at(end));
+            let selected = match step {
+                SearchStep::Next => matches.iter()
+                    .find(|m| m.chars.start >= end_char)
+                    .or(matches.first()),
+                SearchStep::Previous => matches.iter().rev()
+                    .find(|m| m.chars.end <= start_char)
+                    .or(matches.last()),
+            };
+            if let Some(m) = selected {
+                let index_of = |char_idx| {
+                    text::cursor::index_before_char(infos.iter().cloned(), char_idx)
+                        .expect("char index was out of range")
+                };
+                cursor = Cursor::Selection {
+                    start: index_of(m.chars.start),
+                    end: index_of(m.chars.end),
+                };
+            }
+        }
+
         let cursor_has_changed = state.cursor != cursor;
         if cursor_has_changed {
             state.update(|state| state.cursor = cursor);
@@ -754,6 +787,10 @@ impl<'a> Widget for TextEdit<'a> {
             state.update(|state| state.drag = drag);
         }
 
+        if state.select_by != select_by {
+            state.update(|state| state.select_by = select_by);
+        }
+
         // Takes the `String` from the `Cow` if the `Cow` is `Owned`.
         fn take_if_owned(text: std::borrow::Cow<str>) -> Option<String> {
             match text {
@@ -784,62 +821,142 @@ impl<'a> Widget for TextEdit<'a> {
             .font_size(font_size)
             .set(state.ids.text, ui);
 
-        // Draw the line for the cursor.
+        // Highlight each match of the search query, emphasising the selected match.
+        let match_rects: Vec<(Rect, bool)> = {
+            let line_infos = state.line_infos.iter().cloned();
+            let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+            let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
+                                               justify, y_align, line_spacing);
+            let font = ui.fonts.get(font_id).unwrap();
+            let selection = match cursor {
+                Cursor::Idx(_) => None,
+                Cursor::Selection { start, end } =>
+                    Some((std::cmp::min(start, end), std::cmp::max(start, end))),
+            };
+            let mut match_rects = Vec::new();
+            for m in &matches {
+                let index_of = |char_idx| {
+                    text::cursor::index_before_char(line_infos.clone(), char_idx)
+                        .expect("char index was out of range")
+                };
+                let (start, end) = (index_of(m.chars.start), index_of(m.chars.end));
+                let is_selected = selection == Some((start, end));
+                let lines_with_rects = lines.clone().zip(line_rects.clone());
+                let rects = text::line::selected_rects(lines_with_rects, font, font_size,
+                                                       start, end);
+                match_rects.extend(rects.map(|rect| (rect, is_selected)));
+            }
+            match_rects
+        };
+
+        // Ensure we have at least as many widgets as match rectangles.
+        if state.ids.match_rectangles.len() < match_rects.len() {
+            let num_rects = match_rects.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.match_rectangles.resize(num_rects, id_gen));
+        }
+
+        let match_color = style.match_color(ui.theme());
+        let iter = state.ids.match_rectangles.iter().zip(&match_rects);
+        for (&match_rectangle_id, &(match_rect, is_selected)) in iter {
+            let color = if is_selected { match_color.highlighted() } else { match_color };
+            widget::Rectangle::fill(match_rect.dim())
+                .xy(match_rect.xy())
+                .color(color)
+                .graphics_for(id)
+                .parent(id)
+                .set(match_rectangle_id, ui);
+        }
+
         let cursor_idx = match cursor {
             Cursor::Idx(idx) => idx,
             Cursor::Selection { end, .. } => end,
         };
 
-        // If this widget is not capturing the keyboard, no need to draw cursor or selection.
-        if ui.global_input().current.widget_capturing_keyboard != Some(id) {
-            return take_if_owned(text);
-        }
-
-        let (cursor_x, cursor_y_range) = {
+        // The position of the cursor is only required if it is to be drawn or scrolled into view.
+        let is_capturing_keyboard = ui.global_input().current.widget_capturing_keyboard == Some(id);
+        let cursor_position = if is_capturing_keyboard || cursor_has_changed {
             let font = ui.fonts.get(font_id).unwrap();
-            cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
+            let position = cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
                 .unwrap_or_else(|| {
                     let x = rect.left();
                     let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
                     (x, y)
-                })
+                });
+            Some(position)
+        } else {
+            None
+        };
+
+        // If the cursor position has changed due to input or a search step AND one of our parent
+        // widgets are scrollable AND the change in cursor position would cause the cursor to fall
+        // outside the scrollable parent's `Rect`, attempt to scroll the scrollable parent so that
+        // the cursor would be visible.
+        if let (true, Some((cursor_x, cursor_y_range))) = (cursor_has_changed, cursor_position) {
+            let cursor_rect = Rect { x: Range::new(cursor_x, cursor_x), y: cursor_y_range };
+            use graph::Walker;
+            let mut scrollable_parents = ui.widget_graph().scrollable_y_parent_recursion(id);
+            if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
+                if let Some(parent_rect) = ui.rect_of(parent_id) {
+                    // If cursor is below, scroll down.
+                    if cursor_rect.bottom() < parent_rect.bottom() {
+                        let distance = parent_rect.bottom() - cursor_rect.bottom();
+                        ui.scroll_widget(parent_id, [0.0, distance]);
+                    // If cursor is above, scroll up.
+                    } else if cursor_rect.top() > parent_rect.top() {
+                        let distance = cursor_rect.top() - parent_rect.top();
+                        ui.scroll_widget(parent_id, [0.0, -distance]);
+                    }
+                }
+            }
+        }
+
+        // If this widget is not capturing the keyboard, no need to draw cursor or selection.
+        let (cursor_x, cursor_y_range) = match cursor_position {
+            Some(position) if is_capturing_keyboard => position,
+            _ => {
+                if state.blink_start.is_some() {
+                    state.update(|state| state.blink_start = None);
+                }
+                return take_if_owned(text);
+            },
+        };
+
+        // Restart the blink whenever the cursor moves or the text changes.
+        let now = ui.time();
+        let text_has_changed = match text {
+            std::borrow::Cow::Owned(_) => true,
+            std::borrow::Cow::Borrowed(_) => false,
+        };
+        let blink_start = match state.blink_start {
+            Some(start) if !cursor_has_changed && !text_has_changed => start,
+            _ => {
+                state.update(|state| state.blink_start = Some(now));
+                now
+            },
         };
 
+        // Determine whether the cursor is currently visible and when it should next toggle.
+        let blink_interval = style.caret_blink_interval(ui.theme());
+        let cursor_is_visible = match caret::blink_phase(blink_start, now, blink_interval) {
+            Some((is_visible, next_toggle)) => {
+                ui.request_redraw_at(next_toggle);
+                is_visible
+            },
+            None => true,
+        };
+
+        // Draw the line for the cursor.
         let start = [0.0, cursor_y_range.start];
         let end = [0.0, cursor_y_range.end];
-        let prev_cursor_rect = ui.rect_of(state.ids.cursor);
+        let cursor_color = if cursor_is_visible { color } else { color.alpha(0.0) };
         widget::Line::centred(start, end)
             .x_y(cursor_x, cursor_y_range.middle())
             .graphics_for(id)
             .parent(id)
-            .color(color)
+            .color(cursor_color)
             .set(state.ids.cursor, ui);
 
-        // If the cursor position has changed due to input AND one of our parent widgets are
-        // scrollable AND the change in cursor position would cause the cursor to fall outside the
-        // scrollable parent's `Rect`, attempt to scroll the scrollable parent so that the cursor
-        // would be visible.
-        if cursor_has_changed {
-            let cursor_rect = ui.rect_of(state.ids.cursor).unwrap();
-            if prev_cursor_rect != Some(cursor_rect) {
-                use graph::Walker;
-                let mut scrollable_parents = ui.widget_graph().scrollable_y_parent_recursion(id);
-                if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
-                    if let Some(parent_rect) = ui.rect_of(parent_id) {
-                        // If cursor is below, scroll down.
-                        if cursor_rect.bottom() < parent_rect.bottom() {
-                            let distance = parent_rect.bottom() - cursor_rect.bottom();
-                            ui.scroll_widget(parent_id, [0.0, distance]);
-                        // If cursor is above, scroll up.
-                        } else if cursor_rect.top() > parent_rect.top() {
-                            let distance = cursor_rect.top() - parent_rect.top();
-                            ui.scroll_widget(parent_id, [0.0, -distance]);
-                        }
-                    }
-                }
-            }
-        }
-
         if let Cursor::Selection { start, end } = cursor {
             let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
 
@@ -879,6 +996,46 @@ impl<'a> Widget for TextEdit<'a> {
 }
 
 
+impl Cursor {
+
+    /// The end of the selection that remains in place while selecting, along with the logical
+    /// cursor position.
+    fn anchor_and_head(self) -> (text::cursor::Index, text::cursor::Index) {
+        match self {
+            Cursor::Idx(idx) => (idx, idx),
+            Cursor::Selection { start, end } => (start, end),
+        }
+    }
+
+}
+
+
+/// The range of `char`s currently selected within the `TextEdit` with the given `id`.
+///
+/// This is useful for replacing the selected search match via `text::search::Query::replace_at`.
+///
+/// Returns `None` if no `TextEdit` exists for the given `id` or if no text is selected.
+pub fn selected_chars(ui: &Ui, id: widget::Id) -> Option<std::ops::Range<usize>> {
+    let state = match ui.widget_graph().widget(id)
+        .and_then(|container| container.unique_widget_state::<TextEdit>())
+    {
+        Some(&graph::UniqueWidgetState { ref state, .. }) => state,
+        None => return None,
+    };
+    let (start, end) = match state.cursor {
+        Cursor::Idx(_) => return None,
+        Cursor::Selection { start, end } => (std::cmp::min(start, end), std::cmp::max(start, end)),
+    };
+    let line_infos = state.line_infos.iter().cloned();
+    let start_char = text::glyph::index_after_cursor(line_infos.clone(), start);
+    let end_char = text::glyph::index_after_cursor(line_infos, end);
+    match (start_char, end_char) {
+        (Some(start), Some(end)) if start < end => Some(start..end),
+        _ => None,
+    }
+}
+
+
 impl<'a> Colorable for TextEdit<'a> {
     builder_method!(color { style.color = Some(Color) });
 }
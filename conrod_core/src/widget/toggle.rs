@@ -1,7 +1,11 @@
 //! A button that allows for toggling boolean state.
 
-use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Scalar, Widget};
+use {Color, Colorable, Dimensions, FontSize, Borderable, Labelable, Positionable, Scalar, Ui,
+     Widget};
+use animation::Easing;
+use label;
 use position::{self, Align};
+use std;
 use text;
 use widget;
 
@@ -51,6 +55,9 @@ pub struct Style {
     /// The position of the title bar's `Label` widget over the *y* axis.
     #[conrod(default = "position::Relative::Align(Align::Middle)")]
     pub label_y: Option<position::Relative>,
+    /// The duration of the transition between the colors of the on and off states.
+    #[conrod(default = "theme.transition_duration")]
+    pub transition: Option<std::time::Duration>,
 }
 
 widget_ids! {
@@ -143,6 +150,16 @@ impl<'a> Widget for Toggle<'a> {
         self.style.clone()
     }
 
+    /// The measured dimensions of the label along with the border.
+    fn content_dimensions(&self, _id: widget::Id, ui: &Ui) -> Option<Dimensions> {
+        let style = &self.style;
+        self.maybe_label.and_then(|label| {
+            let font_id = style.label_font_id(&ui.theme);
+            let font_size = style.label_font_size(&ui.theme);
+            label::bordered_dimensions(label, font_id, font_size, style.border(&ui.theme), ui)
+        })
+    }
+
     /// Update the state of the Toggle.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -163,6 +180,8 @@ impl<'a> Widget for Toggle<'a> {
             let color = style.color(ui.theme());
             let new_value = times_clicked.clone().last().unwrap_or(value);
             let color = if new_value { color } else { color.with_luminance(0.1) };
+            let transition = style.transition(ui.theme());
+            let color = ui.animate(id, "color", color, transition, Easing::QuadOut);
             match ui.widget_input(id).mouse() {
                 Some(mouse) =>
                     if mouse.buttons.left().is_down() { color.clicked() }
@@ -0,0 +1,255 @@
+//! A small floating label that explains the widget beneath the mouse cursor.
+
+use {Color, Colorable, FontSize, Borderable, Labelable, Point, Positionable, Rect, Sizeable, Theme};
+use event;
+use graph;
+use position::{Dimensions, Scalar};
+use std;
+use text;
+use utils;
+use widget::{self, Widget};
+
+
+/// A small floating label that is displayed near the mouse cursor once it has hovered over the
+/// `target` widget for the `delay` duration.
+///
+/// The tooltip is hidden again as soon as the mouse leaves the target, or as soon as any button
+/// is pressed or some scrolling occurs. It will not reappear until the mouse leaves the target
+/// and returns.
+///
+/// The **Tooltip** itself occupies the same area as its target so that it does not disturb the
+/// positioning of any widgets that are instantiated after it. It is never picked as the widget
+/// under the mouse. The visible label is drawn within a floating rectangle on top of all other
+/// widgets and is clamped so that it always stays within the window.
+#[derive(Clone, WidgetCommon_)]
+pub struct Tooltip<'a> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Tooltip**.
+    pub style: Style,
+    /// The text displayed within the **Tooltip**.
+    pub text: &'a str,
+    /// The widget whose hovering triggers the **Tooltip**.
+    pub target: widget::Id,
+}
+
+/// Unique styling for the **Tooltip** widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the tooltip's rectangle surface.
+    #[conrod(default = "theme.background_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the tooltip's rectangle.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the tooltip's border.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the tooltip's text.
+    #[conrod(default = "theme.label_color")]
+    pub text_color: Option<Color>,
+    /// The font size for the tooltip's text.
+    #[conrod(default = "theme.font_size_small")]
+    pub font_size: Option<FontSize>,
+    /// The font used for the tooltip's text.
+    #[conrod(default = "theme.font_id")]
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The distance between lines for multi-line tooltip text.
+    #[conrod(default = "1.0")]
+    pub line_spacing: Option<Scalar>,
+    /// The padding between the edge of the tooltip's rectangle and its text.
+    #[conrod(default = "4.0")]
+    pub padding: Option<Scalar>,
+    /// The duration for which the target must be hovered before the tooltip appears.
+    #[conrod(default = "theme.tooltip_delay")]
+    pub delay: Option<std::time::Duration>,
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        text,
+    }
+}
+
+/// Unique state for the **Tooltip** widget.
+pub struct State {
+    ids: Ids,
+    /// The position of the mouse at the moment the tooltip appeared, if it is visible.
+    maybe_anchor: Option<Point>,
+    /// Whether or not the tooltip was dismissed by a press or scroll during the current hover.
+    dismissed: bool,
+}
+
+/// The distance between the mouse cursor and the nearest corner of the tooltip's rectangle.
+const CURSOR_OFFSET: Scalar = 12.0;
+
+
+impl<'a> Tooltip<'a> {
+
+    /// Construct a new **Tooltip** displaying the given `text` when the `target` widget is hovered.
+    pub fn new(text: &'a str, target: widget::Id) -> Self {
+        Tooltip {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            text: text,
+            target: target,
+        }.xy_relative_to(target, [0.0, 0.0]).wh_of(target)
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// The duration for which the target must be hovered before the tooltip appears.
+    pub fn delay(mut self, delay: std::time::Duration) -> Self {
+        self.style.delay = Some(delay);
+        self
+    }
+
+    builder_methods!{
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+}
+
+
+impl<'a> Widget for Tooltip<'a> {
+    type State = State;
+    type Style = Style;
+    /// Whether or not the tooltip is currently visible.
+    type Event = bool;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            maybe_anchor: None,
+            dismissed: false,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        fn is_over_widget(_: &graph::Container, _: Point, _: &Theme) -> widget::IsOver {
+            false.into()
+        }
+        is_over_widget
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, ui, .. } = args;
+        let Tooltip { text, target, .. } = self;
+
+        // The mouse is still hovering if it is over the target, or if it has somehow wandered
+        // onto the tooltip's own graphics.
+        let hovered = ui.hovered_widget();
+        let is_hovering = hovered == Some(target) || hovered == Some(id);
+
+        // Any press or scroll dismisses the tooltip until the next time the target is hovered.
+        let is_dismissing = ui.global_input().events().ui().any(|event| match *event {
+            event::Ui::Press(_, _) | event::Ui::Scroll(_, _) => true,
+            _ => false,
+        });
+
+        let (maybe_anchor, dismissed) = if !is_hovering {
+            (None, false)
+        } else if is_dismissing || state.dismissed {
+            (None, true)
+        } else if state.maybe_anchor.is_some() {
+            (state.maybe_anchor, false)
+        } else {
+            let delay = style.delay(&ui.theme);
+            let maybe_anchor = match ui.hover_duration_of(target) {
+                Some(hovered) if hovered >= delay => Some(ui.global_input().current.mouse.xy),
+                // Ensure we are updated once the delay has passed, even if the mouse is still.
+                Some(hovered) => {
+                    ui.request_redraw_after(delay - hovered);
+                    None
+                },
+                None => None,
+            };
+            (maybe_anchor, false)
+        };
+
+        if state.maybe_anchor != maybe_anchor || state.dismissed != dismissed {
+            state.update(|state| {
+                state.maybe_anchor = maybe_anchor;
+                state.dismissed = dismissed;
+            });
+        }
+
+        let anchor = match maybe_anchor {
+            Some(anchor) => anchor,
+            None => return false,
+        };
+
+        let label_style = widget::floating_label::Style {
+            color: style.color(&ui.theme),
+            border: style.border(&ui.theme),
+            border_color: style.border_color(&ui.theme),
+            text_color: style.text_color(&ui.theme),
+            font_size: style.font_size(&ui.theme),
+            maybe_font_id: style.font_id(&ui.theme),
+            line_spacing: style.line_spacing(&ui.theme),
+            padding: style.padding(&ui.theme),
+        };
+        widget::floating_label::set(text, anchor, label_style, id,
+                                    state.ids.rectangle, state.ids.text, ui);
+
+        true
+    }
+
+}
+
+
+/// Determine the position of a tooltip with the given dimensions so that it sits below and to
+/// the right of the cursor at `anchor`.
+///
+/// If the tooltip would extend beyond the edge of the window, it is flipped to the opposite side
+/// of the cursor along that axis and then clamped to the window bounds.
+pub fn position_near_cursor(anchor: Point, dim: Dimensions, window_dim: Dimensions) -> Point {
+    let window = Rect::from_xy_dim([0.0, 0.0], window_dim);
+
+    let mut x = anchor[0] + CURSOR_OFFSET + dim[0] / 2.0;
+    if x + dim[0] / 2.0 > window.right() {
+        x = anchor[0] - CURSOR_OFFSET - dim[0] / 2.0;
+    }
+    let mut y = anchor[1] - CURSOR_OFFSET - dim[1] / 2.0;
+    if y - dim[1] / 2.0 < window.bottom() {
+        y = anchor[1] + CURSOR_OFFSET + dim[1] / 2.0;
+    }
+
+    let clamp = |v: Scalar, half: Scalar, start: Scalar, end: Scalar| {
+        utils::partial_max(start + half, utils::partial_min(v, end - half))
+    };
+    let x = clamp(x, dim[0] / 2.0, window.left(), window.right());
+    let y = clamp(y, dim[1] / 2.0, window.bottom(), window.top());
+    [x, y]
+}
+
+
+impl<'a> Colorable for Tooltip<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for Tooltip<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
+
+impl<'a> Labelable<'a> for Tooltip<'a> {
+    builder_methods!{
+        label { text = &'a str }
+        label_color { style.text_color = Some(Color) }
+        label_font_size { style.font_size = Some(FontSize) }
+    }
+}
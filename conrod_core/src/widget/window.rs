@@ -0,0 +1,446 @@
+//! A floating sub-window that may be moved, resized, maximized, minimized and closed.
+
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Widget};
+use color;
+use cursor;
+use position::{Axis, Padding, Range, Rect, Scalar, SizeLimits};
+use widget;
+use widget::title_bar;
+
+
+/// A floating sub-window with a title bar, resize handles and window buttons.
+///
+/// The **Window** does not move or resize itself. Instead, it produces an `Event::Geometry`
+/// describing the rectangle that the user has requested, which the application should use to
+/// position and size the **Window** on the next update. The window may be moved by dragging its
+/// title bar and resized by dragging any of its edges or corners.
+///
+/// The **Window** is floating by default, and is brought to the front whenever it, or any widget
+/// placed upon it, is clicked. Child widgets are placed within the area below the title bar.
+///
+/// A modal **Window** opens a modal layer (see `widget::Modal`), blocking interaction with the
+/// rest of the `Ui` for as long as it is set.
+#[derive(Clone, WidgetCommon_)]
+pub struct Window<'a> {
+    /// Common widget building params for the **Window**.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Window**.
+    pub style: Style,
+    title: &'a str,
+    keep_inside_parent: bool,
+    is_modal: bool,
+    is_resizable: bool,
+}
+
+/// Unique styling for the **Window**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the window's surface.
+    #[conrod(default = "theme.background_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the window.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the border surrounding the window.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the title bar.
+    #[conrod(default = "theme.shape_color")]
+    pub title_bar_color: Option<Color>,
+    /// The color of the title bar's text.
+    #[conrod(default = "theme.label_color")]
+    pub title_bar_text_color: Option<Color>,
+    /// The font size of the title bar's text.
+    #[conrod(default = "theme.font_size_medium")]
+    pub title_bar_font_size: Option<FontSize>,
+    /// The thickness of the resize handles along each edge of the window.
+    #[conrod(default = "4.0")]
+    pub resize_margin: Option<Scalar>,
+    /// The color of the backdrop drawn behind a modal window.
+    #[conrod(default = "color::BLACK.alpha(0.4)")]
+    pub backdrop_color: Option<Color>,
+}
+
+widget_ids! {
+    struct Ids {
+        backdrop,
+        rectangle,
+        title_bar,
+        minimize,
+        maximize,
+        close,
+        grips[],
+    }
+}
+
+/// Represents the state of the **Window** widget.
+pub struct State {
+    ids: Ids,
+    /// The grip being dragged along with the window's rectangle at the beginning of the drag.
+    maybe_drag: Option<(Grip, Rect)>,
+    /// The rectangle to restore when a maximized window is restored.
+    maybe_restore: Option<Rect>,
+}
+
+/// The events produced by the **Window**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The user moved, resized, maximized or restored the window, producing its new rectangle.
+    Geometry(Rect),
+    /// The minimize button was pressed.
+    Minimize,
+    /// The maximize button was pressed while the window was not maximized.
+    Maximize,
+    /// The maximize button was pressed while the window was maximized.
+    Restore,
+    /// The close button was pressed.
+    Close,
+}
+
+/// The part of a window that may be dragged.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Grip {
+    /// The title bar, which moves the window.
+    TitleBar,
+    /// The left edge.
+    Left,
+    /// The right edge.
+    Right,
+    /// The bottom edge.
+    Bottom,
+    /// The top edge.
+    Top,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The bottom-right corner.
+    BottomRight,
+    /// The top-left corner.
+    TopLeft,
+    /// The top-right corner.
+    TopRight,
+}
+
+/// The resize handles in the order in which their widget ids are generated.
+const RESIZE_GRIPS: [Grip; 8] = [
+    Grip::Left, Grip::Right, Grip::Bottom, Grip::Top,
+    Grip::BottomLeft, Grip::BottomRight, Grip::TopLeft, Grip::TopRight,
+];
+
+/// The padding between the title bar's buttons and the edges of the title bar.
+const BUTTON_PADDING: Scalar = 2.0;
+
+
+impl<'a> Window<'a> {
+
+    /// Begin building a floating **Window** with the given title.
+    pub fn new(title: &'a str) -> Self {
+        Window {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            title: title,
+            keep_inside_parent: false,
+            is_modal: false,
+            is_resizable: true,
+        }.floating(true)
+    }
+
+    builder_methods!{
+        pub keep_inside_parent { keep_inside_parent = bool }
+        pub modal { is_modal = bool }
+        pub resizable { is_resizable = bool }
+        pub title_bar_color { style.title_bar_color = Some(Color) }
+        pub title_bar_text_color { style.title_bar_text_color = Some(Color) }
+        pub title_bar_font_size { style.title_bar_font_size = Some(FontSize) }
+        pub resize_margin { style.resize_margin = Some(Scalar) }
+        pub backdrop_color { style.backdrop_color = Some(Color) }
+    }
+
+}
+
+impl<'a> Widget for Window<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Vec<Event>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            maybe_drag: None,
+            maybe_restore: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// The area of the window within its resize margin and below its title bar.
+    fn kid_area(&self, args: widget::KidAreaArgs<Self>) -> widget::KidArea {
+        let widget::KidAreaArgs { rect, style, theme, .. } = args;
+        let margin = style.resize_margin(theme);
+        let title_bar_h = title_bar::calc_height(style.title_bar_font_size(theme));
+        widget::KidArea {
+            rect: rect.pad(margin).pad_top(title_bar_h),
+            pad: Padding::none(),
+        }
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let limits = self.get_size_limits();
+        let Window { title, keep_inside_parent, is_modal, is_resizable, .. } = self;
+
+        if state.ids.grips.len() < RESIZE_GRIPS.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.grips.resize(RESIZE_GRIPS.len(), id_gen));
+        }
+
+        // The area within which the window is maximized and, optionally, kept.
+        let win_rect = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+        let bounds = ui.widget_graph().depth_parent(id)
+            .and_then(|parent| ui.kid_area_of(parent))
+            .unwrap_or(win_rect);
+
+        // A modal window opens a modal layer, routing all input to the window and its children.
+        if is_modal {
+            ui.open_modal(id);
+            widget::Rectangle::fill(win_rect.dim())
+                .xy(win_rect.xy())
+                .color(style.backdrop_color(&ui.theme))
+                .depth(1.0)
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.backdrop, ui);
+        }
+
+        widget::BorderedRectangle::new(rect.dim())
+            .color(style.color(&ui.theme))
+            .border(style.border(&ui.theme))
+            .border_color(style.border_color(&ui.theme))
+            .middle_of(id)
+            .graphics_for(id)
+            .set(state.ids.rectangle, ui);
+
+        // The title bar, along with its buttons.
+        let margin = style.resize_margin(&ui.theme);
+        let font_size = style.title_bar_font_size(&ui.theme);
+        let title_bar_h = title_bar::calc_height(font_size);
+        let title_bar_rect = Rect {
+            x: Range::new(rect.left() + margin, rect.right() - margin),
+            y: Range::new(rect.top() - margin - title_bar_h, rect.top() - margin),
+        };
+        let text_color = style.title_bar_text_color(&ui.theme);
+        widget::TitleBar::new(title, id)
+            .left_justify_label()
+            .color(style.title_bar_color(&ui.theme))
+            .label_color(text_color)
+            .label_font_size(font_size)
+            .wh(title_bar_rect.dim())
+            .xy(title_bar_rect.xy())
+            .parent(id)
+            .set(state.ids.title_bar, ui);
+
+        let mut events = Vec::new();
+        let button_side = title_bar_h - BUTTON_PADDING * 2.0;
+        let buttons = [
+            (state.ids.close, "x"),
+            (state.ids.maximize, "+"),
+            (state.ids.minimize, "-"),
+        ];
+        for (i, &(button_id, label)) in buttons.iter().enumerate() {
+            let x = title_bar_rect.right()
+                - (BUTTON_PADDING + button_side) * i as Scalar
+                - BUTTON_PADDING - button_side / 2.0;
+            let clicks = widget::Button::new()
+                .label(label)
+                .label_color(text_color)
+                .label_font_size(font_size)
+                .color(style.title_bar_color(&ui.theme))
+                .w_h(button_side, button_side)
+                .x_y(x, title_bar_rect.y.middle())
+                .parent(state.ids.title_bar)
+                .set(button_id, ui);
+            for _click in clicks {
+                if button_id == state.ids.close {
+                    events.push(Event::Close);
+                } else if button_id == state.ids.minimize {
+                    events.push(Event::Minimize);
+                } else {
+                    match state.maybe_restore {
+                        Some(restore) => {
+                            state.update(|state| state.maybe_restore = None);
+                            events.push(Event::Geometry(restore));
+                            events.push(Event::Restore);
+                        },
+                        None => {
+                            state.update(|state| state.maybe_restore = Some(rect));
+                            events.push(Event::Geometry(bounds));
+                            events.push(Event::Maximize);
+                        },
+                    }
+                }
+            }
+        }
+
+        // The resize handles along each edge and corner.
+        let corner = margin * 3.0;
+        let mut grips = vec![(Grip::TitleBar, state.ids.title_bar)];
+        if is_resizable {
+            for (i, &grip) in RESIZE_GRIPS.iter().enumerate() {
+                let grip_id = state.ids.grips[i];
+                let grip_rect = grip_rect(rect, grip, margin, corner);
+                widget::Rectangle::fill(grip_rect.dim())
+                    .xy(grip_rect.xy())
+                    .color(color::TRANSPARENT)
+                    .parent(id)
+                    .set(grip_id, ui);
+                grips.push((grip, grip_id));
+            }
+        }
+
+        // Move or resize the window in response to dragging its title bar or resize handles.
+        let min_dim = [
+            margin * 2.0 + (BUTTON_PADDING + button_side) * 3.0 + BUTTON_PADDING,
+            margin * 2.0 + title_bar_h,
+        ];
+        let mut new_rect = rect;
+        for &(grip, grip_id) in &grips {
+            for drag in ui.widget_input(grip_id).drags().left() {
+                let start = match state.maybe_drag {
+                    Some((g, start)) if g == grip => start,
+                    _ => {
+                        state.update(|state| state.maybe_drag = Some((grip, rect)));
+                        rect
+                    },
+                };
+                new_rect = drag_rect(start, grip, drag.total_delta_xy, min_dim, &limits);
+                if keep_inside_parent {
+                    new_rect = match grip {
+                        Grip::TitleBar => shift_inside(new_rect, bounds),
+                        _ => new_rect.overlap(bounds).unwrap_or(new_rect),
+                    };
+                }
+            }
+            if ui.widget_input(grip_id).releases().mouse().left().next().is_some() {
+                state.update(|state| state.maybe_drag = None);
+            }
+
+            let is_dragging = state.maybe_drag.map_or(false, |(g, _)| g == grip);
+            if grip != Grip::TitleBar && (is_dragging || ui.widget_input(grip_id).mouse().is_some()) {
+                ui.set_mouse_cursor(resize_cursor(grip));
+            }
+        }
+        if new_rect != rect {
+            if state.maybe_restore.is_some() {
+                state.update(|state| state.maybe_restore = None);
+            }
+            events.push(Event::Geometry(new_rect));
+        }
+
+        events
+    }
+
+}
+
+
+/// The rectangle occupied by the given resize handle.
+///
+/// The top and bottom edges span the full width of the window, with a `corner` length at either
+/// end acting as the corner handles. The left and right edges span the height between them.
+fn grip_rect(rect: Rect, grip: Grip, margin: Scalar, corner: Scalar) -> Rect {
+    let left = Range::new(rect.left(), rect.left() + margin);
+    let right = Range::new(rect.right() - margin, rect.right());
+    let bottom = Range::new(rect.bottom(), rect.bottom() + margin);
+    let top = Range::new(rect.top() - margin, rect.top());
+    let side = Range::new(rect.bottom() + margin, rect.top() - margin);
+    let middle = Range::new(rect.left() + corner, rect.right() - corner);
+    let left_corner = Range::new(rect.left(), rect.left() + corner);
+    let right_corner = Range::new(rect.right() - corner, rect.right());
+    let (x, y) = match grip {
+        Grip::TitleBar => (rect.x, rect.y),
+        Grip::Left => (left, side),
+        Grip::Right => (right, side),
+        Grip::Bottom => (middle, bottom),
+        Grip::Top => (middle, top),
+        Grip::BottomLeft => (left_corner, bottom),
+        Grip::BottomRight => (right_corner, bottom),
+        Grip::TopLeft => (left_corner, top),
+        Grip::TopRight => (right_corner, top),
+    };
+    Rect { x: x, y: y }
+}
+
+/// The mouse cursor to display while hovering or dragging the given resize handle.
+fn resize_cursor(grip: Grip) -> cursor::MouseCursor {
+    match grip {
+        Grip::Left | Grip::Right => cursor::MouseCursor::ResizeHorizontal,
+        Grip::Bottom | Grip::Top => cursor::MouseCursor::ResizeVertical,
+        Grip::TopLeft | Grip::BottomRight => cursor::MouseCursor::ResizeTopLeftBottomRight,
+        Grip::TopRight | Grip::BottomLeft => cursor::MouseCursor::ResizeTopRightBottomLeft,
+        Grip::TitleBar => cursor::MouseCursor::Grabbing,
+    }
+}
+
+/// The rectangle resulting from dragging the given grip of the `start` rectangle by `delta`.
+///
+/// Resizing moves only the dragged edges, keeping the window at least `min_dim` in size and within
+/// the given size limits.
+pub fn drag_rect(start: Rect,
+                 grip: Grip,
+                 delta: [Scalar; 2],
+                 min_dim: [Scalar; 2],
+                 limits: &SizeLimits) -> Rect
+{
+    if grip == Grip::TitleBar {
+        return start.shift(delta);
+    }
+
+    let (left, right) = match grip {
+        Grip::Left | Grip::BottomLeft | Grip::TopLeft => (true, false),
+        Grip::Right | Grip::BottomRight | Grip::TopRight => (false, true),
+        _ => (false, false),
+    };
+    let (bottom, top) = match grip {
+        Grip::Bottom | Grip::BottomLeft | Grip::BottomRight => (true, false),
+        Grip::Top | Grip::TopLeft | Grip::TopRight => (false, true),
+        _ => (false, false),
+    };
+    let resize = |range: Range, axis: Axis, start_edge: bool, end_edge: bool, delta: Scalar| {
+        let len = match (start_edge, end_edge) {
+            (true, _) => range.len() - delta,
+            (_, true) => range.len() + delta,
+            _ => return range,
+        };
+        let min = min_dim[match axis { Axis::X => 0, Axis::Y => 1 }];
+        let len = limits.clamp(axis, if len < min { min } else { len });
+        match start_edge {
+            true => Range::new(range.end - len, range.end),
+            false => Range::new(range.start, range.start + len),
+        }
+    };
+    Rect {
+        x: resize(start.x, Axis::X, left, right, delta[0]),
+        y: resize(start.y, Axis::Y, bottom, top, delta[1]),
+    }
+}
+
+/// Shift the rectangle so that it lies within the given bounds where possible.
+///
+/// If the rectangle is larger than the bounds, its left and top edges are kept within them.
+fn shift_inside(rect: Rect, bounds: Rect) -> Rect {
+    let shift = |range: Range, bounds: Range, keep_end: bool| {
+        let over_end = range.end - bounds.end;
+        let range = if over_end > 0.0 { range.shift(-over_end) } else { range };
+        let under_start = bounds.start - range.start;
+        let range = if under_start > 0.0 { range.shift(under_start) } else { range };
+        match keep_end && range.end > bounds.end {
+            true => range.shift(bounds.end - range.end),
+            false => range,
+        }
+    };
+    Rect {
+        x: shift(rect.x, bounds.x, false),
+        y: shift(rect.y, bounds.y, true),
+    }
+}